@@ -13,12 +13,15 @@
 mod bar_chart;
 mod base;
 mod box_plot_chart;
+mod bullet_chart;
 mod calendar_chart;
 mod candlestick_chart;
 mod canvas;
 mod color;
+mod color_scheme;
 mod common;
 mod component;
+mod date;
 #[cfg(feature = "raster")]
 mod encoder;
 mod error;
@@ -28,15 +31,20 @@ mod gauge_chart;
 mod graph_chart;
 mod heatmap_chart;
 mod horizontal_bar_chart;
+mod label;
 mod line_chart;
 mod multi_chart;
+mod palette;
 mod parallel_chart;
 mod params;
 mod path;
 mod pie_chart;
+mod polar_bar_chart;
 mod radar_chart;
 mod sankey_chart;
 mod scatter_chart;
+mod sparkline_chart;
+mod stat_chart;
 mod sunburst_chart;
 mod table_chart;
 mod theme;
@@ -49,6 +57,7 @@ mod waterfall_chart;
 pub use bar_chart::BarChart;
 pub use base::ChartBase;
 pub use box_plot_chart::{BoxPlotChart, BoxPlotSeries};
+pub use bullet_chart::{BulletChart, BulletData};
 pub use canvas::Canvas;
 pub use color::*;
 pub use common::*;
@@ -67,20 +76,29 @@ pub use font::{
     measure_text_width_family,
 };
 pub use funnel_chart::FunnelChart;
-pub use gauge_chart::GaugeChart;
+pub use gauge_chart::{GaugeChart, GaugeColorBand};
 pub use graph_chart::{GraphChart, GraphLink, GraphNode};
 pub use heatmap_chart::{HeatmapChart, HeatmapData, HeatmapSeries};
 pub use horizontal_bar_chart::HorizontalBarChart;
 pub use line_chart::LineChart;
 pub use multi_chart::{ChildChart, MultiChart};
-pub use parallel_chart::ParallelChart;
+pub use palette::{
+    ColorVisionDeficiency, Palette, PaletteKind, PaletteWarning, check_palette, distinct_colors,
+};
+pub use parallel_chart::{ParallelAxis, ParallelChart};
 pub use path::*;
-pub use pie_chart::PieChart;
+pub use pie_chart::{PieChart, PieRing};
+pub use polar_bar_chart::PolarBarChart;
 pub use radar_chart::{RadarChart, RadarIndicator};
 pub use sankey_chart::{SankeyChart, SankeyLink, SankeyNode};
 pub use scatter_chart::ScatterChart;
-pub use sunburst_chart::{SunburstChart, SunburstData};
-pub use table_chart::{TableCellStyle, TableChart};
+pub use sparkline_chart::SparklineChart;
+pub use stat_chart::StatChart;
+pub use sunburst_chart::{SunburstChart, SunburstData, SunburstLevel};
+pub use table_chart::{
+    TableAggregation, TableCellCondition, TableCellRenderer, TableCellRule, TableCellStyle,
+    TableChart, TableMergedCell,
+};
 pub use theme::Theme;
 pub use theme::{
     THEME_ANT, THEME_CHALK, THEME_DARK, THEME_GRAFANA, THEME_LIGHT, THEME_SHADCN, THEME_SHINE,
@@ -120,6 +138,7 @@ macro_rules! impl_chart {
 impl_chart!(
     BarChart,
     BoxPlotChart,
+    BulletChart,
     CalendarChart,
     CandlestickChart,
     FunnelChart,
@@ -131,9 +150,12 @@ impl_chart!(
     MultiChart,
     ParallelChart,
     PieChart,
+    PolarBarChart,
     RadarChart,
     SankeyChart,
     ScatterChart,
+    SparklineChart,
+    StatChart,
     SunburstChart,
     TableChart,
     ThemeRiverChart,
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::common::*;
 use super::component::*;
@@ -34,6 +34,19 @@ pub struct BarChart {
 
     /// Corner radius of the bars.
     pub radius: Option<f32>,
+
+    /// Share (0–0.9) of every category's width left empty around its bars;
+    /// `None` keeps a 5px margin on each side.
+    pub bar_category_gap: Option<f32>,
+
+    /// Gap between the bars (or stacks) of one category as a share of the
+    /// bar width, negative values overlap them; `None` keeps a 3px gap.
+    pub bar_gap: Option<f32>,
+
+    /// Group of every category, e.g. the quarter of each month. Consecutive
+    /// equal names form one group, drawn as a second row of x axis labels
+    /// with separators between the groups.
+    pub x_axis_group_data: Vec<String>,
 }
 
 impl std::ops::Deref for BarChart {
@@ -58,6 +71,15 @@ impl BarChart {
         if let Some(radius) = get_f32_from_value(&value, "radius") {
             b.radius = Some(radius);
         }
+        if let Some(bar_category_gap) = get_f32_from_value(&value, "bar_category_gap") {
+            b.bar_category_gap = Some(bar_category_gap);
+        }
+        if let Some(bar_gap) = get_f32_from_value(&value, "bar_gap") {
+            b.bar_gap = Some(bar_gap);
+        }
+        if let Some(x_axis_group_data) = get_string_slice_from_value(&value, "x_axis_group_data") {
+            b.x_axis_group_data = x_axis_group_data;
+        }
         Ok(b)
     }
     /// Creates a bar chart with custom theme.
@@ -90,18 +112,69 @@ impl BarChart {
     pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> BarChart {
         BarChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
     }
+    /// Renders the group row of a two-level x axis: every run of equal
+    /// group names gets one centered label under its categories, and the
+    /// groups are separated by lines through both label rows.
+    fn render_x_axis_group(&self, c: Canvas, axis_width: f32) {
+        let mut c = c;
+        let count = self.x_axis_data.len();
+        if count == 0 {
+            return;
+        }
+        let unit_width = axis_width / count as f32;
+        let mut groups: Vec<(&str, usize, usize)> = vec![];
+        for (index, name) in self.x_axis_group_data.iter().take(count).enumerate() {
+            match groups.last_mut() {
+                Some((last, _, end)) if *last == name.as_str() => *end = index + 1,
+                _ => groups.push((name, index, index + 1)),
+            }
+        }
+        let row_height = self.x_axis_height;
+        for (index, (name, start, end)) in groups.iter().enumerate() {
+            let left = unit_width * *start as f32;
+            let right = unit_width * *end as f32;
+            if index != 0 {
+                c.line(Line {
+                    color: Some(self.x_axis_stroke_color),
+                    left,
+                    top: 0.0,
+                    right: left,
+                    bottom: row_height * 2.0,
+                    ..Default::default()
+                });
+            }
+            c.text(Text {
+                text: name.to_string(),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.x_axis_font_color),
+                font_size: Some(self.x_axis_font_size),
+                font_weight: self.x_axis_font_weight.clone(),
+                x: Some((left + right) / 2.0),
+                y: Some(row_height * 1.5),
+                text_anchor: Some("middle".to_string()),
+                dominant_baseline: Some("central".to_string()),
+                ..Default::default()
+            });
+        }
+    }
     /// Converts bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let mut x_axis_height = self.x_axis_height;
         if self.x_axis_hidden {
             x_axis_height = 0.0;
+        } else if !self.x_axis_group_data.is_empty() {
+            // The group labels take a second row below the category labels.
+            x_axis_height += self.x_axis_height;
         }
         let axis_top = self.render_header(&mut c);
 
         let (left_y_axis_values, mut left_y_axis_width) =
-            self.get_y_axis_values(&self.y_axis_configs, 0);
+            self.get_y_axis_values(&self.y_axis_configs, 0)?;
         if self.y_axis_hidden {
             left_y_axis_width = 0.0;
         }
@@ -116,7 +189,7 @@ impl BarChart {
         let mut right_y_axis_width = 0.0_f32;
         if !self.y_axis_hidden && exist_right_y_axis {
             (right_y_axis_values, right_y_axis_width) =
-                self.get_y_axis_values(&self.y_axis_configs, 1);
+                self.get_y_axis_values(&self.y_axis_configs, 1)?;
         }
 
         let axis_height = c.height() - x_axis_height - axis_top;
@@ -177,6 +250,17 @@ impl BarChart {
                 self.x_axis_data.clone(),
                 axis_width,
             );
+            if !self.x_axis_group_data.is_empty() {
+                self.render_x_axis_group(
+                    c.child(Box {
+                        top: c.height() - x_axis_height,
+                        left: left_y_axis_width,
+                        right: right_y_axis_width,
+                        ..Default::default()
+                    }),
+                    axis_width,
+                );
+            }
         }
 
         // bar point
@@ -206,6 +290,7 @@ impl BarChart {
             max_height,
             self.x_axis_data.len(),
             self.radius,
+            (self.bar_category_gap, self.bar_gap),
             self.animation.as_ref(),
             self.tooltip_show,
         );
@@ -221,6 +306,7 @@ impl BarChart {
             max_height,
             axis_height,
             self.x_axis_data.len(),
+            &StackBaseline::Zero,
             None,
             self.tooltip_show,
         );
@@ -1045,6 +1131,52 @@ mod tests {
         assert!(chart.svg().is_ok(), "empty series_colors must not panic");
     }
 
+    #[test]
+    fn invalid_color_error() {
+        for (option, message) in [
+            (
+                r#""title_font_color": "reddish""#,
+                r#"color "reddish" of "title_font_color" is invalid"#,
+            ),
+            (
+                r##""series_colors": ["#5470c6", 3]"##,
+                r#"color 3 of "series_colors" is invalid"#,
+            ),
+            (
+                r##""y_axis_configs": [{"axis_font_color": "#12345"}]"##,
+                r##"color "#12345" of "axis_font_color" is invalid"##,
+            ),
+            (
+                r#""series_list": [{"name": "A", "data": [1.0], "fill": "blue-ish"}]"#,
+                r#"color "blue-ish" of "fill" is invalid"#,
+            ),
+        ] {
+            let json = format!(r#"{{{option}, "x_axis_data": ["x"]}}"#);
+            assert_eq!(
+                format!("Params is invalid: {message}"),
+                BarChart::from_json(&json).unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_stack_mode_error() {
+        let err = BarChart::from_json(r#"{"series_stack_mode": "percentage"}"#).unwrap_err();
+        assert_eq!(
+            r#"Params is invalid: stack mode "percentage" of "series_stack_mode" is invalid"#,
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_label_overlap_error() {
+        let err = BarChart::from_json(r#"{"series_label_overlap": "avoid"}"#).unwrap_err();
+        assert_eq!(
+            r#"Params is invalid: label overlap "avoid" of "series_label_overlap" is invalid"#,
+            err.to_string()
+        );
+    }
+
     // A free-form string field that lands in an SVG attribute must be escaped,
     // not emitted as raw markup (XSS prevention).
     #[test]
@@ -1092,4 +1224,93 @@ mod tests {
             "easing should fall back to the safe default"
         );
     }
+
+    // Percent stacks fill the whole 0–100% axis; `{d}` is the share of the
+    // category total and `{c}` the raw value.
+    #[test]
+    fn bar_chart_stack_percent() {
+        let chart = BarChart::from_json(
+            r###"{
+                "series_stack_mode": "percent",
+                "series_label_formatter": "{d} ({c})",
+                "series_list": [
+                    {"name": "A", "stack": "s", "label_show": true, "data": [1.0, 3.0]},
+                    {"name": "B", "stack": "s", "data": [3.0, 1.0]}
+                ],
+                "x_axis_data": ["x", "y"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n100%\n"), "missing 100% axis label");
+        assert!(svg.contains("\n0%\n"), "missing 0% axis label");
+        assert!(svg.contains("\n25% (1)\n"), "missing share label of x");
+        assert!(svg.contains("\n75% (3)\n"), "missing share label of y");
+        // Both stacks reach the top of the plot area.
+        assert_eq!(2, svg.matches(r#"y="27" width="263.5""#).count());
+    }
+
+    // A percent axis can't scale a raw series too; the raw series needs an
+    // axis of its own.
+    #[test]
+    fn bar_chart_stack_percent_mixed() {
+        let json = r###"{
+            "series_stack_mode": "percent",
+            "series_list": [
+                {"name": "A", "stack": "s", "data": [1.0, 3.0]},
+                {"name": "B", "stack": "s", "data": [3.0, 1.0]},
+                {"name": "C", "data": [150.0, 240.0]}
+            ],
+            "x_axis_data": ["x", "y"]
+        }"###;
+        let err = BarChart::from_json(json).unwrap().svg().unwrap_err();
+        assert_eq!(
+            "Params is invalid: percent stacked series can't share y axis 0 with series outside a stack",
+            err.to_string()
+        );
+
+        let chart = BarChart::from_json(
+            &json.replace(r#""name": "C", "#, r#""name": "C", "y_axis_index": 1, "#),
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n100%\n"), "missing 100% axis label");
+        assert!(svg.contains("\n270\n"), "raw series must not be clipped");
+    }
+
+    #[test]
+    fn bar_chart_group_stack() {
+        let bar_chart = BarChart::from_json(
+            r###"{
+                "bar_category_gap": 0.2,
+                "bar_gap": 0.5,
+                "series_list": [
+                    {"name": "A", "stack": "x", "data": [1.0, 2.0, 3.0, 4.0]},
+                    {"name": "B", "stack": "x", "data": [1.0, 2.0, 3.0, 4.0]},
+                    {"name": "C", "stack": "y", "data": [2.0, 2.0, 3.0, 1.0]}
+                ],
+                "x_axis_data": ["Jan", "Feb", "Apr", "May"],
+                "x_axis_group_data": ["Q1", "Q1", "Q2", "Q2"]
+            }"###,
+        )
+        .unwrap();
+        let svg = bar_chart.svg().unwrap();
+        // Two stacks side by side: 20% of the category is margin and the
+        // gap is half a bar wide.
+        assert!(
+            svg.contains(
+                r##"<rect x="40.2" y="283.7" width="45.5" height="25.7" fill="#91CC75"/>"##
+            )
+        );
+        assert!(svg.contains(
+            r##"<rect x="108.5" y="283.7" width="45.5" height="51.3" fill="#FAC858"/>"##
+        ));
+        // One label per quarter below the months, with a separator between.
+        assert!(svg.contains(r##"<text font-size="14" x="168.2" y="380" dominant-baseline="central" text-anchor="middle" font-family="Roboto" fill="#6E7079">
+Q1
+</text>"##));
+        assert!(svg.contains(
+            r##"<line stroke-width="1" x1="310.5" y1="335" x2="310.5" y2="395" stroke="#6E7079"/>"##
+        ));
+    }
 }
@@ -24,11 +24,13 @@ use serde::{Deserialize, Serialize};
 use super::canvas;
 use super::canvas::Canvas;
 use super::color::*;
+use super::color_scheme::ColorVariables;
 use super::common::*;
 use super::component::*;
+use super::label::{LabelBox, place_labels};
 use super::measure_text_width_family;
 use super::params::*;
-use super::theme::{DEFAULT_Y_AXIS_WIDTH, Theme, get_theme};
+use super::theme::{DEFAULT_Y_AXIS_WIDTH, THEME_LIGHT, Theme, get_theme};
 use super::util::*;
 
 /// The options shared by every chart type: canvas size and position, the data
@@ -143,6 +145,9 @@ pub struct ChartBase {
     /// Series label format, supporting `{c}` value, `{a}` series name,
     /// `{b}` category, `{d}` percentage and `{t}` thousands.
     pub series_label_formatter: String,
+    /// How overlapping series labels are resolved (line, bar, scatter, pie
+    /// and funnel charts).
+    pub series_label_overlap: LabelOverlap,
     /// Color palette cycled through by the series.
     pub series_colors: Vec<Color>,
     /// Marker drawn on data points (circle, dot or none).
@@ -151,6 +156,9 @@ pub struct ChartBase {
     pub series_smooth: bool,
     /// Fills the area under line series.
     pub series_fill: bool,
+    /// How stacked series are accumulated; [`StackMode::Percent`] normalizes
+    /// every stack to 100% (bar, horizontal bar and line charts).
+    pub series_stack_mode: StackMode,
 
     /// SVG animation (duration/easing/stagger delay) for the chart types that
     /// support it (bar, line, pie, funnel, sunburst, treemap, sankey).
@@ -160,6 +168,154 @@ pub struct ChartBase {
     /// for accessibility. Supported by bar, horizontal bar, line, pie and
     /// scatter charts. Default: false; output is unchanged when off.
     pub tooltip_show: bool,
+    /// Renders the themed colors as CSS custom properties that switch from the
+    /// `light` to the `dark` theme under `@media (prefers-color-scheme: dark)`,
+    /// so one SVG follows the color scheme of the viewer. Only the colors
+    /// still holding their `light` theme value switch: the background, title,
+    /// sub-title, legend, axis, grid and series label colors and the series
+    /// palette. Meant for browsers; the PNG/JPEG encoders do not resolve CSS
+    /// variables. Default: `None`.
+    pub color_scheme: Option<ColorScheme>,
+}
+
+/// Distance of a series label baseline above the point it describes; the
+/// collision box of the label sits right above it.
+const SERIES_LABEL_OFFSET: f32 = 8.0;
+
+/// Renders `chart` through `svg` with its themed colors swapped for the CSS
+/// variables of [`ChartBase::color_scheme`]; `None` when no color scheme is
+/// set. Every chart's `svg()` starts with it, passing its y axis configs (or
+/// `|_| &mut []` without a y axis) and itself.
+pub(crate) fn render_color_scheme<C>(
+    chart: &C,
+    y_axis_configs: fn(&mut C) -> &mut [YAxisConfig],
+    svg: fn(&C) -> canvas::Result<String>,
+) -> Option<canvas::Result<String>>
+where
+    C: Clone + std::ops::DerefMut<Target = ChartBase>,
+{
+    let result = get_color_scheme_chart(chart, y_axis_configs, svg)?;
+    Some(result.and_then(|(chart, variables)| {
+        let _guard = variables.activate();
+        svg(&chart)
+    }))
+}
+
+/// Gets a copy of `chart` with its themed colors swapped for the keys of
+/// the CSS variables of [`ChartBase::color_scheme`], along with the
+/// variables, for renders other than `svg` (e.g. several pages); `None`
+/// when no color scheme is set.
+pub(crate) fn get_color_scheme_chart<C>(
+    chart: &C,
+    y_axis_configs: fn(&mut C) -> &mut [YAxisConfig],
+    svg: fn(&C) -> canvas::Result<String>,
+) -> Option<canvas::Result<(C, ColorVariables)>>
+where
+    C: Clone + std::ops::DerefMut<Target = ChartBase>,
+{
+    let scheme = chart.color_scheme.as_ref()?;
+    let (light, dark) = (get_theme(&scheme.light), get_theme(&scheme.dark));
+    let mut chart = chart.clone();
+    chart.color_scheme = None;
+    let mut variables = ColorVariables::default();
+    // A plain render shows every color the chart writes, including the user
+    // colors of chart specific fields, none of which may become a key.
+    match svg(&chart) {
+        Ok(plain) => variables.reserve(&plain),
+        Err(err) => return Some(Err(err)),
+    }
+    let base: &mut ChartBase = &mut chart;
+    for (name, color, light, dark) in [
+        (
+            "background-color",
+            &mut base.background_color,
+            light.background_color,
+            dark.background_color,
+        ),
+        (
+            "title-font-color",
+            &mut base.title_font_color,
+            light.title_font_color,
+            dark.title_font_color,
+        ),
+        (
+            "sub-title-font-color",
+            &mut base.sub_title_font_color,
+            light.sub_title_font_color,
+            dark.sub_title_font_color,
+        ),
+        (
+            "legend-font-color",
+            &mut base.legend_font_color,
+            light.legend_font_color,
+            dark.legend_font_color,
+        ),
+        (
+            "x-axis-stroke-color",
+            &mut base.x_axis_stroke_color,
+            light.x_axis_stroke_color,
+            dark.x_axis_stroke_color,
+        ),
+        (
+            "x-axis-font-color",
+            &mut base.x_axis_font_color,
+            light.x_axis_font_color,
+            dark.x_axis_font_color,
+        ),
+        (
+            "grid-stroke-color",
+            &mut base.grid_stroke_color,
+            light.grid_stroke_color,
+            dark.grid_stroke_color,
+        ),
+        (
+            "series-label-font-color",
+            &mut base.series_label_font_color,
+            light.series_label_font_color,
+            dark.series_label_font_color,
+        ),
+    ] {
+        variables.add(name, color, light, dark);
+    }
+    // The symbol fill is a copy of the background color.
+    if let Some(Symbol::Circle(_, Some(color))) = &mut base.series_symbol {
+        variables.add(
+            "background-color",
+            color,
+            light.background_color,
+            dark.background_color,
+        );
+    }
+    if !dark.series_colors.is_empty() {
+        for (index, color) in base.series_colors.iter_mut().enumerate() {
+            if let Some(light_color) = light.series_colors.get(index) {
+                // The palettes pair up by index, the shorter dark one
+                // repeating as it does when coloring series.
+                let dark_color = dark.series_colors[index % dark.series_colors.len()];
+                variables.add(
+                    &format!("series-colors-{index}"),
+                    color,
+                    *light_color,
+                    dark_color,
+                );
+            }
+        }
+    }
+    for config in y_axis_configs(&mut chart) {
+        variables.add(
+            "y-axis-font-color",
+            &mut config.axis_font_color,
+            light.y_axis_font_color,
+            dark.y_axis_font_color,
+        );
+        variables.add(
+            "y-axis-stroke-color",
+            &mut config.axis_stroke_color,
+            light.y_axis_stroke_color,
+            dark.y_axis_stroke_color,
+        );
+    }
+    Some(Ok((chart, variables)))
 }
 
 /// Gets y axis config by index.
@@ -242,9 +398,8 @@ impl ChartBase {
         y_axis_configs: &mut Vec<YAxisConfig>,
     ) -> canvas::Result<serde_json::Value> {
         let data: serde_json::Value = serde_json::from_str(data)?;
-        let series_list = get_series_list_from_value(&data).unwrap_or_default();
-        let theme = get_string_from_value(&data, "theme").unwrap_or_default();
-        let theme = get_theme(&theme);
+        let series_list = get_series_list_from_value(&data)?.unwrap_or_default();
+        let theme = get_theme_from_value(&data);
         self.fill_theme(theme.clone(), y_axis_configs);
         self.series_list = series_list;
 
@@ -272,7 +427,7 @@ impl ChartBase {
         if let Some(title_font_size) = get_f32_from_value(&data, "title_font_size") {
             self.title_font_size = title_font_size;
         }
-        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color") {
+        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color")? {
             self.title_font_color = title_font_color;
         }
         if let Some(title_font_weight) = get_string_from_value(&data, "title_font_weight") {
@@ -294,7 +449,7 @@ impl ChartBase {
         if let Some(sub_title_font_size) = get_f32_from_value(&data, "sub_title_font_size") {
             self.sub_title_font_size = sub_title_font_size;
         }
-        if let Some(sub_title_font_color) = get_color_from_value(&data, "sub_title_font_color") {
+        if let Some(sub_title_font_color) = get_color_from_value(&data, "sub_title_font_color")? {
             self.sub_title_font_color = sub_title_font_color;
         }
         if let Some(sub_title_font_weight) = get_string_from_value(&data, "sub_title_font_weight") {
@@ -313,7 +468,7 @@ impl ChartBase {
         if let Some(legend_font_size) = get_f32_from_value(&data, "legend_font_size") {
             self.legend_font_size = legend_font_size;
         }
-        if let Some(legend_font_color) = get_color_from_value(&data, "legend_font_color") {
+        if let Some(legend_font_color) = get_color_from_value(&data, "legend_font_color")? {
             self.legend_font_color = legend_font_color;
         }
         if let Some(legend_font_weight) = get_string_from_value(&data, "legend_font_weight") {
@@ -338,13 +493,13 @@ impl ChartBase {
         if let Some(x_axis_height) = get_f32_from_value(&data, "x_axis_height") {
             self.x_axis_height = x_axis_height;
         }
-        if let Some(x_axis_stroke_color) = get_color_from_value(&data, "x_axis_stroke_color") {
+        if let Some(x_axis_stroke_color) = get_color_from_value(&data, "x_axis_stroke_color")? {
             self.x_axis_stroke_color = x_axis_stroke_color;
         }
         if let Some(x_axis_font_size) = get_f32_from_value(&data, "x_axis_font_size") {
             self.x_axis_font_size = x_axis_font_size;
         }
-        if let Some(x_axis_font_color) = get_color_from_value(&data, "x_axis_font_color") {
+        if let Some(x_axis_font_color) = get_color_from_value(&data, "x_axis_font_color")? {
             self.x_axis_font_color = x_axis_font_color;
         }
         if let Some(x_axis_font_weight) = get_string_from_value(&data, "x_axis_font_weight") {
@@ -369,11 +524,12 @@ impl ChartBase {
             self.y_axis_hidden = y_axis_hidden;
         }
 
-        if let Some(value) = get_y_axis_configs_from_value(theme.clone(), &data, "y_axis_configs") {
+        if let Some(value) = get_y_axis_configs_from_value(theme.clone(), &data, "y_axis_configs")?
+        {
             *y_axis_configs = value;
         }
 
-        if let Some(grid_stroke_color) = get_color_from_value(&data, "grid_stroke_color") {
+        if let Some(grid_stroke_color) = get_color_from_value(&data, "grid_stroke_color")? {
             self.grid_stroke_color = grid_stroke_color;
         }
         if let Some(grid_stroke_width) = get_f32_from_value(&data, "grid_stroke_width") {
@@ -384,7 +540,7 @@ impl ChartBase {
             self.series_stroke_width = series_stroke_width;
         }
         if let Some(series_label_font_color) =
-            get_color_from_value(&data, "series_label_font_color")
+            get_color_from_value(&data, "series_label_font_color")?
         {
             self.series_label_font_color = series_label_font_color;
         }
@@ -401,10 +557,10 @@ impl ChartBase {
             self.series_label_formatter = series_label_formatter;
         }
 
-        if let Some(series_colors) = get_color_slice_from_value(&data, "series_colors") {
+        if let Some(series_colors) = get_color_slice_from_value(&data, "series_colors")? {
             self.series_colors = series_colors;
         }
-        if let Some(series_symbol) = get_series_symbol_from_value(&data, "series_symbol") {
+        if let Some(series_symbol) = get_series_symbol_from_value(&data, "series_symbol")? {
             self.series_symbol = Some(series_symbol);
         }
         if let Some(series_smooth) = get_bool_from_value(&data, "series_smooth") {
@@ -413,6 +569,14 @@ impl ChartBase {
         if let Some(series_fill) = get_bool_from_value(&data, "series_fill") {
             self.series_fill = series_fill;
         }
+        if let Some(series_label_overlap) =
+            get_label_overlap_from_value(&data, "series_label_overlap")?
+        {
+            self.series_label_overlap = series_label_overlap;
+        }
+        if let Some(series_stack_mode) = get_stack_mode_from_value(&data, "series_stack_mode")? {
+            self.series_stack_mode = series_stack_mode;
+        }
 
         if let Some(anim) = data.get("animation")
             && !anim.is_null()
@@ -432,20 +596,33 @@ impl ChartBase {
         if let Some(v) = get_bool_from_value(&data, "tooltip_show") {
             self.tooltip_show = v;
         }
+        if let Some(scheme) = data.get("color_scheme")
+            && let Some(dark) = get_string_from_value(scheme, "dark")
+        {
+            // The light theme defaults to the theme the chart is created with.
+            let light = get_string_from_value(scheme, "light")
+                .or_else(|| get_string_from_value(&data, "theme"))
+                .unwrap_or_else(|| THEME_LIGHT.to_string());
+            self.color_scheme = Some(ColorScheme { light, dark });
+        }
 
         Ok(data)
     }
-    /// Gets y axis values by index.
-    pub(crate) fn get_y_axis_values(
+    /// Collects the values a value axis has to cover: the individual values
+    /// of the non-stacked series plus the per-x sums of every stack group.
+    /// `y_axis_index` limits the series to one axis (`None` takes them all).
+    /// Percent-mode stacks always span 0–100%, so they add no sums; the
+    /// returned flag is set when the axis is such a percent axis. A percent
+    /// axis can't also scale raw series, so mixing both is an error.
+    pub(crate) fn get_axis_data_list(
         &self,
-        y_axis_configs: &[YAxisConfig],
-        y_axis_index: usize,
-    ) -> (AxisValues, f32) {
-        let y_axis_config = get_y_axis_config(y_axis_configs, y_axis_index);
+        y_axis_index: Option<usize>,
+    ) -> canvas::Result<(Vec<f32>, bool)> {
+        let on_axis = |series: &Series| y_axis_index.is_none_or(|i| series.y_axis_index == i);
         let mut data_list = vec![];
         // Non-stacked series: include individual values directly.
         for series in self.series_list.iter() {
-            if series.y_axis_index == y_axis_index && series.stack.is_none() {
+            if on_axis(series) && series.stack.is_none() {
                 data_list.append(&mut series.data_values());
             }
         }
@@ -453,28 +630,41 @@ impl ChartBase {
         // series in the same stack group, so collect per-x sums per stack key.
         let mut stack_keys: Vec<String> = vec![];
         for series in self.series_list.iter() {
-            if series.y_axis_index == y_axis_index
+            if on_axis(series)
                 && let Some(ref s) = series.stack
                 && !stack_keys.contains(s)
             {
                 stack_keys.push(s.clone());
             }
         }
+        if self.series_stack_mode == StackMode::Percent {
+            if stack_keys.is_empty() {
+                return Ok((data_list, false));
+            }
+            if !data_list.is_empty() {
+                let axis = y_axis_index.map_or("the value axis".to_string(), |index| {
+                    format!("y axis {index}")
+                });
+                return Err(canvas::Error::Params {
+                    message: format!(
+                        "percent stacked series can't share {axis} with series outside a stack"
+                    ),
+                });
+            }
+            // The percent axis gets its 100% top from the axis max.
+            return Ok((vec![0.0], true));
+        }
         for stack_key in &stack_keys {
             let max_len = self
                 .series_list
                 .iter()
-                .filter(|s| {
-                    s.y_axis_index == y_axis_index && s.stack.as_deref() == Some(stack_key.as_str())
-                })
+                .filter(|s| on_axis(s) && s.stack.as_deref() == Some(stack_key.as_str()))
                 .map(|s| s.data.len() + s.start_index)
                 .max()
                 .unwrap_or(0);
             let mut sums = vec![0.0_f32; max_len];
             for series in self.series_list.iter() {
-                if series.y_axis_index == y_axis_index
-                    && series.stack.as_deref() == Some(stack_key.as_str())
-                {
+                if on_axis(series) && series.stack.as_deref() == Some(stack_key.as_str()) {
                     for (i, &v) in series.data_values().iter().enumerate() {
                         let actual_i = i + series.start_index;
                         if actual_i < sums.len() && v != NIL_VALUE {
@@ -485,22 +675,43 @@ impl ChartBase {
             }
             data_list.extend(sums);
         }
+        Ok((data_list, false))
+    }
+    /// Gets y axis values by index.
+    pub(crate) fn get_y_axis_values(
+        &self,
+        y_axis_configs: &[YAxisConfig],
+        y_axis_index: usize,
+    ) -> canvas::Result<(AxisValues, f32)> {
+        let y_axis_config = get_y_axis_config(y_axis_configs, y_axis_index);
+        let (data_list, percent) = self.get_axis_data_list(Some(y_axis_index))?;
+        let mut max = y_axis_config.axis_max;
+        if percent {
+            // The stacks always fill the whole 0–100% range.
+            max = Some(max.unwrap_or(100.0));
+        }
         if data_list.is_empty() {
-            return (AxisValues::default(), 0.0);
+            return Ok((AxisValues::default(), 0.0));
         }
         let mut thousands_format = false;
         if let Some(ref value) = y_axis_config.axis_formatter {
             thousands_format = value.contains(THOUSANDS_FORMAT_LABEL);
         }
-        let y_axis_values = get_axis_values(AxisValueParams {
+        let mut y_axis_values = get_axis_values(AxisValueParams {
             data_list,
             split_number: y_axis_config.axis_split_number,
             reverse: Some(true),
             min: y_axis_config.axis_min,
-            max: y_axis_config.axis_max,
+            max,
             thousands_format,
             scale: y_axis_config.axis_scale.clone(),
         });
+        if percent && y_axis_config.axis_formatter.is_none() {
+            y_axis_values
+                .data
+                .iter_mut()
+                .for_each(|item| item.push('%'));
+        }
         let y_axis_width = if let Some(value) = y_axis_config.axis_width {
             value
         } else {
@@ -520,7 +731,123 @@ impl ChartBase {
                 DEFAULT_Y_AXIS_WIDTH
             }
         };
-        (y_axis_values, y_axis_width)
+        Ok((y_axis_values, y_axis_width))
+    }
+    /// Gets the per-x totals of every stack group (keyed `{stack}_{y_axis_index}`)
+    /// for the percent stack mode; empty in the normal mode. Totals sum the
+    /// absolute values so negative segments still take their share.
+    pub(crate) fn get_stack_totals(
+        &self,
+        series_list: &[&Series],
+        series_data_count: usize,
+    ) -> Vec<(String, Vec<f32>)> {
+        let mut totals: Vec<(String, Vec<f32>)> = vec![];
+        if self.series_stack_mode != StackMode::Percent {
+            return totals;
+        }
+        for series in series_list.iter() {
+            let Some(ref stack) = series.stack else {
+                continue;
+            };
+            let key = format!("{}_{}", stack, series.y_axis_index);
+            let index = if let Some(index) = totals.iter().position(|(k, _)| k == &key) {
+                index
+            } else {
+                totals.push((key, vec![0.0_f32; series_data_count]));
+                totals.len() - 1
+            };
+            for (i, v) in series.data_values().iter().enumerate() {
+                let actual_i = i + series.start_index;
+                if *v != NIL_VALUE && actual_i < series_data_count {
+                    totals[index].1[actual_i] += v.abs();
+                }
+            }
+        }
+        totals
+    }
+    /// Gets the per-x offset every stack group (keyed `{stack}_{y_axis_index}`)
+    /// starts at for the given baseline; empty for the zero baseline.
+    pub(crate) fn get_stack_offsets(
+        &self,
+        series_list: &[&Series],
+        series_data_count: usize,
+        baseline: &StackBaseline,
+    ) -> Vec<(String, Vec<f32>)> {
+        if *baseline == StackBaseline::Zero {
+            return vec![];
+        }
+        self.get_stack_layers(series_list, series_data_count)
+            .into_iter()
+            .map(|(key, layers)| (key, get_stack_baselines(&layers, baseline)))
+            .collect()
+    }
+    /// Gets the layers of every stack group (keyed `{stack}_{y_axis_index}`),
+    /// bottom layer first. Missing points count as zero so the layers stay
+    /// continuous.
+    pub(crate) fn get_stack_layers(
+        &self,
+        series_list: &[&Series],
+        series_data_count: usize,
+    ) -> Vec<(String, Vec<Vec<f32>>)> {
+        let stack_totals = self.get_stack_totals(series_list, series_data_count);
+        let mut groups: Vec<(String, Vec<Vec<f32>>)> = vec![];
+        for series in series_list.iter() {
+            let Some(ref stack) = series.stack else {
+                continue;
+            };
+            let key = format!("{}_{}", stack, series.y_axis_index);
+            let totals = stack_totals.iter().find(|(k, _)| k == &key).map(|(_, v)| v);
+            let mut layer = vec![0.0_f32; series_data_count];
+            for (i, v) in series.data_values().iter().enumerate() {
+                let actual_i = i + series.start_index;
+                if *v == NIL_VALUE || actual_i >= series_data_count {
+                    continue;
+                }
+                layer[actual_i] = totals.map_or(*v, |t| get_percentage(*v, t[actual_i]) * 100.0);
+            }
+            if let Some((_, layers)) = groups.iter_mut().find(|(k, _)| k == &key) {
+                layers.push(layer);
+            } else {
+                groups.push((key, vec![layer]));
+            }
+        }
+        groups
+    }
+    /// Formats the label of one stacked data point. In the percent stack mode
+    /// the formatter may use `{d}` (share), `{c}` (raw value), `{a}` and `{b}`;
+    /// a formatter without placeholders is treated as the precision of the
+    /// percentage, e.g. `"{:.1}"`. Otherwise the normal label format applies.
+    pub(crate) fn format_stack_label(
+        &self,
+        series: &Series,
+        index: usize,
+        value: f32,
+        percentage: Option<f32>,
+    ) -> String {
+        let Some(percentage) = percentage else {
+            return format_series_value(value, &self.series_label_formatter);
+        };
+        let formatter = &self.series_label_formatter;
+        let has_label = [
+            SERIES_NAME_FORMAT_LABEL,
+            CATEGORY_NAME_FORMAT_LABEL,
+            VALUE_FORMAT_LABEL,
+            PERCENTAGE_FORMAT_LABEL,
+            THOUSANDS_FORMAT_LABEL,
+        ]
+        .iter()
+        .any(|label| formatter.contains(label));
+        if !has_label {
+            return format_series_value(percentage * 100.0, formatter) + "%";
+        }
+        LabelOption {
+            series_name: series.name.clone(),
+            category_name: self.x_axis_data.get(index).cloned().unwrap_or_default(),
+            value,
+            percentage,
+            formatter: formatter.clone(),
+        }
+        .format()
     }
     /// Renders background for canvas.
     pub(crate) fn render_background(&self, c: Canvas) {
@@ -771,35 +1098,70 @@ impl ChartBase {
             ..Default::default()
         });
     }
-    /// Renders series label widget for canvas.
+    /// Renders series label widget for canvas. Overlapping labels are
+    /// resolved with `series_label_overlap`, earlier series keep their place.
     pub(crate) fn render_series_label(&self, c: Canvas, series_labels_list: Vec<Vec<SeriesLabel>>) {
         if series_labels_list.is_empty() {
             return;
         }
         let mut c1 = c;
-        for series_labels in series_labels_list.iter() {
-            for series_label in series_labels.iter() {
-                let mut dx = None;
-                if let Ok(value) = measure_text_width_family(
-                    &self.font_family,
-                    self.series_label_font_size,
-                    &series_label.text,
-                ) {
-                    dx = Some(-value.width() / 2.0);
+        let font_size = self.series_label_font_size;
+        let labels: Vec<(&SeriesLabel, Option<f32>)> = series_labels_list
+            .iter()
+            .flatten()
+            .map(|series_label| {
+                let width =
+                    measure_text_width_family(&self.font_family, font_size, &series_label.text)
+                        .map(|b| b.width())
+                        .ok();
+                (series_label, width)
+            })
+            .collect();
+        let label_boxes: Vec<LabelBox> = labels
+            .iter()
+            .enumerate()
+            .map(|(index, (series_label, width))| {
+                let Point { x, y } = series_label.point;
+                let half_width = width.unwrap_or_default() / 2.0;
+                LabelBox {
+                    rect: Box {
+                        left: x - half_width,
+                        top: y - SERIES_LABEL_OFFSET - font_size,
+                        right: x + half_width,
+                        bottom: y - SERIES_LABEL_OFFSET,
+                    },
+                    anchor: series_label.point,
+                    priority: -(index as f32),
                 }
-                c1.text(Text {
-                    text: series_label.text.clone(),
-                    dy: Some(-8.0),
-                    dx,
-                    font_family: Some(self.font_family.clone()),
-                    font_color: Some(self.series_label_font_color),
-                    font_size: Some(self.series_label_font_size),
-                    font_weight: self.series_label_font_weight.clone(),
-                    x: Some(series_label.point.x),
-                    y: Some(series_label.point.y),
+            })
+            .collect();
+        let placements = place_labels(&label_boxes, self.series_label_overlap);
+        for ((series_label, width), placement) in labels.into_iter().zip(placements) {
+            if placement.hidden {
+                continue;
+            }
+            if let Some((start, end)) = placement.leader_line {
+                c1.line(Line {
+                    color: Some(self.series_label_font_color),
+                    left: start.x,
+                    top: start.y,
+                    right: end.x,
+                    bottom: end.y,
                     ..Default::default()
                 });
             }
+            c1.text(Text {
+                text: series_label.text.clone(),
+                dy: Some(-SERIES_LABEL_OFFSET + placement.dy),
+                dx: width.map(|width| -width / 2.0),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(font_size),
+                font_weight: self.series_label_font_weight.clone(),
+                x: Some(series_label.point.x),
+                y: Some(series_label.point.y),
+                ..Default::default()
+            });
         }
     }
     /// Renders the bar widget for canvas.
@@ -812,6 +1174,7 @@ impl ChartBase {
         max_height: f32,
         series_data_count: usize,
         radius: Option<f32>,
+        (category_gap, bar_gap): (Option<f32>, Option<f32>),
         animation: Option<&AnimationConfig>,
         tooltip: bool,
     ) -> Vec<Vec<SeriesLabel>> {
@@ -821,8 +1184,8 @@ impl ChartBase {
         let mut c1 = c;
 
         let unit_width = c1.width() / series_data_count as f32;
-        let bar_chart_margin = 5.0_f32;
-        let bar_chart_gap = 3.0_f32;
+        let bar_chart_margin =
+            category_gap.map_or(5.0_f32, |ratio| unit_width * ratio.clamp(0.0, 0.9) / 2.0);
         let bar_chart_margin_width = bar_chart_margin * 2.0;
 
         // Assign each series a visual slot index.
@@ -850,9 +1213,19 @@ impl ChartBase {
             return vec![];
         }
 
-        let bar_chart_gap_width = bar_chart_gap * (slot_count - 1) as f32;
-        let bar_width =
-            (unit_width - bar_chart_margin_width - bar_chart_gap_width) / slot_count as f32;
+        let (bar_width, bar_chart_gap) = if let Some(ratio) = bar_gap {
+            // n bars and n - 1 gaps of `ratio` bar widths share the space.
+            let ratio = ratio.max(-1.0);
+            let bar_width = (unit_width - bar_chart_margin_width)
+                / (slot_count as f32 + ratio * (slot_count - 1) as f32);
+            (bar_width, bar_width * ratio)
+        } else {
+            let bar_chart_gap = 3.0_f32;
+            let bar_chart_gap_width = bar_chart_gap * (slot_count - 1) as f32;
+            let bar_width =
+                (unit_width - bar_chart_margin_width - bar_chart_gap_width) / slot_count as f32;
+            (bar_width, bar_chart_gap)
+        };
         let half_bar_width = bar_width / 2.0;
 
         // Per-stack accumulator: maps slot key → per-x cumulative data values.
@@ -860,6 +1233,7 @@ impl ChartBase {
             .iter()
             .map(|k| (k.clone(), vec![0.0_f32; series_data_count]))
             .collect();
+        let stack_totals = self.get_stack_totals(series_list, series_data_count);
 
         let mut series_labels_list = vec![];
         let get_bar_color = |colors: &Option<Vec<Option<Color>>>, index: usize| -> Option<Color> {
@@ -891,17 +1265,25 @@ impl ChartBase {
             let acc_idx = stack_key
                 .as_ref()
                 .and_then(|k| stack_acc.iter().position(|(ak, _)| ak == k));
+            let totals = stack_key
+                .as_ref()
+                .and_then(|k| stack_totals.iter().find(|(tk, _)| tk == k))
+                .map(|(_, v)| v);
 
             let mut series_labels = vec![];
             for (i, p) in series.data_values().iter().enumerate() {
-                let value = p.to_owned();
-                if value == NIL_VALUE {
+                let raw_value = p.to_owned();
+                if raw_value == NIL_VALUE {
                     continue;
                 }
                 let actual_i = i + series.start_index;
                 if actual_i >= series_data_count {
                     continue;
                 }
+                // Percent stacks draw each segment as its share of the total.
+                let percentage = totals.map(|t| get_percentage(raw_value, t[actual_i]));
+                let value = percentage.map_or(raw_value, |p| p * 100.0);
+                let label_text = self.format_stack_label(series, actual_i, raw_value, percentage);
 
                 let mut left = unit_width * actual_i as f32 + bar_chart_margin;
                 left += (bar_width + bar_chart_gap) * slot_index as f32;
@@ -920,11 +1302,10 @@ impl ChartBase {
                     (y, max_height - y)
                 };
 
-                let mut fill_color = get_bar_color(&series.colors, i);
-                if fill_color.is_none() {
-                    fill_color = Some(color);
-                }
-                let fill: Option<Fill> = fill_color.map(|c| c.into());
+                let fill = get_bar_color(&series.colors, i)
+                    .map(Fill::Solid)
+                    .or_else(|| series.fill.clone())
+                    .unwrap_or(Fill::Solid(color));
 
                 let bar_style =
                     animation.map(|a| format!("animation-delay:{}ms", actual_i as u32 * a.delay));
@@ -942,14 +1323,10 @@ impl ChartBase {
                 } else {
                     Some(bar_classes.join(" "))
                 };
-                let tooltip_text = format!(
-                    "{}: {}",
-                    series.name,
-                    format_series_value(value, &self.series_label_formatter)
-                );
+                let tooltip_text = format!("{}: {}", series.name, label_text);
 
                 c1.rect(Rect {
-                    fill,
+                    fill: Some(fill),
                     left,
                     top: y_top,
                     width: bar_width,
@@ -992,7 +1369,7 @@ impl ChartBase {
 
                 series_labels.push(SeriesLabel {
                     point: (left + half_bar_width, y_top).into(),
-                    text: format_series_value(value, &self.series_label_formatter),
+                    text: label_text,
                 });
             }
             if series.label_show {
@@ -1011,6 +1388,7 @@ impl ChartBase {
         max_height: f32,
         axis_height: f32,
         series_data_count: usize,
+        stack_baseline: &StackBaseline,
         animation: Option<&AnimationConfig>,
         tooltip: bool,
     ) -> Vec<Vec<SeriesLabel>> {
@@ -1027,6 +1405,8 @@ impl ChartBase {
         // Stack accumulators for line series: stack_key -> Vec<f32> of cumulative
         // data values per x-position (data space, not pixel space).
         let mut stack_acc: Vec<(String, Vec<f32>)> = vec![];
+        let stack_totals = self.get_stack_totals(series_list, series_data_count);
+        let stack_offsets = self.get_stack_offsets(series_list, series_data_count, stack_baseline);
 
         for (index, series) in series_list.iter().enumerate() {
             let y_axis_values = if series.y_axis_index >= y_axis_values_list.len() {
@@ -1040,6 +1420,10 @@ impl ChartBase {
                 .as_ref()
                 .map(|s| format!("{}_{}", s, series.y_axis_index));
             let is_stacked = stack_key.is_some();
+            let totals = stack_key
+                .as_ref()
+                .and_then(|k| stack_totals.iter().find(|(tk, _)| tk == k))
+                .map(|(_, v)| v);
 
             // Retrieve the current accumulated data values for this stack group.
             let acc_data: Vec<f32> = if let Some(ref key) = stack_key {
@@ -1047,16 +1431,24 @@ impl ChartBase {
                     .iter()
                     .find(|(k, _)| k == key)
                     .map(|(_, v)| v.clone())
+                    .or_else(|| {
+                        stack_offsets
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .map(|(_, v)| v.clone())
+                    })
                     .unwrap_or_else(|| vec![0.0_f32; series_data_count])
             } else {
                 vec![0.0_f32; series_data_count]
             };
 
             let mut points: Vec<Point> = vec![];
-            // For stacked fills: the "floor" points of the previous stack level.
-            let mut floor_points: Vec<Point> = vec![];
             let mut points_list: Vec<Vec<Point>> = vec![];
-            let mut floor_points_list: Vec<Vec<Point>> = vec![];
+            // For stacked fills: the band between the previous stack level
+            // (floor) and this series (top). Missing points add nothing, so
+            // the band runs on continuously while the line breaks.
+            let mut band_points: Vec<Point> = vec![];
+            let mut band_floor_points: Vec<Point> = vec![];
             let mut series_labels = vec![];
 
             let mut max_value = f32::MIN;
@@ -1068,20 +1460,30 @@ impl ChartBase {
             let mut new_acc = acc_data.clone();
 
             for (i, p) in series.data_values().iter().enumerate() {
-                let value = p.to_owned();
+                let raw_value = p.to_owned();
                 let actual_i = i + series.start_index;
-                if value == NIL_VALUE {
+                if raw_value == NIL_VALUE {
                     if !points.is_empty() {
                         points_list.push(points);
-                        floor_points_list.push(floor_points);
                         points = vec![];
-                        floor_points = vec![];
+                    }
+                    if is_stacked && actual_i < series_data_count {
+                        let mut x = unit_width * actual_i as f32;
+                        if x_boundary_gap {
+                            x += unit_width / 2.0;
+                        }
+                        let floor_y =
+                            y_axis_values.get_offset_height(acc_data[actual_i], max_height);
+                        band_points.push((x, floor_y).into());
+                        band_floor_points.push((x, floor_y).into());
                     }
                     continue;
                 }
                 if actual_i >= series_data_count {
                     continue;
                 }
+                let percentage = totals.map(|t| get_percentage(raw_value, t[actual_i]));
+                let value = percentage.map_or(raw_value, |p| p * 100.0);
 
                 let base_acc = acc_data[actual_i];
                 let effective_value = base_acc + value;
@@ -1105,20 +1507,20 @@ impl ChartBase {
                 // Floor points for stacked area fill (previous cumulative level).
                 if is_stacked {
                     let floor_y = y_axis_values.get_offset_height(base_acc, max_height);
-                    floor_points.push((x, floor_y).into());
+                    band_points.push((x, y).into());
+                    band_floor_points.push((x, floor_y).into());
                 }
 
                 new_acc[actual_i] += value;
 
                 series_labels.push(SeriesLabel {
                     point: (x, y).into(),
-                    text: format_series_value(value, &self.series_label_formatter),
+                    text: self.format_stack_label(series, actual_i, raw_value, percentage),
                 });
             }
 
             if !points.is_empty() {
                 points_list.push(points);
-                floor_points_list.push(floor_points);
             }
 
             // Update stack accumulator for subsequent series in the same group.
@@ -1136,43 +1538,46 @@ impl ChartBase {
 
             let color = get_color(&self.series_colors, series.index.unwrap_or(index));
             let fill_color = color.with_alpha(100);
-            let fill: Fill = fill_color.into();
+            let fill = series.fill.clone().unwrap_or(Fill::Solid(fill_color));
             let series_fill = self.series_fill;
 
-            for (seg_idx, points) in points_list.iter().enumerate() {
-                let floor = floor_points_list.get(seg_idx);
-
+            // Stacked bands fill between the floor and top points: the
+            // top points forward, then the floor reversed.
+            if series_fill && is_stacked && !band_points.is_empty() {
+                band_floor_points.reverse();
+                if self.series_smooth {
+                    c1.smooth_line_fill(SmoothLineFill {
+                        fill: fill.clone(),
+                        points: band_points,
+                        bottom_points: band_floor_points,
+                        ..Default::default()
+                    });
+                } else {
+                    band_points.extend(band_floor_points);
+                    c1.polygon(Polygon {
+                        fill: Some(fill_color),
+                        gradient: series.fill.clone(),
+                        points: band_points,
+                        ..Default::default()
+                    });
+                }
+            }
+            for points in points_list.iter() {
                 let line_class = animation.map(|_| format!("line-anim-{}", index));
                 let line_path_length = animation.map(|_| 1.0_f32);
 
-                // Fill first, then stroke. The stacked-area polygon is
-                // identical for smooth and straight lines, so it lives in
-                // one place; only the non-stacked fill and the stroke
-                // itself differ by `series_smooth`.
-                if series_fill {
-                    if is_stacked {
-                        if let Some(fp) = floor {
-                            // Area between the current and previous stack
-                            // level: top points forward + floor reversed.
-                            let mut poly = points.clone();
-                            let mut rev_floor = fp.clone();
-                            rev_floor.reverse();
-                            poly.extend(rev_floor);
-                            c1.polygon(Polygon {
-                                fill: Some(fill_color),
-                                points: poly,
-                                ..Default::default()
-                            });
-                        }
-                    } else if self.series_smooth {
+                // Fill first, then stroke.
+                if series_fill && !is_stacked {
+                    if self.series_smooth {
                         c1.smooth_line_fill(SmoothLineFill {
-                            fill,
+                            fill: fill.clone(),
                             points: points.clone(),
                             bottom: axis_height,
+                            ..Default::default()
                         });
                     } else {
                         c1.straight_line_fill(StraightLineFill {
-                            fill,
+                            fill: fill.clone(),
                             points: points.clone(),
                             bottom: axis_height,
                             ..Default::default()
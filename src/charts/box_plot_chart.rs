@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::{ChartBase, get_y_axis_config};
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -138,6 +138,9 @@ impl BoxPlotChart {
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
         self.render_background(c.child(Box::default()));
         let mut x_axis_height = self.x_axis_height;
@@ -0,0 +1,505 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Canvas;
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{Theme, get_default_theme_name, get_theme};
+use super::util::*;
+use crate::charts::measure_text_width_family;
+
+// ── Data types ────────────────────────────────────────────────────────────────
+
+/// A single bullet: a measure bar against a target marker on top of
+/// qualitative range bands.
+#[derive(Clone, Debug, Default)]
+pub struct BulletData {
+    /// Label of the bullet.
+    pub name: String,
+    /// The measured value, drawn as the bar.
+    pub measure: f32,
+    /// The target value, drawn as a marker across the bar.
+    pub target: Option<f32>,
+    /// Upper bounds of the qualitative ranges (e.g. poor / fair / good), in
+    /// ascending order.  The last one is the end of the scale unless the
+    /// measure or target exceed it.
+    pub ranges: Vec<f32>,
+}
+
+impl From<(&str, f32, f32, Vec<f32>)> for BulletData {
+    fn from(value: (&str, f32, f32, Vec<f32>)) -> Self {
+        BulletData {
+            name: value.0.to_string(),
+            measure: value.1,
+            target: Some(value.2),
+            ranges: value.3,
+        }
+    }
+}
+
+fn get_bullet_data_list_from_value(value: &serde_json::Value) -> Option<Vec<BulletData>> {
+    let arr = value.get("data")?.as_array()?;
+    let list = arr
+        .iter()
+        .map(|item| BulletData {
+            name: get_string_from_value(item, "name").unwrap_or_default(),
+            measure: get_f32_from_value(item, "measure").unwrap_or_default(),
+            target: get_f32_from_value(item, "target"),
+            ranges: get_f32_slice_from_value(item, "ranges").unwrap_or_default(),
+        })
+        .collect();
+    Some(list)
+}
+
+// ── BulletChart ───────────────────────────────────────────────────────────────
+
+/// A bullet chart: one row (or column when `vertical`) per bullet, each with
+/// its own scale.
+#[derive(Clone, Debug, Default)]
+pub struct BulletChart {
+    /// The shared chart options (size, series, title/legend, axes); exposed
+    /// directly on the chart through `Deref`, e.g. `chart.title_text`.
+    pub base: ChartBase,
+    y_axis_configs: Vec<YAxisConfig>,
+
+    // ── Bullet-specific fields ────────────────────────────────────────────────
+    /// The bullets.
+    pub data: Vec<BulletData>,
+
+    /// Draws the bullets as vertical columns instead of horizontal rows.
+    pub vertical: bool,
+
+    /// Colors of the range bands, from the first (lowest) range on.  Defaults
+    /// to gray shades that fit the light or dark theme.
+    pub range_colors: Vec<Color>,
+
+    /// Color of the measure bar.  Defaults to the first `series_colors` entry.
+    pub measure_color: Color,
+
+    /// Color of the target marker.  Defaults to the title font color.
+    pub target_color: Color,
+
+    /// Number of scale divisions of every bullet (default: 5).
+    pub split_number: usize,
+
+    /// Formatter of the scale labels, supporting `{c}` and `{t}`.
+    pub axis_formatter: String,
+}
+
+impl std::ops::Deref for BulletChart {
+    type Target = ChartBase;
+    fn deref(&self) -> &ChartBase {
+        &self.base
+    }
+}
+impl std::ops::DerefMut for BulletChart {
+    fn deref_mut(&mut self) -> &mut ChartBase {
+        &mut self.base
+    }
+}
+
+impl BulletChart {
+    fn fill_default(&mut self, t: &Theme) {
+        if self.legend_show.is_none() {
+            self.legend_show = Some(false);
+        }
+        if self.range_colors.is_empty() {
+            self.range_colors.clone_from(&t.bullet_range_colors);
+        }
+        if self.measure_color.is_zero() {
+            self.measure_color = get_color(&self.series_colors, 0);
+        }
+        if self.target_color.is_zero() {
+            self.target_color = self.title_font_color;
+        }
+        if self.split_number == 0 {
+            self.split_number = 5;
+        }
+    }
+
+    /// Creates a bullet chart with the default theme.
+    pub fn new(data: Vec<BulletData>) -> BulletChart {
+        BulletChart::new_with_theme(data, &get_default_theme_name())
+    }
+
+    /// Creates a bullet chart with the given theme.
+    pub fn new_with_theme(data: Vec<BulletData>, theme: &str) -> BulletChart {
+        let mut c = BulletChart {
+            data,
+            ..Default::default()
+        };
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.fill_default(&t);
+        c
+    }
+
+    /// Creates a bullet chart from JSON options.
+    pub fn from_json(json: &str) -> canvas::Result<BulletChart> {
+        let mut c = BulletChart {
+            ..Default::default()
+        };
+        let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
+        if let Some(v) = get_bullet_data_list_from_value(&value) {
+            c.data = v;
+        }
+        if let Some(v) = get_bool_from_value(&value, "vertical") {
+            c.vertical = v;
+        }
+        if let Some(v) = get_color_slice_from_value(&value, "range_colors")? {
+            c.range_colors = v;
+        }
+        if let Some(v) = get_color_from_value(&value, "measure_color")? {
+            c.measure_color = v;
+        }
+        if let Some(v) = get_color_from_value(&value, "target_color")? {
+            c.target_color = v;
+        }
+        if let Some(v) = get_usize_from_value(&value, "split_number") {
+            c.split_number = v;
+        }
+        if let Some(v) = get_string_from_value(&value, "axis_formatter") {
+            c.axis_formatter = v;
+        }
+        c.fill_default(&get_theme_from_value(&value));
+        Ok(c)
+    }
+
+    /// Returns the scale maximum of a bullet.
+    fn get_scale_max(item: &BulletData) -> f32 {
+        let max = item
+            .ranges
+            .iter()
+            .copied()
+            .chain([item.measure, item.target.unwrap_or_default()])
+            .fold(0.0_f32, f32::max);
+        if max <= 0.0 { 1.0 } else { max }
+    }
+
+    /// Renders the chart to an SVG string.
+    pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
+        if self.data.is_empty() {
+            return Err(canvas::Error::Params {
+                message: "data is empty".to_string(),
+            });
+        }
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        self.render_background(c.child(Box::default()));
+        c.margin = self.margin.clone();
+
+        let title_height = self.render_title(c.child(Box::default()));
+        let legend_height = self.render_legend(c.child(Box::default()));
+        let axis_top = title_height.max(legend_height);
+
+        let mut body = if axis_top > 0.0 {
+            c.child(Box {
+                top: axis_top,
+                ..Default::default()
+            })
+        } else {
+            c.child(Box::default())
+        };
+        if self.vertical {
+            self.render_vertical(&mut body);
+        } else {
+            self.render_horizontal(&mut body);
+        }
+
+        c.svg()
+    }
+
+    fn render_horizontal(&self, c: &mut Canvas) {
+        let label_font_size = self.x_axis_font_size;
+        let name_width = self
+            .data
+            .iter()
+            .filter_map(|item| {
+                measure_text_width_family(
+                    &self.font_family,
+                    self.series_label_font_size,
+                    &item.name,
+                )
+                .ok()
+                .map(|b| b.width())
+            })
+            .fold(0.0_f32, f32::max);
+        let name_width = if name_width > 0.0 {
+            name_width + 10.0
+        } else {
+            0.0
+        };
+        let width = c.width() - name_width - 10.0;
+        let row_height = c.height() / self.data.len() as f32;
+        let axis_height = label_font_size + 8.0;
+        let band_height = ((row_height - axis_height) * 0.7).clamp(4.0, 40.0);
+
+        for (i, item) in self.data.iter().enumerate() {
+            let max = Self::get_scale_max(item);
+            let get_x = |value: f32| name_width + value.clamp(0.0, max) / max * width;
+            let top = i as f32 * row_height + (row_height - axis_height - band_height) / 2.0;
+
+            // qualitative ranges
+            let mut prev = 0.0;
+            for (index, range) in item.ranges.iter().enumerate() {
+                c.rect(Rect {
+                    fill: Some(get_color(&self.range_colors, index).into()),
+                    left: get_x(prev),
+                    top,
+                    width: get_x(*range) - get_x(prev),
+                    height: band_height,
+                    ..Default::default()
+                });
+                prev = *range;
+            }
+
+            // measure
+            let measure_height = band_height / 3.0;
+            c.rect(Rect {
+                fill: Some(self.measure_color.into()),
+                left: name_width,
+                top: top + (band_height - measure_height) / 2.0,
+                width: get_x(item.measure) - name_width,
+                height: measure_height,
+                title: Some(format!("{}: {}", item.name, format_float(item.measure))),
+                ..Default::default()
+            });
+
+            // target
+            if let Some(target) = item.target {
+                let x = get_x(target);
+                c.line(Line {
+                    color: Some(self.target_color),
+                    stroke_width: 3.0,
+                    left: x,
+                    top: top + band_height * 0.15,
+                    right: x,
+                    bottom: top + band_height * 0.85,
+                    ..Default::default()
+                });
+            }
+
+            // name
+            if name_width > 0.0 {
+                c.text(Text {
+                    text: item.name.clone(),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.series_label_font_color),
+                    font_size: Some(self.series_label_font_size),
+                    font_weight: Some("bold".to_string()),
+                    dominant_baseline: Some("middle".to_string()),
+                    text_anchor: Some("end".to_string()),
+                    x: Some(name_width - 10.0),
+                    y: Some(top + band_height / 2.0),
+                    ..Default::default()
+                });
+            }
+
+            // scale
+            for index in 0..=self.split_number {
+                let value = max * index as f32 / self.split_number as f32;
+                let x = get_x(value);
+                let text = format_value(value, &self.axis_formatter);
+                c.line(Line {
+                    color: Some(self.x_axis_stroke_color),
+                    left: x,
+                    top: top + band_height,
+                    right: x,
+                    bottom: top + band_height + 4.0,
+                    ..Default::default()
+                });
+                c.text(Text {
+                    text,
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.x_axis_font_color),
+                    font_size: Some(label_font_size),
+                    dominant_baseline: Some("hanging".to_string()),
+                    text_anchor: Some("middle".to_string()),
+                    x: Some(x),
+                    y: Some(top + band_height + 6.0),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    fn render_vertical(&self, c: &mut Canvas) {
+        let y_axis_config = get_y_axis_config(&self.y_axis_configs, 0);
+        let label_font_size = y_axis_config.axis_font_size;
+        let name_height = self.series_label_font_size + 10.0;
+        let height = c.height() - name_height - label_font_size / 2.0;
+        let column_width = c.width() / self.data.len() as f32;
+
+        for (i, item) in self.data.iter().enumerate() {
+            let max = Self::get_scale_max(item);
+            let scale_labels: Vec<String> = (0..=self.split_number)
+                .map(|index| {
+                    let value = max * index as f32 / self.split_number as f32;
+                    format_value(value, &self.axis_formatter)
+                })
+                .collect();
+            let axis_width = scale_labels
+                .iter()
+                .filter_map(|text| {
+                    measure_text_width_family(&self.font_family, label_font_size, text)
+                        .ok()
+                        .map(|b| b.width())
+                })
+                .fold(0.0_f32, f32::max)
+                + 8.0;
+            let band_width = ((column_width - axis_width) * 0.5).clamp(4.0, 40.0);
+            let left = i as f32 * column_width + (column_width - band_width + axis_width) / 2.0;
+            let top = label_font_size / 2.0;
+            let get_y = |value: f32| top + height - value.clamp(0.0, max) / max * height;
+
+            let mut prev = 0.0;
+            for (index, range) in item.ranges.iter().enumerate() {
+                c.rect(Rect {
+                    fill: Some(get_color(&self.range_colors, index).into()),
+                    left,
+                    top: get_y(*range),
+                    width: band_width,
+                    height: get_y(prev) - get_y(*range),
+                    ..Default::default()
+                });
+                prev = *range;
+            }
+
+            let measure_width = band_width / 3.0;
+            c.rect(Rect {
+                fill: Some(self.measure_color.into()),
+                left: left + (band_width - measure_width) / 2.0,
+                top: get_y(item.measure),
+                width: measure_width,
+                height: get_y(0.0) - get_y(item.measure),
+                title: Some(format!("{}: {}", item.name, format_float(item.measure))),
+                ..Default::default()
+            });
+
+            if let Some(target) = item.target {
+                let y = get_y(target);
+                c.line(Line {
+                    color: Some(self.target_color),
+                    stroke_width: 3.0,
+                    left: left + band_width * 0.15,
+                    top: y,
+                    right: left + band_width * 0.85,
+                    bottom: y,
+                    ..Default::default()
+                });
+            }
+
+            c.text(Text {
+                text: item.name.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(self.series_label_font_size),
+                font_weight: Some("bold".to_string()),
+                dominant_baseline: Some("middle".to_string()),
+                text_anchor: Some("middle".to_string()),
+                x: Some(left + band_width / 2.0),
+                y: Some(top + height + name_height / 2.0 + 2.0),
+                ..Default::default()
+            });
+
+            for (index, text) in scale_labels.into_iter().enumerate() {
+                let y = get_y(max * index as f32 / self.split_number as f32);
+                c.line(Line {
+                    color: Some(self.x_axis_stroke_color),
+                    left: left - 4.0,
+                    top: y,
+                    right: left,
+                    bottom: y,
+                    ..Default::default()
+                });
+                c.text(Text {
+                    text,
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(y_axis_config.axis_font_color),
+                    font_size: Some(label_font_size),
+                    dominant_baseline: Some("middle".to_string()),
+                    text_anchor: Some("end".to_string()),
+                    x: Some(left - 6.0),
+                    y: Some(y),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BulletChart;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn bullet_chart_basic() {
+        let mut chart = BulletChart::new(vec![
+            ("Revenue", 270.0, 250.0, vec![150.0, 225.0, 300.0]).into(),
+            ("Profit", 23.0, 26.0, vec![20.0, 25.0, 30.0]).into(),
+            ("Satisfaction", 4.4, 4.6, vec![3.5, 4.25, 5.0]).into(),
+        ]);
+        chart.title_text = "Bullet".to_string();
+        chart.height = 300.0;
+        assert_eq!(
+            include_str!("../../asset/bullet_chart/basic.svg"),
+            chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn bullet_chart_vertical_json() {
+        let chart = BulletChart::from_json(
+            r##"{
+                "theme": "dark",
+                "vertical": true,
+                "axis_formatter": "{t}",
+                "data": [
+                    {"name": "Orders", "measure": 1800, "target": 2000, "ranges": [1000, 1500, 2500]},
+                    {"name": "Returns", "measure": 40, "ranges": [50, 100]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("2,500"));
+        // dark theme range colors
+        assert!(svg.contains(r##"fill="#5F5F64""##));
+        // only the first bullet has a target marker
+        assert_eq!(1, svg.matches(r#"stroke-width="3""#).count());
+        assert!(svg.contains("<title>Returns: 40</title>"));
+    }
+
+    #[test]
+    fn bullet_chart_vertical_default() {
+        let chart = BulletChart {
+            vertical: true,
+            data: vec![("Revenue", 270.0, 250.0, vec![150.0, 225.0, 300.0]).into()],
+            ..Default::default()
+        };
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("<title>Revenue: 270</title>"));
+    }
+
+    #[test]
+    fn bullet_chart_empty() {
+        let chart = BulletChart::new(vec![]);
+        assert!(chart.svg().is_err());
+    }
+}
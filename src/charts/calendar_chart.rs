@@ -11,108 +11,22 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::date::{SECONDS_PER_DAY, civil_from_days, day_of_week, days_from_civil, parse_date};
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme};
+use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
 
-// ── Simple date helpers (no external crate) ──────────────────────────────────
-
-fn is_leap_year(year: i32) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
-}
-
-fn days_in_month(year: i32, month: u32) -> u32 {
-    match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => {
-            if is_leap_year(year) {
-                29
-            } else {
-                28
-            }
-        }
-        _ => 0,
-    }
-}
-
-/// Returns day-of-week: 0 = Sunday, 1 = Monday, …, 6 = Saturday.
-/// Uses Tomohiko Sakamoto's algorithm.
-fn day_of_week(year: i32, month: u32, day: u32) -> u32 {
-    let t: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
-    let y = if month < 3 { year - 1 } else { year };
-    ((y + y / 4 - y / 100 + y / 400 + t[(month - 1) as usize] + day as i32).rem_euclid(7)) as u32
-}
-
-/// Parses "YYYY-MM-DD" → (year, month, day).  Returns None on malformed input.
-fn parse_date(s: &str) -> Option<(i32, u32, u32)> {
-    let mut parts = s.splitn(3, '-');
-    let year: i32 = parts.next()?.parse().ok()?;
-    let month: u32 = parts.next()?.parse().ok()?;
-    let day: u32 = parts.next()?.parse().ok()?;
-    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
-        return None;
-    }
-    Some((year, month, day))
-}
-
-/// Julian Day Number – used only to compute day differences.
-fn jdn(year: i32, month: u32, day: u32) -> i64 {
-    let a = (14_i64 - month as i64) / 12;
-    let y = year as i64 + 4800 - a;
-    let m = month as i64 + 12 * a - 3;
-    day as i64 + (153 * m + 2) / 5 + 365 * y + y / 4 - y / 100 + y / 400 - 32045
-}
-
-/// Number of days from `(y1,m1,d1)` to `(y2,m2,d2)` (negative if before).
-fn days_diff(y1: i32, m1: u32, d1: u32, y2: i32, m2: u32, d2: u32) -> i64 {
-    jdn(y2, m2, d2) - jdn(y1, m1, d1)
-}
-
-/// Advance a date by `n` days (n ≥ 0).
-fn add_days(mut year: i32, mut month: u32, mut day: u32, mut n: u32) -> (i32, u32, u32) {
-    while n > 0 {
-        let dim = days_in_month(year, month);
-        let remaining = dim - day;
-        if n <= remaining {
-            day += n;
-            n = 0;
-        } else {
-            n -= remaining + 1;
-            day = 1;
-            month += 1;
-            if month > 12 {
-                month = 1;
-                year += 1;
-            }
-        }
-    }
-    (year, month, day)
-}
-
-fn current_year() -> i32 {
-    // std::time gives seconds since Unix epoch; derive the year without external crates.
+fn current_year() -> i64 {
     let secs = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0);
-    // Rough: 400-year Gregorian cycle = 146_097 days = 12_622_780_800 s
-    let days = (secs / 86_400) as i64;
-    // Shift to 1970-01-01; use the same JDN approach as jdn()
-    let jdn = days + 2_440_588; // JDN of 1970-01-01
-    let a = jdn + 32_044;
-    let b = (4 * a + 3) / 146_097;
-    let c = a - (146_097 * b) / 4;
-    let d = (4 * c + 3) / 1_461;
-    let e = c - (1_461 * d) / 4;
-    let m = (5 * e + 2) / 153;
-    let year = 100 * b + d - 4_800 + m / 10;
-    year as i32
+    civil_from_days(secs as i64 / SECONDS_PER_DAY).0
 }
 
 static MONTH_ABBR: [&str; 12] = [
@@ -190,7 +104,7 @@ impl std::ops::DerefMut for CalendarChart {
 }
 
 impl CalendarChart {
-    fn fill_default(&mut self) {
+    fn fill_default(&mut self, t: &Theme) {
         if self.cell_size <= 0.0 {
             self.cell_size = 13.0;
         }
@@ -206,21 +120,14 @@ impl CalendarChart {
         if self.show_dow_labels.is_empty() {
             self.show_dow_labels = vec![1, 3, 5]; // Mon, Wed, Fri
         }
-        // default colors – GitHub contribution-graph palette
         if self.min_color.is_zero() {
-            self.min_color = (235, 237, 240).into();
+            self.min_color = t.calendar_min_color;
         }
         if self.max_color.is_zero() {
-            self.max_color = (33, 110, 57).into();
+            self.max_color = t.calendar_max_color;
         }
         if self.empty_color.is_zero() {
-            let mut c: Color = if self.is_light {
-                (235, 237, 240).into()
-            } else {
-                (40, 40, 45).into()
-            };
-            c = c.with_alpha(180);
-            self.empty_color = c;
+            self.empty_color = t.calendar_empty_color;
         }
         // auto min / max from data when not explicitly set
         if self.min == 0.0 && self.max == 0.0 && !self.data.is_empty() {
@@ -254,17 +161,8 @@ impl CalendarChart {
         if range <= 0.0 {
             return self.max_color;
         }
-        let t = (value - self.min) / range;
-        let lerp = |a: u8, b: u8| -> u8 {
-            let diff = (b as f32 - a as f32) * t;
-            (a as f32 + diff).round() as u8
-        };
-        Color {
-            r: lerp(self.min_color.r, self.max_color.r),
-            g: lerp(self.min_color.g, self.max_color.g),
-            b: lerp(self.min_color.b, self.max_color.b),
-            a: lerp(self.min_color.a, self.max_color.a),
-        }
+        self.min_color
+            .mix(self.max_color, (value - self.min) / range)
     }
 
     /// Creates a calendar chart for `year` (Jan 1 – Dec 31) with default theme.
@@ -281,8 +179,8 @@ impl CalendarChart {
             ..Default::default()
         };
         let t = get_theme(theme);
-        c.base.fill_theme(t, &mut c.y_axis_configs);
-        c.fill_default();
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.fill_default(&t);
         // Auto-size to fit the calendar
         c.width = c.auto_width();
         c.height = c.auto_height();
@@ -307,13 +205,13 @@ impl CalendarChart {
         if let Some(max) = get_f32_from_value(&value, "max") {
             c.max = max;
         }
-        if let Some(col) = get_color_from_value(&value, "min_color") {
+        if let Some(col) = get_color_from_value(&value, "min_color")? {
             c.min_color = col;
         }
-        if let Some(col) = get_color_from_value(&value, "max_color") {
+        if let Some(col) = get_color_from_value(&value, "max_color")? {
             c.max_color = col;
         }
-        if let Some(col) = get_color_from_value(&value, "empty_color") {
+        if let Some(col) = get_color_from_value(&value, "empty_color")? {
             c.empty_color = col;
         }
         if let Some(v) = get_f32_from_value(&value, "cell_size") {
@@ -347,7 +245,7 @@ impl CalendarChart {
             }
             c.data = items;
         }
-        c.fill_default();
+        c.fill_default(&get_theme_from_value(&value));
         // CalendarChart always auto-sizes: layout is driven by cell_size/cell_gap,
         // not by a fixed canvas width. Users control size via those fields instead.
         c.width = c.auto_width();
@@ -365,7 +263,7 @@ impl CalendarChart {
             Some(d) => d,
             None => return 53,
         };
-        let total_days = days_diff(sy, sm, sd, ey, em, ed) + 1;
+        let total_days = days_from_civil(ey, em, ed) - days_from_civil(sy, sm, sd) + 1;
         if total_days <= 0 {
             return 1;
         }
@@ -398,6 +296,9 @@ impl CalendarChart {
 
     /// Renders the calendar heatmap to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let (sy, sm, sd) = parse_date(&self.start_date).ok_or_else(|| canvas::Error::Params {
             message: format!("invalid start_date: {}", self.start_date),
         })?;
@@ -405,7 +306,8 @@ impl CalendarChart {
             message: format!("invalid end_date: {}", self.end_date),
         })?;
 
-        let total_days = days_diff(sy, sm, sd, ey, em, ed) + 1;
+        let start_days = days_from_civil(sy, sm, sd);
+        let total_days = days_from_civil(ey, em, ed) - start_days + 1;
         if total_days <= 0 {
             return Err(canvas::Error::Params {
                 message: "end_date must be >= start_date".to_string(),
@@ -458,11 +360,9 @@ impl CalendarChart {
         // We track which week each month starts in.
         let month_font_size = self.x_axis_font_size.max(10.0);
         let month_color = self.x_axis_font_color;
-        let mut cur_y = sy;
-        let mut cur_m = sm;
-        let mut cur_d = sd;
         let mut last_month_col: Option<u32> = None;
         for day_idx in 0..total_days {
+            let (_, cur_m, cur_d) = civil_from_days(start_days + day_idx);
             let col = ((start_dow + day_idx) / 7) as u32;
             // Is this the first day of a new month within the visible range?
             if cur_d == 1 && last_month_col != Some(col) {
@@ -480,18 +380,11 @@ impl CalendarChart {
                 });
                 last_month_col = Some(col);
             }
-            // Advance date by one day
-            let next = add_days(cur_y, cur_m, cur_d, 1);
-            cur_y = next.0;
-            cur_m = next.1;
-            cur_d = next.2;
         }
 
         // ── Day cells ─────────────────────────────────────────────────────────
-        let mut cy = sy;
-        let mut cm = sm;
-        let mut cd = sd;
         for day_idx in 0..total_days {
+            let (cy, cm, cd) = civil_from_days(start_days + day_idx);
             let col = ((start_dow + day_idx) / 7) as usize;
             let row = ((start_dow + day_idx) % 7) as usize;
 
@@ -516,11 +409,6 @@ impl CalendarChart {
                 ry: Some(2.0),
                 ..Default::default()
             });
-
-            let next = add_days(cy, cm, cd, 1);
-            cy = next.0;
-            cm = next.1;
-            cd = next.2;
         }
 
         c.svg()
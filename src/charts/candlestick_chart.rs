@@ -11,13 +11,13 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme};
+use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
 use serde::{Deserialize, Serialize};
 
@@ -68,18 +68,18 @@ impl std::ops::DerefMut for CandlestickChart {
 }
 
 impl CandlestickChart {
-    fn fill_default(&mut self) {
+    fn fill_default(&mut self, t: &Theme) {
         if self.candlestick_up_color.is_zero() {
-            self.candlestick_up_color = (236, 0, 0).into();
+            self.candlestick_up_color = t.candlestick_up_color;
         }
         if self.candlestick_up_border_color.is_zero() {
-            self.candlestick_up_border_color = (138, 0, 0).into();
+            self.candlestick_up_border_color = t.candlestick_up_border_color;
         }
         if self.candlestick_down_color.is_zero() {
-            self.candlestick_down_color = (0, 218, 60).into();
+            self.candlestick_down_color = t.candlestick_down_color;
         }
         if self.candlestick_down_border_color.is_zero() {
-            self.candlestick_down_border_color = (0, 143, 40).into();
+            self.candlestick_down_border_color = t.candlestick_down_border_color;
         }
     }
     /// Creates a candlestick chart from json.
@@ -88,19 +88,19 @@ impl CandlestickChart {
             ..Default::default()
         };
         let value = c.base.fill_option(data, &mut c.y_axis_configs)?;
-        if let Some(value) = get_color_from_value(&value, "candlestick_up_color") {
+        if let Some(value) = get_color_from_value(&value, "candlestick_up_color")? {
             c.candlestick_up_color = value;
         }
-        if let Some(value) = get_color_from_value(&value, "candlestick_up_border_color") {
+        if let Some(value) = get_color_from_value(&value, "candlestick_up_border_color")? {
             c.candlestick_up_border_color = value;
         }
-        if let Some(value) = get_color_from_value(&value, "candlestick_down_color") {
+        if let Some(value) = get_color_from_value(&value, "candlestick_down_color")? {
             c.candlestick_down_color = value;
         }
-        if let Some(value) = get_color_from_value(&value, "candlestick_down_border_color") {
+        if let Some(value) = get_color_from_value(&value, "candlestick_down_border_color")? {
             c.candlestick_down_border_color = value;
         }
-        c.fill_default();
+        c.fill_default(&get_theme_from_value(&value));
         Ok(c)
     }
     /// Creates a candlestick chart with custom theme.
@@ -122,8 +122,8 @@ impl CandlestickChart {
         c.series_list = series_list;
         c.x_axis_data = x_axis_data;
         let theme = get_theme(theme);
-        c.base.fill_theme(theme, &mut c.y_axis_configs);
-        c.fill_default();
+        c.base.fill_theme(theme.clone(), &mut c.y_axis_configs);
+        c.fill_default(&theme);
         c
     }
     /// Creates a candlestick chart with default theme.
@@ -132,6 +132,9 @@ impl CandlestickChart {
     }
     /// Converts candlestick chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let mut x_axis_height = self.x_axis_height;
@@ -141,7 +144,7 @@ impl CandlestickChart {
         let axis_top = self.render_header(&mut c);
 
         let (left_y_axis_values, mut left_y_axis_width) =
-            self.get_y_axis_values(&self.y_axis_configs, 0);
+            self.get_y_axis_values(&self.y_axis_configs, 0)?;
         if self.y_axis_hidden {
             left_y_axis_width = 0.0;
         }
@@ -273,6 +276,7 @@ impl CandlestickChart {
             max_height,
             axis_height,
             self.x_axis_data.len(),
+            &StackBaseline::Zero,
             None,
             false,
         );
@@ -15,6 +15,7 @@ use super::component::{
     Polyline, Rect, SmoothLine, SmoothLineFill, StraightLine, StraightLineFill, Text, generate_svg,
 };
 
+use super::color_scheme::color_variables_style;
 use super::{measure_text_width_family, util::*};
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -252,13 +253,16 @@ impl Canvas {
     /// Appends smooth line points with fill color widget to canvas.
     pub fn smooth_line_fill(&mut self, fill: SmoothLineFill) -> Box {
         let mut c = fill;
-        for p in c.points.iter_mut() {
+        for p in c.points.iter_mut().chain(c.bottom_points.iter_mut()) {
             p.x += self.margin.left;
             p.y += self.margin.top
         }
         c.bottom += self.margin.top;
         let mut b = get_box_of_points(&c.points);
         b.bottom = c.bottom;
+        if !c.bottom_points.is_empty() {
+            b.bottom = get_box_of_points(&c.bottom_points).bottom;
+        }
         self.append(Component::SmoothLineFill(c));
         b
     }
@@ -344,6 +348,9 @@ impl Canvas {
     }
     /// Generates the svg of canvas.
     pub fn svg(&self) -> Result<String> {
+        if color_variables_style().is_some() {
+            return self.svg_with_style("");
+        }
         let components = self.components.borrow();
         let mut data = String::with_capacity(components.len() * 128);
         let mut grad_seen = HashSet::new();
@@ -356,10 +363,15 @@ impl Canvas {
         Ok(generate_svg(self.width, self.height, self.x, self.y, data))
     }
     /// Generates the svg of canvas with an embedded CSS style block prepended.
+    /// While a chart renders with a color scheme, the block also defines its
+    /// color variables.
     pub fn svg_with_style(&self, style: &str) -> Result<String> {
         let components = self.components.borrow();
-        let mut data = String::with_capacity(components.len() * 128 + style.len() + 30);
+        let variables = color_variables_style().unwrap_or_default();
+        let mut data =
+            String::with_capacity(components.len() * 128 + style.len() + variables.len() + 30);
         data.push_str("<style>");
+        data.push_str(&variables);
         data.push_str(style);
         data.push_str("</style>");
         let mut grad_seen = HashSet::new();
@@ -573,6 +585,7 @@ Hello World!
                 (90.0, 40.0),
             ]),
             bottom: 150.0,
+            ..Default::default()
         });
         assert_eq!("(10,10,90,150)", b.to_string());
         assert_eq!(
@@ -10,10 +10,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::error::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-/// An RGBA color. Parses from hex strings (`"#345"`, `"#ffcc00"`,
-/// `"#ffcc0080"`) and converts from `(r, g, b)` / `(r, g, b, a)` tuples.
+/// An RGBA color. Parses from CSS color strings (see [`Color::parse`]) and
+/// converts from `(r, g, b)` / `(r, g, b, a)` tuples.
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
 pub struct Color {
     /// Red channel.
@@ -81,6 +83,100 @@ impl Color {
         b = b * b * 0.114;
         (r + g + b).sqrt() > 127.5
     }
+    /// Creates an opaque color from hue (degrees), saturation and lightness
+    /// (0.0 to 1.0).
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+        (channel(r), channel(g), channel(b)).into()
+    }
+    /// Converts the color to hue (degrees), saturation and lightness (0.0 to
+    /// 1.0).
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (h, s, l)
+    }
+    fn with_hsl(&self, h: f32, s: f32, l: f32) -> Color {
+        Color::from_hsl(h, s, l).with_alpha(self.a)
+    }
+    /// Raises the lightness by `amount` (0.0 to 1.0), keeping the alpha.
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        self.with_hsl(h, s, l + amount)
+    }
+    /// Lowers the lightness by `amount` (0.0 to 1.0), keeping the alpha.
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+    /// Raises the saturation by `amount` (0.0 to 1.0), a negative amount
+    /// desaturates. The alpha is kept.
+    pub fn saturate(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        self.with_hsl(h, s + amount, l)
+    }
+    /// Mixes the color with `other`, from the color itself at a `ratio` of 0.0
+    /// to `other` at 1.0, alpha included.
+    pub fn mix(&self, other: Color, ratio: f32) -> Color {
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+        Color {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
+    /// Relative luminance as defined by WCAG, from 0.0 (black) to 1.0
+    /// (white).
+    pub fn luminance(&self) -> f32 {
+        0.2126 * srgb_to_linear(self.r)
+            + 0.7152 * srgb_to_linear(self.g)
+            + 0.0722 * srgb_to_linear(self.b)
+    }
+    /// WCAG contrast ratio with `other`, from 1.0 to 21.0; text needs 4.5 to
+    /// be readable.
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+    /// Returns the candidate with the highest contrast ratio against the
+    /// color, e.g. to pick a label color for a filled shape.
+    pub fn contrast_color(&self, candidates: &[Color]) -> Color {
+        candidates
+            .iter()
+            .copied()
+            .max_by(|a, b| self.contrast_ratio(*a).total_cmp(&self.contrast_ratio(*b)))
+            .unwrap_or_default()
+    }
 }
 
 impl From<(u8, u8, u8)> for Color {
@@ -104,34 +200,280 @@ impl From<(u8, u8, u8, u8)> for Color {
     }
 }
 
-fn parse_hex(hex: &str) -> u8 {
-    u8::from_str_radix(hex, 16).unwrap_or_default()
+/// The CSS named colors, sorted by name.
+static NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// Parses a CSS number, or a percentage of `percent_base`.
+fn parse_number(value: &str, percent_base: f32) -> Option<f32> {
+    let value = value.trim();
+    if let Some(percent) = value.strip_suffix('%') {
+        return percent
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .map(|v| v / 100.0 * percent_base);
+    }
+    value.parse::<f32>().ok().filter(|v| v.is_finite())
 }
 
-impl From<&str> for Color {
-    fn from(value: &str) -> Self {
-        let mut c = Color::default();
-        let Some(hex) = value.strip_prefix('#') else {
-            return c;
-        };
-        c.a = 255;
-        // Hex colors are ASCII; anything else falls through to the 0 default
-        // that invalid hex digits produce anyway.
-        if !hex.is_ascii() {
-            return c;
+/// Splits the arguments of a CSS color function, accepting both the comma
+/// and the space separated (`rgb(255 0 0 / 50%)`) syntax.
+fn split_args(args: &str) -> Vec<&str> {
+    args.split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        // Shorthand "#abc": each digit doubles ("a" → 0xaa = digit * 17).
+        3 => Some((digit(0)?, digit(1)?, digit(2)?).into()),
+        4 => Some((digit(0)?, digit(1)?, digit(2)?, digit(3)?).into()),
+        6 => Some((pair(0)?, pair(2)?, pair(4)?).into()),
+        8 => Some((pair(0)?, pair(2)?, pair(4)?, pair(6)?).into()),
+        _ => None,
+    }
+}
+
+fn parse_function_color(name: &str, args: &str) -> Option<Color> {
+    let args = split_args(args);
+    if !matches!(args.len(), 3 | 4) {
+        return None;
+    }
+    let alpha = match args.get(3) {
+        Some(v) => parse_number(v, 1.0)?,
+        None => 1.0,
+    };
+    let mut color = match name {
+        "rgb" | "rgba" => {
+            let channel =
+                |v: &str| parse_number(v, 255.0).map(|v| v.clamp(0.0, 255.0).round() as u8);
+            (channel(args[0])?, channel(args[1])?, channel(args[2])?).into()
         }
-        if hex.len() == 3 {
-            // Shorthand "#abc": each digit doubles ("a" → 0xaa = digit * 17).
-            let digit = |i: usize| parse_hex(hex.get(i..i + 1).unwrap_or_default()) * 17;
-            c.r = digit(0);
-            c.g = digit(1);
-            c.b = digit(2);
-        } else {
-            c.r = parse_hex(hex.get(0..2).unwrap_or_default());
-            c.g = parse_hex(hex.get(2..4).unwrap_or_default());
-            c.b = parse_hex(hex.get(4..6).unwrap_or_default());
+        "hsl" | "hsla" => {
+            let hue = args[0].strip_suffix("deg").unwrap_or(args[0]);
+            let hue = hue.parse::<f32>().ok().filter(|v| v.is_finite())?;
+            // The saturation and lightness are percentages, `%` is optional.
+            let percent =
+                |v: &str| parse_number(v.strip_suffix('%').unwrap_or(v), 1.0).map(|v| v / 100.0);
+            Color::from_hsl(hue, percent(args[1])?, percent(args[2])?)
         }
-        c
+        _ => return None,
+    };
+    color.a = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Some(color)
+}
+
+impl Color {
+    /// Parses a CSS color: hex (`"#345"`, `"#3458"`, `"#ffcc00"`,
+    /// `"#ffcc0080"`), `rgb()`/`rgba()`, `hsl()`/`hsla()`, a named color or
+    /// `transparent`. Unlike the `From<&str>` conversion, an invalid color is
+    /// an error.
+    pub fn parse(value: &str) -> Result<Color> {
+        let text = value.trim().to_ascii_lowercase();
+        let color = if let Some(hex) = text.strip_prefix('#') {
+            parse_hex_color(hex)
+        } else if let Some((name, args)) = text.split_once('(') {
+            args.strip_suffix(')')
+                .and_then(|args| parse_function_color(name.trim(), args))
+        } else if text == "transparent" {
+            Some(Color::transparent())
+        } else {
+            NAMED_COLORS
+                .binary_search_by(|(name, _)| name.cmp(&text.as_str()))
+                .ok()
+                .map(|index| {
+                    let value = NAMED_COLORS[index].1;
+                    (
+                        (value >> 16) as u8,
+                        ((value >> 8) & 0xff) as u8,
+                        (value & 0xff) as u8,
+                    )
+                        .into()
+                })
+        };
+        color.ok_or_else(|| Error::Params {
+            message: format!("color \"{value}\" is invalid"),
+        })
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+    fn from_str(value: &str) -> Result<Color> {
+        Color::parse(value)
+    }
+}
+
+impl From<&str> for Color {
+    /// Parses the color as [`Color::parse`], an invalid color becomes the
+    /// zero color.
+    fn from(value: &str) -> Self {
+        Color::parse(value).unwrap_or_default()
+    }
+}
+
+/// Converts an sRGB channel to linear light (0.0 to 1.0).
+pub(crate) fn srgb_to_linear(v: u8) -> f32 {
+    let v = v as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
     }
 }
 
@@ -165,6 +507,72 @@ mod tests {
         assert_eq!("rgba(51,51,51,0.2)", c.rgba());
     }
     #[test]
+    fn color_from_hsl() {
+        assert_eq!("#FF0000", Color::from_hsl(0.0, 1.0, 0.5).hex());
+        assert_eq!("#00FF00", Color::from_hsl(120.0, 1.0, 0.5).hex());
+        assert_eq!("#0000FF", Color::from_hsl(-120.0, 1.0, 0.5).hex());
+        assert_eq!("#808080", Color::from_hsl(200.0, 0.0, 0.5).hex());
+        assert_eq!("#5471C6", Color::from_hsl(225.0, 0.5, 0.553).hex());
+    }
+    #[test]
+    fn color_parse() {
+        for (value, expected) in [
+            ("#345", "rgba(51,68,85,1.0)"),
+            ("#3458", "rgba(51,68,85,0.5)"),
+            ("#FFCC00", "rgba(255,204,0,1.0)"),
+            ("#ffcc0080", "rgba(255,204,0,0.5)"),
+            ("rgb(255, 99, 71)", "rgba(255,99,71,1.0)"),
+            ("rgba(255,99,71,0.2)", "rgba(255,99,71,0.2)"),
+            ("rgb(100% 0% 50% / 40%)", "rgba(255,0,128,0.4)"),
+            ("hsl(120, 100%, 25%)", "rgba(0,128,0,1.0)"),
+            ("hsla(240deg 100% 50% / 0.5)", "rgba(0,0,255,0.5)"),
+            ("RebeccaPurple", "rgba(102,51,153,1.0)"),
+            (" tomato ", "rgba(255,99,71,1.0)"),
+            ("transparent", "rgba(0,0,0,0.0)"),
+        ] {
+            assert_eq!(expected, Color::parse(value).unwrap().rgba(), "{value}");
+        }
+        for value in [
+            "",
+            "#12",
+            "#ggg",
+            "rgb(1, 2)",
+            "hsl(a, 1%, 2%)",
+            "blurple",
+            "rgb(1,2,3",
+        ] {
+            assert_eq!(
+                format!("Params is invalid: color \"{value}\" is invalid"),
+                value.parse::<Color>().unwrap_err().to_string()
+            );
+        }
+        let c: Color = "blurple".into();
+        assert!(c.is_zero());
+    }
+    #[test]
+    fn color_manipulation() {
+        let c: Color = "#5470c6".into();
+        assert_eq!("#A1B0E0", c.lighten(0.2).hex());
+        assert_eq!("#334D9A", c.darken(0.15).hex());
+        assert_eq!("#3D64DD", c.saturate(0.2).hex());
+        assert_eq!("#8D8D8D", c.saturate(-1.0).hex());
+        assert_eq!(128, c.with_alpha(128).lighten(0.1).a);
+        assert_eq!("#AAB8E3", c.mix(Color::white(), 0.5).hex());
+        assert_eq!(
+            "21.0",
+            format!("{:.1}", Color::black().contrast_ratio(Color::white()))
+        );
+        assert_eq!(1.0, c.contrast_ratio(c));
+        assert_eq!(
+            Color::white(),
+            c.contrast_color(&[Color::white(), Color::black()])
+        );
+        assert_eq!(
+            Color::black(),
+            Color::from("#fac858").contrast_color(&[Color::white(), Color::black()])
+        );
+    }
+    #[test]
     fn color_opacity() {
         let mut c: Color = (200, 200, 200).into();
         assert_eq!(1.0, c.opacity());
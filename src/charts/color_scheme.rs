@@ -0,0 +1,296 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Render-time CSS custom properties for
+//! [`ChartBase::color_scheme`](super::ChartBase::color_scheme).
+//!
+//! Before rendering, every themed color of the chart is swapped for a stand-in
+//! *key* color that no other variable uses. While the chart renders, the
+//! components write a key as `var(--charts-…)` instead of its hex value, and
+//! the canvas prepends the `:root` / `@media (prefers-color-scheme: dark)`
+//! block defining the variables. Keying by the field rather than by the color
+//! value keeps user colors that happen to equal a theme color untouched, and
+//! gives two fields sharing a light color their own dark colors. A key never
+//! equals a color the chart writes by itself, so those stay untouched too.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::color::Color;
+use super::util::format_float;
+
+#[derive(Clone, Debug)]
+struct ColorVariable {
+    name: String,
+    key: Color,
+    light: Color,
+    dark: Color,
+}
+
+impl ColorVariable {
+    fn matches(&self, color: &Color) -> bool {
+        (self.key.r, self.key.g, self.key.b) == (color.r, color.g, color.b)
+    }
+}
+
+/// The CSS variables of the themed colors of one chart.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ColorVariables {
+    items: Vec<ColorVariable>,
+    reserved: Vec<(u8, u8, u8)>,
+}
+
+impl ColorVariables {
+    /// Keeps every `#RRGGBB` color written in `svg`, a render of the chart
+    /// without variables, from being picked as a key.
+    pub(crate) fn reserve(&mut self, svg: &str) {
+        for (index, _) in svg.match_indices('#') {
+            if let Some(hex) = svg.get(index + 1..index + 7)
+                && hex.bytes().all(|b| b.is_ascii_hexdigit())
+                && let Ok(color) = Color::parse(&svg[index..index + 7])
+            {
+                let rgb = (color.r, color.g, color.b);
+                if !self.reserved.contains(&rgb) {
+                    self.reserved.push(rgb);
+                }
+            }
+        }
+    }
+    /// Turns `color` into the variable `--charts-{name}` when it still holds
+    /// the `light` theme value and the `dark` theme changes it, swapping it
+    /// for the key of the variable. Transparent colors are left alone, as
+    /// the components skip them altogether.
+    pub(crate) fn add(&mut self, name: &str, color: &mut Color, light: Color, dark: Color) {
+        if *color != light || light == dark || light.is_transparent() {
+            return;
+        }
+        let name = format!("--charts-{name}");
+        if let Some(item) = self.items.iter().find(|item| item.name == name) {
+            if item.light == light && item.dark == dark {
+                *color = item.key;
+            }
+            return;
+        }
+        // The nearest color that is neither a key, a themed color nor a
+        // reserved one already; the alpha stays, so opacity and transparency
+        // checks are unchanged.
+        let key = (1..=u8::MAX)
+            .map(|i| Color {
+                b: light.b ^ i,
+                ..light
+            })
+            .find(|key| {
+                !self.reserved.contains(&(key.r, key.g, key.b))
+                    && !self
+                        .items
+                        .iter()
+                        .any(|item| item.matches(key) || item.light == *key)
+            })
+            .unwrap_or(light);
+        self.items.push(ColorVariable {
+            name,
+            key,
+            light,
+            dark,
+        });
+        *color = key;
+    }
+    /// Makes the variables visible to [`color_variable`] and
+    /// [`color_variables_style`] until the returned guard is dropped.
+    pub(crate) fn activate(self) -> ColorVariablesGuard {
+        let previous = COLOR_VARIABLES.replace(Some(Rc::new(self)));
+        ColorVariablesGuard { previous }
+    }
+}
+
+thread_local! {
+    static COLOR_VARIABLES: RefCell<Option<Rc<ColorVariables>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previously active variables (if any) on drop.
+pub(crate) struct ColorVariablesGuard {
+    previous: Option<Rc<ColorVariables>>,
+}
+
+impl Drop for ColorVariablesGuard {
+    fn drop(&mut self) {
+        COLOR_VARIABLES.set(self.previous.take());
+    }
+}
+
+/// The `var()` reference for `color` when it is the key of an active
+/// variable. Only the rgb is compared: the alpha of a key that went through
+/// `with_alpha` still comes out as the opacity attribute of the element.
+pub(crate) fn color_variable(color: &Color) -> Option<String> {
+    COLOR_VARIABLES.with_borrow(|variables| {
+        variables
+            .as_ref()?
+            .items
+            .iter()
+            .find(|item| item.matches(color))
+            .map(|item| format!("var({})", item.name))
+    })
+}
+
+fn css_color(color: Color, alpha: f32) -> String {
+    if alpha >= 1.0 {
+        color.hex()
+    } else {
+        format!(
+            "rgba({},{},{},{})",
+            color.r,
+            color.g,
+            color.b,
+            format_float(alpha)
+        )
+    }
+}
+
+/// The CSS block defining the active variables, `None` when there are none.
+/// The element opacity comes from the light color, so a dark color with a
+/// different alpha gets the ratio of the two as its own.
+pub(crate) fn color_variables_style() -> Option<String> {
+    COLOR_VARIABLES.with_borrow(|variables| {
+        let items = &variables.as_ref()?.items;
+        if items.is_empty() {
+            return None;
+        }
+        let mut light = String::new();
+        let mut dark = String::new();
+        for item in items {
+            light.push_str(&format!("{}:{};", item.name, css_color(item.light, 1.0)));
+            let alpha = item.dark.opacity() / item.light.opacity();
+            dark.push_str(&format!("{}:{};", item.name, css_color(item.dark, alpha)));
+        }
+        Some(format!(
+            ":root{{{light}}}@media (prefers-color-scheme: dark){{:root{{{dark}}}}}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, ColorScheme, LineChart, add_theme, get_theme};
+    use pretty_assertions::assert_eq;
+
+    fn new_chart() -> LineChart {
+        let mut chart = LineChart::new(
+            vec![("Email", vec![120.0, 132.0, 101.0]).into()],
+            vec!["Mon".to_string(), "Tue".to_string(), "Wed".to_string()],
+        );
+        chart.title_text = "Title".to_string();
+        chart
+    }
+
+    fn color_scheme(light: &str, dark: &str) -> Option<ColorScheme> {
+        Some(ColorScheme {
+            light: light.to_string(),
+            dark: dark.to_string(),
+        })
+    }
+
+    #[test]
+    fn color_scheme_svg() {
+        let mut chart = new_chart();
+        let svg = chart.svg().unwrap();
+        chart.color_scheme = color_scheme("light", "light");
+        assert_eq!(svg, chart.svg().unwrap());
+
+        chart.color_scheme = color_scheme("light", "dark");
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains(":root{--charts-background-color:#FFFFFF;"));
+        assert!(svg.contains(
+            "@media (prefers-color-scheme: dark){:root{--charts-background-color:#100C2A;"
+        ));
+        assert!(svg.contains(r#"fill="var(--charts-background-color)""#));
+        assert!(svg.contains(r#"fill="var(--charts-x-axis-font-color)""#));
+        assert!(svg.contains(r#"fill="var(--charts-y-axis-font-color)""#));
+        assert!(svg.contains(r#"stroke="var(--charts-grid-stroke-color)""#));
+        // the chart itself is left untouched
+        assert_eq!(get_theme("light").background_color, chart.background_color);
+    }
+
+    #[test]
+    fn color_scheme_user_color() {
+        // A user color equal to a theme color keeps its value.
+        let mut chart = new_chart();
+        chart.title_font_color = Color::white();
+        chart.color_scheme = color_scheme("light", "dark");
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains(r#"fill="var(--charts-background-color)""#));
+        assert!(svg.contains(
+            r##"fill="#FFFFFF">
+Title"##
+        ));
+        assert!(!svg.contains("--charts-title-font-color"));
+    }
+
+    #[test]
+    fn color_scheme_near_user_color() {
+        // The first key candidate of the white background is #FFFFFE, which
+        // the title already uses.
+        let mut chart = new_chart();
+        chart.title_font_color = Color::from("#FFFFFE");
+        chart.color_scheme = color_scheme("light", "dark");
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains(r#"fill="var(--charts-background-color)""#));
+        assert!(svg.contains(
+            r##"fill="#FFFFFE">
+Title"##
+        ));
+    }
+
+    #[test]
+    fn color_scheme_field_identity() {
+        // The title and legend share a light color but not a dark one, and
+        // the dark grid color is translucent.
+        let mut dark = (*get_theme("dark")).clone();
+        dark.legend_font_color = (255, 0, 0).into();
+        dark.grid_stroke_color = dark.grid_stroke_color.with_alpha(128);
+        add_theme("color_scheme_dark", dark);
+
+        let mut chart = new_chart();
+        chart.color_scheme = color_scheme("light", "color_scheme_dark");
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains(
+            r#"fill="var(--charts-title-font-color)">
+Title"#
+        ));
+        assert!(svg.contains(
+            r#"fill="var(--charts-legend-font-color)">
+Email"#
+        ));
+        assert!(svg.contains("--charts-title-font-color:#EEEEEE;"));
+        assert!(svg.contains("--charts-legend-font-color:#FF0000;"));
+        assert!(svg.contains("--charts-grid-stroke-color:rgba(71,71,83,0.5);"));
+    }
+
+    #[test]
+    fn color_scheme_from_json() {
+        let chart = LineChart::from_json(
+            r###"{
+                "theme": "light",
+                "color_scheme": {"dark": "dark"},
+                "series_list": [{"name": "Email", "data": [120.0, 132.0, 101.0]}],
+                "x_axis_data": ["Mon", "Tue", "Wed"]
+            }"###,
+        )
+        .unwrap();
+        assert_eq!(color_scheme("light", "dark"), chart.color_scheme);
+        assert!(
+            chart
+                .svg()
+                .unwrap()
+                .contains(r#"fill="var(--charts-background-color)""#)
+        );
+    }
+}
@@ -77,6 +77,75 @@ pub enum SeriesCategory {
     Bar,
 }
 
+/// How the series of one `stack` group are accumulated.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum StackMode {
+    #[default]
+    /// Values are stacked as they are.
+    Normal,
+    /// Every stack is normalized to 100%: segments show their share of the
+    /// category total and the value axis is labelled in percent. Labels can
+    /// use `{d}` for the share and `{c}` for the raw value.
+    Percent,
+}
+
+/// Where the first layer of a stacked area starts.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum StackBaseline {
+    #[default]
+    /// Layers are stacked up from zero.
+    Zero,
+    /// The stack is centered around zero, like a stream graph.
+    Silhouette,
+    /// The baseline moves to minimize the weighted slope of the layers.
+    Wiggle,
+}
+
+/// How overlapping series labels are resolved.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum LabelOverlap {
+    #[default]
+    /// Labels stay where they are placed, even when they overlap.
+    Allow,
+    /// Overlapping labels are moved apart vertically.
+    Nudge,
+    /// Overlapping labels are moved further apart and joined to their data
+    /// point by a leader line.
+    LeaderLine,
+    /// Labels overlapping a label with a higher priority are dropped.
+    Hide,
+}
+
+/// The direction of the labels of a sunburst chart.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum LabelRotate {
+    #[default]
+    /// Along the arc.
+    Tangential,
+    /// Along the radius.
+    Radial,
+    /// Along the arc when it fits, along the radius otherwise.
+    Auto,
+}
+
+/// How the two funnels of a comparison funnel chart are drawn.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum FunnelComparison {
+    /// The funnels are drawn side by side, growing from the middle.
+    Mirror,
+    /// The second funnel is drawn see-through over the first one.
+    Overlay,
+}
+
+/// The order of the nodes within a column of a sankey chart.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum NodeSort {
+    /// The smallest value first.
+    Asc,
+    /// The largest value first.
+    Desc,
+}
+
 /// The statistic a mark line is drawn at.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
 pub enum MarkLineCategory {
@@ -134,6 +203,9 @@ pub struct Series {
     pub mark_points: Vec<MarkPoint>,
     /// Per-data-point color overrides (bar charts).
     pub colors: Option<Vec<Option<Color>>>,
+    /// Fill of the bars, the area under the line or the pie slice; `None`
+    /// uses the series color. Per-data-point `colors` take precedence.
+    pub fill: Option<Fill>,
     /// Overrides how the series is drawn, e.g. a line inside a bar chart.
     pub category: Option<SeriesCategory>,
     /// SVG stroke dash array for line series.
@@ -184,6 +256,17 @@ impl AnimationConfig {
     }
 }
 
+/// The pair of themes a chart switches between following the
+/// `prefers-color-scheme` of the viewer, see
+/// [`ChartBase::color_scheme`](crate::ChartBase::color_scheme).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct ColorScheme {
+    /// The theme the chart is created with, e.g. `"light"`.
+    pub light: String,
+    /// The theme used when the viewer prefers a dark color scheme.
+    pub dark: String,
+}
+
 /// A rendered series label: its text and anchor point.
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct SeriesLabel {
@@ -267,8 +350,39 @@ pub struct YAxisConfig {
     pub axis_scale: AxisScale,
 }
 
-/// A fill that can be either a solid color or a linear gradient.
-#[derive(Clone, Copy, PartialEq, Debug)]
+/// A color at a relative position of a gradient.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub struct ColorStop {
+    /// Position of the stop, from 0.0 (start) to 1.0 (end).
+    pub offset: f32,
+    /// Color at the stop.
+    pub color: Color,
+}
+
+impl From<(f32, Color)> for ColorStop {
+    fn from(value: (f32, Color)) -> Self {
+        ColorStop {
+            offset: value.0,
+            color: value.1,
+        }
+    }
+}
+
+/// The shape repeated by a pattern fill.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum PatternKind {
+    /// Diagonal lines.
+    #[default]
+    Hatch,
+    /// Two crossing sets of diagonal lines.
+    CrossHatch,
+    /// A grid of dots.
+    Dots,
+}
+
+/// A fill that can be a solid color, a linear or radial gradient, or a
+/// repeated pattern.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Fill {
     /// A solid color fill.
     Solid(Color),
@@ -281,6 +395,33 @@ pub enum Fill {
         /// Angle in degrees: 0 = top→bottom, 90 = left→right, 180 = bottom→top, 270 = right→left.
         angle: f32,
     },
+    /// A linear gradient fill through any number of color stops.
+    MultiStopGradient {
+        /// Color stops, ordered by offset.
+        stops: Vec<ColorStop>,
+        /// Angle in degrees, see `LinearGradient`.
+        angle: f32,
+    },
+    /// A radial gradient fill from the center (offset 0.0) to the edge
+    /// (offset 1.0). Rectangles and areas use their bounding box, pie slices
+    /// use the center and radius of the pie.
+    RadialGradient {
+        /// Color stops, ordered by offset.
+        stops: Vec<ColorStop>,
+    },
+    /// A repeated pattern drawn over a background, readable when printed in
+    /// black and white.
+    Pattern {
+        /// Shape of the pattern.
+        kind: PatternKind,
+        /// Color of the lines or dots.
+        color: Color,
+        /// Background color; transparent leaves the shape unfilled behind the
+        /// pattern.
+        background: Color,
+        /// Size of one pattern tile.
+        size: f32,
+    },
 }
 
 impl Default for Fill {
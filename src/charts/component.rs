@@ -17,6 +17,7 @@ use std::fmt::Write as _;
 use std::vec;
 
 use super::color::*;
+use super::color_scheme::color_variable;
 use super::common::*;
 use super::font;
 use super::measure_text_width_family;
@@ -69,6 +70,13 @@ static ATTR_CLASS: &str = "class";
 static ATTR_STYLE: &str = "style";
 static ATTR_PATH_LENGTH: &str = "pathLength";
 
+/// The svg value of a color: its hex, or the `var()` reference while the
+/// color is the key of a [`ChartBase::color_scheme`](crate::ChartBase::color_scheme)
+/// variable.
+fn svg_color(color: &Color) -> String {
+    color_variable(color).unwrap_or_else(|| color.hex())
+}
+
 /// Converts opacity to string value.
 fn convert_opacity(color: &Color) -> String {
     if color.is_nontransparent() {
@@ -78,86 +86,249 @@ fn convert_opacity(color: &Color) -> String {
     }
 }
 
-fn fill_grad_id(start: &Color, end: &Color, angle: f32) -> String {
+fn fill_color_id(color: &Color) -> String {
+    format!(
+        "{:02X}{:02X}{:02X}{:02X}",
+        color.r, color.g, color.b, color.a
+    )
+}
+
+fn fill_stops_id(stops: &[ColorStop]) -> String {
+    stops
+        .iter()
+        .map(|stop| {
+            format!(
+                "{}_{}",
+                fill_color_id(&stop.color),
+                (stop.offset * 100.0).round() as i32
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Returns the id of the `<defs>` entry a fill references, `None` for solid
+/// fills. `circle` is the (cx, cy, r) a radial gradient is drawn in, `None`
+/// uses the bounding box of the shape.
+fn fill_id(fill: &Fill, circle: Option<(f32, f32, f32)>) -> Option<String> {
+    match fill {
+        Fill::Solid(_) => None,
+        Fill::LinearGradient {
+            start_color,
+            end_color,
+            angle,
+        } => Some(format!(
+            "grad_{}_{}_{}",
+            fill_color_id(start_color),
+            fill_color_id(end_color),
+            angle.round() as i32
+        )),
+        Fill::MultiStopGradient { stops, angle } => Some(format!(
+            "grad_{}_{}",
+            fill_stops_id(stops),
+            angle.round() as i32
+        )),
+        Fill::RadialGradient { stops } => {
+            let mut id = format!("radial_{}", fill_stops_id(stops));
+            if let Some((cx, cy, r)) = circle {
+                let _ = write!(
+                    id,
+                    "_{}_{}_{}",
+                    cx.round() as i32,
+                    cy.round() as i32,
+                    r.round() as i32
+                );
+            }
+            Some(id)
+        }
+        Fill::Pattern {
+            kind,
+            color,
+            background,
+            size,
+        } => Some(format!(
+            "pattern_{}_{}_{}_{}",
+            match kind {
+                PatternKind::Hatch => "hatch",
+                PatternKind::CrossHatch => "cross",
+                PatternKind::Dots => "dots",
+            },
+            fill_color_id(color),
+            fill_color_id(background),
+            (size * 10.0).round() as i32
+        )),
+    }
+}
+
+fn gradient_stop_svg(offset: f32, color: &Color) -> String {
+    let opacity = if color.is_nontransparent() {
+        String::new()
+    } else {
+        format!(" stop-opacity=\"{:.2}\"", color.opacity())
+    };
+    format!(
+        "<stop offset=\"{}%\" stop-color=\"{}\"{opacity}/>",
+        format_float(offset.clamp(0.0, 1.0) * 100.0),
+        svg_color(color)
+    )
+}
+
+fn linear_gradient_svg(id: &str, angle: f32, stops: &[ColorStop]) -> String {
+    let angle_rad = angle * std::f32::consts::PI / 180.0;
+    let x1 = 0.5 - 0.5 * angle_rad.sin();
+    let y1 = 0.5 - 0.5 * angle_rad.cos();
+    let x2 = 0.5 + 0.5 * angle_rad.sin();
+    let y2 = 0.5 + 0.5 * angle_rad.cos();
+    let stops: String = stops
+        .iter()
+        .map(|stop| gradient_stop_svg(stop.offset, &stop.color))
+        .collect();
+    format!(
+        "<defs><linearGradient id=\"{id}\" x1=\"{x1:.2}\" y1=\"{y1:.2}\" x2=\"{x2:.2}\" y2=\"{y2:.2}\" gradientUnits=\"objectBoundingBox\">{stops}</linearGradient></defs>"
+    )
+}
+
+fn pattern_svg(
+    id: &str,
+    kind: PatternKind,
+    color: &Color,
+    background: &Color,
+    size: f32,
+) -> String {
+    let size_str = format_float(size);
+    let center = format_float(size / 2.0);
+    let mut stroke = format!(
+        "stroke=\"{}\" stroke-width=\"{}\"",
+        svg_color(color),
+        format_float(size / 4.0)
+    );
+    if !color.is_nontransparent() {
+        let _ = write!(stroke, " stroke-opacity=\"{}\"", convert_opacity(color));
+    }
+    let mut data = String::new();
+    if !background.is_transparent() {
+        let _ = write!(
+            data,
+            "<rect width=\"{size_str}\" height=\"{size_str}\" fill=\"{}\"",
+            svg_color(background)
+        );
+        if !background.is_nontransparent() {
+            let _ = write!(data, " fill-opacity=\"{}\"", convert_opacity(background));
+        }
+        data.push_str("/>");
+    }
+    let transform = match kind {
+        PatternKind::Hatch => {
+            let _ = write!(
+                data,
+                "<line x1=\"{center}\" y1=\"0\" x2=\"{center}\" y2=\"{size_str}\" {stroke}/>"
+            );
+            " patternTransform=\"rotate(45)\""
+        }
+        PatternKind::CrossHatch => {
+            let _ = write!(
+                data,
+                "<line x1=\"{center}\" y1=\"0\" x2=\"{center}\" y2=\"{size_str}\" {stroke}/><line x1=\"0\" y1=\"{center}\" x2=\"{size_str}\" y2=\"{center}\" {stroke}/>"
+            );
+            " patternTransform=\"rotate(45)\""
+        }
+        PatternKind::Dots => {
+            let mut fill = format!("fill=\"{}\"", svg_color(color));
+            if !color.is_nontransparent() {
+                let _ = write!(fill, " fill-opacity=\"{}\"", convert_opacity(color));
+            }
+            let _ = write!(
+                data,
+                "<circle cx=\"{center}\" cy=\"{center}\" r=\"{}\" {fill}/>",
+                format_float(size / 4.0)
+            );
+            ""
+        }
+    };
     format!(
-        "grad_{:02X}{:02X}{:02X}{:02X}_{:02X}{:02X}{:02X}{:02X}_{}",
-        start.r,
-        start.g,
-        start.b,
-        start.a,
-        end.r,
-        end.g,
-        end.b,
-        end.a,
-        angle.round() as i32
+        "<defs><pattern id=\"{id}\" width=\"{size_str}\" height=\"{size_str}\" patternUnits=\"userSpaceOnUse\"{transform}>{data}</pattern></defs>"
     )
 }
 
-/// Renders the `<defs>` block for a gradient fill. When a render-scoped
-/// `grad_seen` set is provided, a gradient id that was already emitted in this
-/// document is skipped — identical gradients share one definition instead of
-/// repeating an (invalid) duplicate id per shape.
-fn fill_svg_defs(fill: &Fill, grad_seen: Option<&mut HashSet<String>>) -> String {
+/// Renders the `<defs>` block for a gradient or pattern fill. When a
+/// render-scoped `grad_seen` set is provided, an id that was already emitted
+/// in this document is skipped — identical fills share one definition instead
+/// of repeating an (invalid) duplicate id per shape.
+fn fill_svg_defs(
+    fill: &Fill,
+    circle: Option<(f32, f32, f32)>,
+    grad_seen: Option<&mut HashSet<String>>,
+) -> String {
+    let Some(id) = fill_id(fill, circle) else {
+        return String::new();
+    };
+    if let Some(seen) = grad_seen
+        && !seen.insert(id.clone())
+    {
+        return String::new();
+    }
     match fill {
         Fill::Solid(_) => String::new(),
         Fill::LinearGradient {
             start_color,
             end_color,
             angle,
-        } => {
-            if let Some(seen) = grad_seen
-                && !seen.insert(fill_grad_id(start_color, end_color, *angle))
-            {
-                return String::new();
-            }
-            let angle_rad = angle * std::f32::consts::PI / 180.0;
-            let x1 = 0.5 - 0.5 * angle_rad.sin();
-            let y1 = 0.5 - 0.5 * angle_rad.cos();
-            let x2 = 0.5 + 0.5 * angle_rad.sin();
-            let y2 = 0.5 + 0.5 * angle_rad.cos();
-            let id = fill_grad_id(start_color, end_color, *angle);
-            let start_opacity = if start_color.is_nontransparent() {
-                String::new()
-            } else {
-                format!(" stop-opacity=\"{:.2}\"", start_color.opacity())
-            };
-            let end_opacity = if end_color.is_nontransparent() {
-                String::new()
+        } => linear_gradient_svg(
+            &id,
+            *angle,
+            &[(0.0, *start_color).into(), (1.0, *end_color).into()],
+        ),
+        Fill::MultiStopGradient { stops, angle } => linear_gradient_svg(&id, *angle, stops),
+        Fill::RadialGradient { stops } => {
+            let (cx, cy, r, units) = if let Some((cx, cy, r)) = circle {
+                (
+                    format_float(cx),
+                    format_float(cy),
+                    format_float(r),
+                    "userSpaceOnUse",
+                )
             } else {
-                format!(" stop-opacity=\"{:.2}\"", end_color.opacity())
+                (
+                    "0.5".to_string(),
+                    "0.5".to_string(),
+                    "0.5".to_string(),
+                    "objectBoundingBox",
+                )
             };
+            let stops: String = stops
+                .iter()
+                .map(|stop| gradient_stop_svg(stop.offset, &stop.color))
+                .collect();
             format!(
-                "<defs><linearGradient id=\"{id}\" x1=\"{x1:.2}\" y1=\"{y1:.2}\" x2=\"{x2:.2}\" y2=\"{y2:.2}\" gradientUnits=\"objectBoundingBox\"><stop offset=\"0%\" stop-color=\"{}\"{start_opacity}/><stop offset=\"100%\" stop-color=\"{}\"{end_opacity}/></linearGradient></defs>",
-                start_color.hex(),
-                end_color.hex(),
+                "<defs><radialGradient id=\"{id}\" cx=\"{cx}\" cy=\"{cy}\" r=\"{r}\" gradientUnits=\"{units}\">{stops}</radialGradient></defs>"
             )
         }
+        Fill::Pattern {
+            kind,
+            color,
+            background,
+            size,
+        } => pattern_svg(&id, *kind, color, background, *size),
     }
 }
 
-fn fill_svg_attr(fill: &Fill) -> String {
+fn fill_svg_attr(fill: &Fill, circle: Option<(f32, f32, f32)>) -> String {
     match fill {
         Fill::Solid(c) => {
             if c.is_transparent() {
                 "none".to_string()
             } else {
-                c.hex()
+                svg_color(c)
             }
         }
-        Fill::LinearGradient {
-            start_color,
-            end_color,
-            angle,
-        } => {
-            format!("url(#{})", fill_grad_id(start_color, end_color, *angle))
-        }
+        _ => format!("url(#{})", fill_id(fill, circle).unwrap_or_default()),
     }
 }
 
 fn fill_svg_opacity(fill: &Fill) -> String {
     match fill {
         Fill::Solid(c) => convert_opacity(c),
-        Fill::LinearGradient { .. } => String::new(),
+        _ => String::new(),
     }
 }
 
@@ -398,7 +569,7 @@ impl Line {
             (ATTR_Y2, format_float(self.bottom)),
         ];
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         if let Some(ref stroke_dash_array) = self.stroke_dash_array {
@@ -460,18 +631,18 @@ impl Rect {
         ];
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         let defs = if let Some(fill) = &self.fill {
-            let fill_attr = fill_svg_attr(fill);
+            let fill_attr = fill_svg_attr(fill, None);
             if fill_attr == "none" {
                 attrs.push((ATTR_FILL, "none".to_string()));
             } else {
                 attrs.push((ATTR_FILL, fill_attr));
                 attrs.push((ATTR_FILL_OPACITY, fill_svg_opacity(fill)));
             }
-            fill_svg_defs(fill, grad_seen)
+            fill_svg_defs(fill, None, grad_seen)
         } else {
             String::new()
         };
@@ -543,7 +714,7 @@ impl Polyline {
         ];
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
 
@@ -610,12 +781,12 @@ impl Circle {
             (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
         ];
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         let mut fill = "none".to_string();
         if let Some(color) = self.fill {
-            fill = color.hex();
+            fill = svg_color(&color);
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
         }
         attrs.push((ATTR_FILL, fill));
@@ -725,19 +896,19 @@ impl Polygon {
         }
         let mut attrs = vec![(ATTR_POINTS, points)];
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         let defs = if let Some(ref fill) = self.gradient {
-            attrs.push((ATTR_FILL, fill_svg_attr(fill)));
+            attrs.push((ATTR_FILL, fill_svg_attr(fill, None)));
             let opacity = fill_svg_opacity(fill);
             if !opacity.is_empty() {
                 attrs.push((ATTR_FILL_OPACITY, opacity));
             }
-            fill_svg_defs(fill, grad_seen)
+            fill_svg_defs(fill, None, grad_seen)
         } else {
             if let Some(color) = self.fill {
-                attrs.push((ATTR_FILL, color.hex()));
+                attrs.push((ATTR_FILL, svg_color(&color)));
                 attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
             }
             String::new()
@@ -794,7 +965,7 @@ impl Bubble {
 
         let attrs = vec![
             (ATTR_D, path_list.join(" ")),
-            (ATTR_FILL, self.fill.hex()),
+            (ATTR_FILL, svg_color(&self.fill)),
             (ATTR_FILL_OPACITY, convert_opacity(&self.fill)),
         ];
         SVGTag {
@@ -880,7 +1051,7 @@ impl Text {
             attrs.push((ATTR_FONT_FAMILY, font_family.clone()));
         }
         if let Some(color) = self.font_color {
-            attrs.push((ATTR_FILL, color.hex()));
+            attrs.push((ATTR_FILL, svg_color(&color)));
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&color)));
         }
         if let Some(ref class) = self.class {
@@ -993,6 +1164,8 @@ pub struct Pie {
     pub fill: Fill,
     /// Stroke color.
     pub stroke_color: Option<Color>,
+    /// Stroke width; the SVG default when `None`.
+    pub stroke_width: Option<f32>,
     /// Center x coordinate.
     pub cx: f32,
     /// Center y coordinate.
@@ -1020,6 +1193,7 @@ impl Default for Pie {
         Pie {
             fill: Fill::Solid((0, 0, 0).into()),
             stroke_color: None,
+            stroke_width: None,
             cx: 0.0,
             cy: 0.0,
             r: 250.0,
@@ -1165,16 +1339,22 @@ impl Pie {
 
         path_list.push("Z".to_string());
 
-        let defs = fill_svg_defs(&self.fill, grad_seen);
+        let defs = fill_svg_defs(&self.fill, Some((self.cx, self.cy, self.r)), grad_seen);
         let mut attrs = vec![
             (ATTR_D, path_list.join(" ")),
-            (ATTR_FILL, fill_svg_attr(&self.fill)),
+            (
+                ATTR_FILL,
+                fill_svg_attr(&self.fill, Some((self.cx, self.cy, self.r))),
+            ),
             (ATTR_FILL_OPACITY, fill_svg_opacity(&self.fill)),
         ];
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
+        if let Some(width) = self.stroke_width {
+            attrs.push((ATTR_STROKE_WIDTH, format_float(width)));
+        }
         if let Some(ref class) = self.class {
             attrs.push((ATTR_CLASS, class.clone()));
         }
@@ -1244,14 +1424,14 @@ impl<'a> BaseLine<'a> {
             (ATTR_STROKE_WIDTH, format_float(self.stroke_width)),
         ];
         if let Some(fill) = self.fill {
-            attrs.push((ATTR_FILL, fill.hex()));
+            attrs.push((ATTR_FILL, svg_color(&fill)));
             attrs.push((ATTR_FILL_OPACITY, convert_opacity(&fill)));
         } else {
             attrs.push((ATTR_FILL, "none".to_string()));
         }
 
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
         if let Some(stroke_dash_array) = &self.stroke_dash_array {
@@ -1370,6 +1550,9 @@ pub struct SmoothLineFill {
     pub points: Vec<Point>,
     /// Y coordinate of the baseline the fill closes to.
     pub bottom: f32,
+    /// Smooth lower edge, from the last point back to the first (stacked
+    /// areas); when empty the fill closes to `bottom`.
+    pub bottom_points: Vec<Point>,
 }
 
 impl Default for SmoothLineFill {
@@ -1378,6 +1561,7 @@ impl Default for SmoothLineFill {
             fill: Fill::Solid((255, 255, 255, 255).into()),
             points: vec![],
             bottom: 0.0,
+            bottom_points: vec![],
         }
     }
 }
@@ -1397,6 +1581,16 @@ impl SmoothLineFill {
         }
         .to_string();
 
+        if !self.bottom_points.is_empty() {
+            let bottom = SmoothCurve {
+                points: self.bottom_points.clone(),
+                ..Default::default()
+            }
+            .to_string();
+            // Continue the top curve into the lower edge and close the band.
+            path.push_str(&format!(" L{} Z", bottom.trim_start_matches('M')));
+            return self.fill_path(path, grad_seen);
+        }
         let last = self.points[self.points.len() - 1];
         let first = self.points[0];
         let fill_path = [
@@ -1407,11 +1601,13 @@ impl SmoothLineFill {
         ]
         .join(" ");
         path.push_str(&fill_path);
-
-        let defs = fill_svg_defs(&self.fill, grad_seen);
+        self.fill_path(path, grad_seen)
+    }
+    fn fill_path(&self, path: String, grad_seen: Option<&mut HashSet<String>>) -> String {
+        let defs = fill_svg_defs(&self.fill, None, grad_seen);
         let attrs = vec![
             (ATTR_D, path),
-            (ATTR_FILL, fill_svg_attr(&self.fill)),
+            (ATTR_FILL, fill_svg_attr(&self.fill, None)),
             (ATTR_FILL_OPACITY, fill_svg_opacity(&self.fill)),
         ];
 
@@ -1532,10 +1728,10 @@ impl StraightLineFill {
         if self.close {
             arr.push('Z'.to_string());
         }
-        let defs = fill_svg_defs(&self.fill, grad_seen);
+        let defs = fill_svg_defs(&self.fill, None, grad_seen);
         let attrs = vec![
             (ATTR_D, arr.join(" ")),
-            (ATTR_FILL, fill_svg_attr(&self.fill)),
+            (ATTR_FILL, fill_svg_attr(&self.fill, None)),
             (ATTR_FILL_OPACITY, fill_svg_opacity(&self.fill)),
         ];
 
@@ -1622,7 +1818,7 @@ impl Grid {
 
         let mut attrs = vec![];
         if let Some(color) = self.color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
         }
 
@@ -1715,7 +1911,7 @@ impl Axis {
         let mut attrs = vec![];
         let mut is_transparent = false;
         if let Some(color) = self.stroke_color {
-            attrs.push((ATTR_STROKE, color.hex()));
+            attrs.push((ATTR_STROKE, svg_color(&color)));
             attrs.push((ATTR_STROKE_OPACITY, convert_opacity(&color)));
 
             is_transparent = color.is_transparent();
@@ -2567,6 +2763,16 @@ Hello World!
             r###"<path d="M173.3,126.7 L179.7,120.3 A8 8 0 0 1 186.6,115.9 A50 50 0 0 1 115.9,186.6 A50 50 0 0 1 100.1,147.4 A8 8 0 0 1 108.2,146.3 L117.1,147.1 A8 8 0 0 1 125,148.7 A25 25 0 0 0 174.9,152.2 A25 25 0 0 0 168.3,133 A8 8 0 0 1 173.3,126.7 Z" fill="#000000" fill-opacity="0.5" stroke="#000000"/>"###,
             p.svg()
         );
+
+        let p = Pie {
+            stroke_color: Some((0, 0, 0).into()),
+            stroke_width: Some(2.0),
+            ..p
+        };
+        assert!(
+            p.svg()
+                .ends_with(r###"stroke="#000000" stroke-width="2"/>"###)
+        );
     }
 
     #[test]
@@ -2689,6 +2895,7 @@ Hello World!
                     (40.0, 50.0).into(),
                 ],
                 bottom: 100.0,
+                ..Default::default()
             }
             .svg()
         );
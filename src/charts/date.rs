@@ -0,0 +1,293 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proleptic Gregorian date helpers (UTC, no external crate) shared by the
+//! time axes and the calendar chart.
+
+pub(crate) const SECONDS_PER_DAY: i64 = 86400;
+
+pub(crate) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Parses "YYYY-MM-DD" → (year, month, day).  Returns None on malformed
+/// input or a day the month does not have.
+pub(crate) fn parse_date(value: &str) -> Option<(i64, u32, u32)> {
+    let mut parts = value.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+/// Days since the Unix epoch of a proleptic Gregorian date.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Proleptic Gregorian date of a day count since the Unix epoch.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Returns day-of-week: 0 = Sunday, 1 = Monday, …, 6 = Saturday.
+pub(crate) fn day_of_week(year: i64, month: u32, day: u32) -> u32 {
+    // the epoch was a thursday
+    (days_from_civil(year, month, day) + 4).rem_euclid(7) as u32
+}
+
+/// Parses a timestamp: seconds since the Unix epoch, or a UTC date
+/// `YYYY-MM-DD` with an optional `HH:MM[:SS]` time after a space or `T`.
+pub(crate) fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(seconds);
+    }
+    let (date, time) = match value.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let (year, month, day) = parse_date(date)?;
+    let mut seconds = days_from_civil(year, month, day) * SECONDS_PER_DAY;
+    if let Some(time) = time {
+        let mut parts = time.trim_end_matches('Z').splitn(3, ':');
+        let hour: i64 = parts.next()?.parse().ok()?;
+        let minute: i64 = parts.next()?.parse().ok()?;
+        let second: i64 = parts.next().map(|v| v.parse().ok()).unwrap_or(Some(0))?;
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        seconds += hour * 3600 + minute * 60 + second;
+    }
+    Some(seconds)
+}
+
+/// Formats a timestamp (seconds since the Unix epoch, UTC) replacing `%Y`,
+/// `%m`, `%d`, `%H`, `%M` and `%S`.
+pub(crate) fn format_timestamp(timestamp: i64, formatter: &str) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+    formatter
+        .replace("%Y", &year.to_string())
+        .replace("%m", &format!("{month:02}"))
+        .replace("%d", &format!("{day:02}"))
+        .replace("%H", &format!("{:02}", seconds / 3600))
+        .replace("%M", &format!("{:02}", seconds % 3600 / 60))
+        .replace("%S", &format!("{:02}", seconds % 60))
+}
+
+/// Interval between the ticks of a time axis.
+enum TimeInterval {
+    Seconds(i64),
+    Months(i64),
+}
+
+/// Gets at most `max_count` calendar-aligned ticks (UTC) from `start` to
+/// `end`, together with the format matching their interval.
+pub(crate) fn get_time_ticks(start: i64, end: i64, max_count: usize) -> (Vec<i64>, &'static str) {
+    const HOUR: i64 = 3600;
+    let intervals = [
+        TimeInterval::Seconds(1),
+        TimeInterval::Seconds(5),
+        TimeInterval::Seconds(15),
+        TimeInterval::Seconds(30),
+        TimeInterval::Seconds(60),
+        TimeInterval::Seconds(5 * 60),
+        TimeInterval::Seconds(15 * 60),
+        TimeInterval::Seconds(30 * 60),
+        TimeInterval::Seconds(HOUR),
+        TimeInterval::Seconds(3 * HOUR),
+        TimeInterval::Seconds(6 * HOUR),
+        TimeInterval::Seconds(12 * HOUR),
+        TimeInterval::Seconds(SECONDS_PER_DAY),
+        TimeInterval::Seconds(2 * SECONDS_PER_DAY),
+        TimeInterval::Seconds(7 * SECONDS_PER_DAY),
+        TimeInterval::Months(1),
+        TimeInterval::Months(3),
+        TimeInterval::Months(6),
+        TimeInterval::Months(12),
+        TimeInterval::Months(24),
+        TimeInterval::Months(60),
+        TimeInterval::Months(120),
+        TimeInterval::Months(600),
+        TimeInterval::Months(1200),
+    ];
+    let span = (end - start).max(0) as f64;
+    let max_count = max_count.max(1) as f64;
+    let interval = intervals
+        .iter()
+        .find(|interval| {
+            let seconds = match interval {
+                TimeInterval::Seconds(seconds) => *seconds as f64,
+                TimeInterval::Months(months) => *months as f64 * 30.44 * SECONDS_PER_DAY as f64,
+            };
+            span / seconds < max_count
+        })
+        .unwrap_or(&intervals[intervals.len() - 1]);
+
+    let mut ticks = vec![];
+    match interval {
+        TimeInterval::Seconds(seconds) => {
+            // weeks start on monday, the epoch was a thursday
+            let offset = if *seconds == 7 * SECONDS_PER_DAY {
+                4 * SECONDS_PER_DAY
+            } else {
+                0
+            };
+            let mut tick = (start - offset).div_euclid(*seconds) * seconds + offset;
+            if tick < start {
+                tick += seconds;
+            }
+            while tick <= end {
+                ticks.push(tick);
+                tick += seconds;
+            }
+        }
+        TimeInterval::Months(months) => {
+            let (year, month, _) = civil_from_days(start.div_euclid(SECONDS_PER_DAY));
+            let mut index = (year * 12 + month as i64 - 1).div_euclid(*months) * months;
+            loop {
+                let tick =
+                    days_from_civil(index.div_euclid(12), (index.rem_euclid(12) + 1) as u32, 1)
+                        * SECONDS_PER_DAY;
+                if tick > end {
+                    break;
+                }
+                if tick >= start {
+                    ticks.push(tick);
+                }
+                index += months;
+            }
+        }
+    }
+    let format = match interval {
+        TimeInterval::Seconds(seconds) if *seconds < 60 => "%H:%M:%S",
+        TimeInterval::Seconds(seconds) if *seconds < SECONDS_PER_DAY => "%H:%M",
+        TimeInterval::Seconds(_) => "%m-%d",
+        TimeInterval::Months(months) if *months < 12 => "%Y-%m",
+        TimeInterval::Months(_) => "%Y",
+    };
+    (ticks, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        civil_from_days, day_of_week, days_from_civil, format_timestamp, get_time_ticks,
+        parse_date, parse_timestamp,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dates() {
+        assert_eq!(Some((2024, 2, 29)), parse_date("2024-02-29"));
+        assert_eq!(None, parse_date("2023-02-29"));
+        assert_eq!(None, parse_date("2024-02-31"));
+        assert_eq!(None, parse_date("2024-04-31"));
+        assert_eq!(None, parse_date("2024-00-10"));
+        assert_eq!(
+            (2024, 3, 1),
+            civil_from_days(days_from_civil(2024, 2, 29) + 1)
+        );
+        // 2024-01-01 was a monday
+        assert_eq!(1, day_of_week(2024, 1, 1));
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(Some(0), parse_timestamp("1970-01-01"));
+        assert_eq!(Some(1709210096), parse_timestamp("2024-02-29T12:34:56Z"));
+        assert_eq!(Some(1709210040), parse_timestamp("2024-02-29 12:34"));
+        assert_eq!(Some(-86400), parse_timestamp("1969-12-31"));
+        assert_eq!(Some(1700000000), parse_timestamp("1700000000"));
+        assert_eq!(None, parse_timestamp("2024-13-01"));
+        assert_eq!(None, parse_timestamp("2024-02-31"));
+        assert_eq!(None, parse_timestamp("2024-01-01 25:00"));
+        assert_eq!(None, parse_timestamp("today"));
+
+        assert_eq!(
+            "2024-02-29 12:34:56",
+            format_timestamp(1709210096, "%Y-%m-%d %H:%M:%S")
+        );
+        assert_eq!("1969-12-31", format_timestamp(-1, "%Y-%m-%d"));
+    }
+
+    #[test]
+    fn time_ticks() {
+        let day = 86400;
+        let start = parse_timestamp("2024-01-03").unwrap();
+
+        // 9 days fit daily ticks
+        let (ticks, format) = get_time_ticks(start, start + 8 * day, 10);
+        assert_eq!(9, ticks.len());
+        assert_eq!("%m-%d", format);
+
+        // weekly ticks fall on mondays
+        let (ticks, _) = get_time_ticks(start, start + 40 * day, 8);
+        assert_eq!(
+            vec!["01-08", "01-15", "01-22", "01-29", "02-05", "02-12"],
+            ticks
+                .iter()
+                .map(|t| format_timestamp(*t, "%m-%d"))
+                .collect::<Vec<_>>()
+        );
+
+        // quarters across a year
+        let (ticks, format) = get_time_ticks(start, parse_timestamp("2025-01-01").unwrap(), 5);
+        assert_eq!("%Y-%m", format);
+        assert_eq!(
+            vec!["2024-04", "2024-07", "2024-10", "2025-01"],
+            ticks
+                .iter()
+                .map(|t| format_timestamp(*t, format))
+                .collect::<Vec<_>>()
+        );
+
+        let (ticks, format) = get_time_ticks(start, start + 2 * 3600, 5);
+        assert_eq!("%H:%M", format);
+        assert_eq!(5, ticks.len());
+    }
+}
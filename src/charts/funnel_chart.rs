@@ -11,16 +11,27 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::label::{LabelBox, LabelPlacement, place_labels};
 use super::params::*;
 use super::theme::{get_default_theme_name, get_theme};
 use super::util::*;
 use crate::charts::measure_text_width_family;
 
+/// A stage or conversion label, drawn once the labels are placed.
+struct FunnelLabel {
+    text: String,
+    x: f32,
+    y: f32,
+    width: f32,
+    anchor: Point,
+    priority: f32,
+}
+
 /// A funnel chart for stage conversion data.
 #[derive(Clone, Debug, Default)]
 pub struct FunnelChart {
@@ -45,6 +56,19 @@ pub struct FunnelChart {
 
     /// Minimum trapezoid width for the narrowest end, in pixels (default: 20).
     pub min_width: f32,
+
+    /// Lays the stages out from left to right instead of top to bottom.
+    pub horizontal: bool,
+
+    /// Draws the conversion rates between the stages.
+    pub conversion_show: bool,
+
+    /// Formatter of the conversion rates: `{r}` is the rate from the previous
+    /// stage and `{o}` the overall rate from the first one (default: "{r}").
+    pub conversion_formatter: String,
+
+    /// Compares two funnels, the first and second data value of each stage.
+    pub comparison: Option<FunnelComparison>,
 }
 
 impl std::ops::Deref for FunnelChart {
@@ -71,6 +95,9 @@ impl FunnelChart {
         if self.series_label_position.is_none() {
             self.series_label_position = Some("right".to_string());
         }
+        if self.conversion_formatter.is_empty() {
+            self.conversion_formatter = "{r}".to_string();
+        }
     }
 
     /// Creates a funnel chart with default theme.
@@ -110,12 +137,107 @@ impl FunnelChart {
         if let Some(a) = get_align_from_value(&value, "funnel_align") {
             c.funnel_align = a;
         }
+        if let Some(b) = get_bool_from_value(&value, "horizontal") {
+            c.horizontal = b;
+        }
+        if let Some(b) = get_bool_from_value(&value, "conversion_show") {
+            c.conversion_show = b;
+        }
+        if let Some(s) = get_string_from_value(&value, "conversion_formatter") {
+            c.conversion_formatter = s;
+        }
+        if let Some(comparison) = get_funnel_comparison_from_value(&value, "comparison") {
+            c.comparison = Some(comparison);
+        }
         c.fill_default();
         Ok(c)
     }
 
+    /// Places a label beside the edge of a stage: `"left"` is before the edge
+    /// (left of it, or above it when horizontal), `"right"` after it and
+    /// `"inside"` centered on `across`.
+    fn new_label(
+        &self,
+        text: String,
+        along: f32,
+        across: f32,
+        side: &str,
+        priority: f32,
+    ) -> FunnelLabel {
+        let font_size = self.series_label_font_size;
+        let text_width = measure_text_width_family(&self.font_family, font_size, &text)
+            .map(|b| b.width())
+            .unwrap_or_default();
+        let (x, y, anchor) = if self.horizontal {
+            let y = match side {
+                "inside" => across,
+                "left" => across - 5.0 - font_size / 2.0,
+                _ => across + 5.0 + font_size / 2.0,
+            };
+            (along - text_width / 2.0, y, (along, across).into())
+        } else {
+            let x = match side {
+                "inside" => across - text_width / 2.0,
+                "left" => (across - 5.0 - text_width).max(0.0),
+                _ => across + 5.0,
+            };
+            (x, along, (across, along).into())
+        };
+        FunnelLabel {
+            text,
+            x,
+            y,
+            width: text_width,
+            anchor,
+            priority,
+        }
+    }
+
+    /// Draws the label right away without an overlap strategy, otherwise
+    /// keeps it to be placed with the other labels.
+    fn push_label(&self, c: &mut Canvas, labels: &mut Vec<FunnelLabel>, label: FunnelLabel) {
+        if self.series_label_overlap == LabelOverlap::Allow {
+            self.render_label(c, label, LabelPlacement::default());
+        } else {
+            labels.push(label);
+        }
+    }
+
+    /// Draws a label moved by its placement.
+    fn render_label(&self, c: &mut Canvas, label: FunnelLabel, placement: LabelPlacement) {
+        if placement.hidden {
+            return;
+        }
+        let class = self.animation.as_ref().map(|_| "funnel-anim".to_string());
+        if let Some((start, end)) = placement.leader_line {
+            c.line(Line {
+                color: Some(self.series_label_font_color),
+                left: start.x,
+                top: start.y,
+                right: end.x,
+                bottom: end.y,
+                ..Default::default()
+            });
+        }
+        c.text(Text {
+            text: label.text,
+            font_family: Some(self.font_family.clone()),
+            font_color: Some(self.series_label_font_color),
+            font_size: Some(self.series_label_font_size),
+            font_weight: self.series_label_font_weight.clone(),
+            dominant_baseline: Some("central".to_string()),
+            x: Some(label.x),
+            y: Some(label.y + placement.dy),
+            class,
+            ..Default::default()
+        });
+    }
+
     /// Renders the funnel chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         if self.series_list.is_empty() {
             return Err(canvas::Error::Params {
                 message: "series_list is empty".to_string(),
@@ -137,160 +259,247 @@ impl FunnelChart {
             });
         }
 
-        let funnel_width = c.width();
-        let funnel_height = c.height();
+        // the stages follow the length of the funnel, their widths the breadth
+        let (funnel_width, funnel_height) = if self.horizontal {
+            (c.height(), c.width())
+        } else {
+            (c.width(), c.height())
+        };
+        let comparison = self.comparison;
+        let is_comparison = comparison.is_some();
 
         // ── Collect & sort series ─────────────────────────────────────────────
-        // Tuple: (color_index, value, name).
-        let mut stages: Vec<(usize, f32, String)> = self
+        // Tuple: (color_index, values, name); the values are the sum of the
+        // data, or its first two values in comparison mode.
+        let mut stages: Vec<(usize, Vec<f32>, String)> = self
             .series_list
             .iter()
             .enumerate()
             .map(|(i, s)| {
-                let val: f32 = s.data_values().iter().copied().sum();
-                (s.index.unwrap_or(i), val, s.name.clone())
+                let data = s.data_values();
+                let values = if is_comparison {
+                    (0..2)
+                        .map(|index| data.get(index).copied().unwrap_or_default())
+                        .collect()
+                } else {
+                    vec![data.iter().copied().sum()]
+                };
+                (s.index.unwrap_or(i), values, s.name.clone())
             })
             .collect();
 
         if self.sort_ascending {
-            stages.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+            stages.sort_by(|a, b| {
+                a.1[0]
+                    .partial_cmp(&b.1[0])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
         } else {
-            stages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            stages.sort_by(|a, b| {
+                b.1[0]
+                    .partial_cmp(&a.1[0])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
         }
 
         let max_val = stages
             .iter()
-            .map(|(_, v, _)| *v)
+            .flat_map(|(_, values, _)| values.iter().copied())
             .fold(f32::NEG_INFINITY, f32::max);
         if max_val <= 0.0 {
             return c.svg();
         }
-        let total: f32 = stages.iter().map(|(_, v, _)| *v).sum();
+        let funnel_count = stages[0].1.len();
+        let totals: Vec<f32> = (0..funnel_count)
+            .map(|k| stages.iter().map(|(_, values, _)| values[k]).sum())
+            .collect();
 
         let n = stages.len();
         let gap = self.funnel_gap;
         let stage_h = (funnel_height - (n as f32 - 1.0) * gap) / n as f32;
 
         let label_pos = self.series_label_position.as_deref().unwrap_or("right");
-        let label_font_size = self.series_label_font_size;
-        let label_color = self.series_label_font_color;
         let mut formatter = self.series_label_formatter.clone();
         if formatter.is_empty() {
             formatter = "{a}: {c}".to_string();
         }
         let anim_class = self.animation.as_ref().map(|_| "funnel-anim".to_string());
 
-        for (stage_idx, (color_idx, val, name)) in stages.iter().enumerate() {
-            let top_w = (val / max_val) * funnel_width;
-            // bottom width = next stage's width (or min_width for last stage)
-            let bot_w = if stage_idx + 1 < n {
-                let next_val = stages[stage_idx + 1].1;
-                ((next_val / max_val) * funnel_width).max(self.min_width)
+        // the breadth of a funnel and where it is drawn: mirrored funnels
+        // grow from the middle to either side
+        let (max_w, min_width) = if comparison == Some(FunnelComparison::Mirror) {
+            ((funnel_width - gap) / 2.0, self.min_width / 2.0)
+        } else {
+            (funnel_width, self.min_width)
+        };
+        let get_range = |k: usize, w: f32| -> (f32, f32) {
+            if comparison == Some(FunnelComparison::Mirror) {
+                let middle = funnel_width / 2.0;
+                return if k == 0 {
+                    (middle - gap / 2.0 - w, middle - gap / 2.0)
+                } else {
+                    (middle + gap / 2.0, middle + gap / 2.0 + w)
+                };
+            }
+            let left = match self.funnel_align {
+                Align::Left => 0.0,
+                Align::Right => funnel_width - w,
+                _ => (funnel_width - w) / 2.0,
+            };
+            (left, left + w)
+        };
+        let point = |along: f32, across: f32| -> Point {
+            if self.horizontal {
+                (along, across).into()
             } else {
-                self.min_width
+                (across, along).into()
+            }
+        };
+        // the side of the labels and of the conversion rates of each funnel
+        let get_sides = |k: usize| -> (&str, &str) {
+            if is_comparison {
+                let side = if k == 0 { "left" } else { "right" };
+                return (side, side);
+            }
+            let conversion_side = match self.funnel_align {
+                Align::Left => "right",
+                Align::Right => "left",
+                _ if label_pos == "left" => "right",
+                _ => "left",
             };
+            (label_pos, conversion_side)
+        };
 
+        let mut labels = vec![];
+        for (stage_idx, (color_idx, values, name)) in stages.iter().enumerate() {
             let y_top = stage_idx as f32 * (stage_h + gap);
             let y_bot = y_top + stage_h;
-
-            // horizontal offset per alignment
-            let (x_left_top, x_left_bot) = match self.funnel_align {
-                Align::Left => (0.0, 0.0),
-                Align::Right => (funnel_width - top_w, funnel_width - bot_w),
-                _ => ((funnel_width - top_w) / 2.0, (funnel_width - bot_w) / 2.0),
-            };
-            let x_right_top = x_left_top + top_w;
-            let x_right_bot = x_left_bot + bot_w;
-
-            let color = get_color(&self.series_colors, *color_idx);
-
-            c.polygon(Polygon {
-                color: Some(color),
-                fill: Some(color),
-                points: vec![
-                    (x_left_top, y_top).into(),
-                    (x_right_top, y_top).into(),
-                    (x_right_bot, y_bot).into(),
-                    (x_left_bot, y_bot).into(),
-                ],
-                class: anim_class.clone(),
-                ..Default::default()
-            });
-
-            let label_option = LabelOption {
-                series_name: name.clone(),
-                value: *val,
-                percentage: if total > 0.0 { val / total } else { 0.0 },
-                formatter: formatter.clone(),
-                ..Default::default()
-            };
-            let label_text = label_option.format();
-
             let mid_y = (y_top + y_bot) / 2.0;
+            let color = get_color(&self.series_colors, *color_idx);
 
-            match label_pos {
-                "inside" => {
-                    // Center text inside the trapezoid
-                    let mid_x = (x_left_top + x_right_top) / 2.0;
-                    let mut text_x = mid_x;
-                    if let Ok(b) =
-                        measure_text_width_family(&self.font_family, label_font_size, &label_text)
-                    {
-                        text_x -= b.width() / 2.0;
+            for (k, val) in values.iter().enumerate() {
+                let top_w = (val / max_val) * max_w;
+                // bottom width = next stage's width (or min_width for last stage)
+                let bot_w = if stage_idx + 1 < n {
+                    let next_val = stages[stage_idx + 1].1[k];
+                    ((next_val / max_val) * max_w).max(min_width)
+                } else {
+                    min_width
+                };
+
+                let (x_left_top, x_right_top) = get_range(k, top_w);
+                let (x_left_bot, x_right_bot) = get_range(k, bot_w);
+
+                // the overlaid funnel is see-through
+                let fill = if comparison == Some(FunnelComparison::Overlay) && k == 1 {
+                    color.with_alpha(100)
+                } else if comparison == Some(FunnelComparison::Mirror) && k == 1 {
+                    color.with_alpha(180)
+                } else {
+                    color
+                };
+                c.polygon(Polygon {
+                    color: Some(fill),
+                    fill: Some(fill),
+                    points: vec![
+                        point(y_top, x_left_top),
+                        point(y_top, x_right_top),
+                        point(y_bot, x_right_bot),
+                        point(y_bot, x_left_bot),
+                    ],
+                    class: anim_class.clone(),
+                    ..Default::default()
+                });
+
+                let label_option = LabelOption {
+                    series_name: name.clone(),
+                    value: *val,
+                    percentage: if totals[k] > 0.0 {
+                        val / totals[k]
+                    } else {
+                        0.0
+                    },
+                    formatter: formatter.clone(),
+                    ..Default::default()
+                };
+                let label_text = label_option.format();
+
+                // overlaid labels sit outside of both funnels
+                let (mut left_edge, mut right_edge) =
+                    (x_left_top.min(x_left_bot), x_right_top.max(x_right_bot));
+                if comparison == Some(FunnelComparison::Overlay) {
+                    for w in values.iter().map(|v| (v / max_val) * max_w) {
+                        let (left, right) = get_range(k, w);
+                        left_edge = left_edge.min(left);
+                        right_edge = right_edge.max(right);
                     }
-                    c.text(Text {
-                        text: label_text,
-                        font_family: Some(self.font_family.clone()),
-                        font_color: Some(label_color),
-                        font_size: Some(label_font_size),
-                        font_weight: self.series_label_font_weight.clone(),
-                        dominant_baseline: Some("central".to_string()),
-                        x: Some(text_x),
-                        y: Some(mid_y),
-                        class: anim_class.clone(),
-                        ..Default::default()
-                    });
                 }
-                "left" => {
-                    let x_edge = x_left_top.min(x_left_bot) - 5.0;
-                    let mut text_x = x_edge;
-                    if let Ok(b) =
-                        measure_text_width_family(&self.font_family, label_font_size, &label_text)
-                    {
-                        text_x -= b.width();
-                    }
-                    c.text(Text {
-                        text: label_text,
-                        font_family: Some(self.font_family.clone()),
-                        font_color: Some(label_color),
-                        font_size: Some(label_font_size),
-                        font_weight: self.series_label_font_weight.clone(),
-                        dominant_baseline: Some("central".to_string()),
-                        x: Some(text_x.max(0.0)),
-                        y: Some(mid_y),
-                        class: anim_class.clone(),
-                        ..Default::default()
-                    });
+                let (label_side, conversion_side) = get_sides(k);
+                let label_across = match label_side {
+                    "inside" => (x_left_top + x_right_top) / 2.0,
+                    "left" => left_edge,
+                    _ => right_edge,
+                };
+                if !(comparison == Some(FunnelComparison::Overlay)
+                    && label_side == "inside"
+                    && k == 1)
+                {
+                    let label = self.new_label(label_text, mid_y, label_across, label_side, *val);
+                    self.push_label(&mut c, &mut labels, label);
                 }
-                _ => {
-                    // "right" (default): to the right of the widest edge
-                    let x_edge = x_right_top.max(x_right_bot) + 5.0;
-                    c.text(Text {
-                        text: label_text,
-                        font_family: Some(self.font_family.clone()),
-                        font_color: Some(label_color),
-                        font_size: Some(label_font_size),
-                        font_weight: self.series_label_font_weight.clone(),
-                        dominant_baseline: Some("central".to_string()),
-                        x: Some(x_edge),
-                        y: Some(mid_y),
-                        class: anim_class.clone(),
-                        ..Default::default()
-                    });
+
+                // the conversion from this stage to the next one
+                if self.conversion_show && stage_idx + 1 < n && *val > 0.0 {
+                    let next_val = stages[stage_idx + 1].1[k];
+                    let first_val = stages[0].1[k];
+                    let format_rate = |rate: f32| format!("{}%", format_float(rate * 100.0));
+                    let mut text = self
+                        .conversion_formatter
+                        .replace("{r}", &format_rate(next_val / val));
+                    if first_val > 0.0 {
+                        text = text.replace("{o}", &format_rate(next_val / first_val));
+                    }
+                    // beside the narrower end, outside of an overlaid funnel
+                    let (mut left_edge, mut right_edge) = (x_left_bot, x_right_bot);
+                    if comparison == Some(FunnelComparison::Overlay) {
+                        for values in stages[stage_idx + 1].1.iter() {
+                            let (left, right) = get_range(k, (values / max_val) * max_w);
+                            left_edge = left_edge.min(left);
+                            right_edge = right_edge.max(right);
+                        }
+                    }
+                    let across = if conversion_side == "left" {
+                        left_edge
+                    } else {
+                        right_edge
+                    };
+                    // conversion rates yield to the labels of the stages
+                    let label =
+                        self.new_label(text, y_bot + gap / 2.0, across, conversion_side, -1.0);
+                    self.push_label(&mut c, &mut labels, label);
                 }
             }
         }
 
+        let font_size = self.series_label_font_size;
+        let label_boxes: Vec<LabelBox> = labels
+            .iter()
+            .map(|label| LabelBox {
+                rect: Box {
+                    left: label.x,
+                    top: label.y - font_size / 2.0,
+                    right: label.x + label.width,
+                    bottom: label.y + font_size / 2.0,
+                },
+                anchor: label.anchor,
+                priority: label.priority,
+            })
+            .collect();
+        let placements = place_labels(&label_boxes, self.series_label_overlap);
+        for (label, placement) in labels.into_iter().zip(placements) {
+            self.render_label(&mut c, label, placement);
+        }
+
         if let Some(ref anim) = self.animation {
             let css = format!(
                 "@keyframes funnel-fade{{from{{opacity:0}}to{{opacity:1}}}} \
@@ -308,7 +517,7 @@ impl FunnelChart {
 #[cfg(test)]
 mod tests {
     use super::FunnelChart;
-    use crate::Series;
+    use crate::{FunnelComparison, Series};
     use pretty_assertions::assert_eq;
 
     fn make_series() -> Vec<Series> {
@@ -383,4 +592,78 @@ mod tests {
         );
         assert!(svg.contains("800ms ease-in"), "missing duration/easing");
     }
+
+    #[test]
+    fn funnel_chart_mirror() {
+        let mut chart = FunnelChart::new(vec![
+            ("Impression", vec![60000.0, 52000.0]).into(),
+            ("Click", vec![40000.0, 30000.0]).into(),
+            ("Inquiry", vec![20000.0, 18000.0]).into(),
+            ("Order", vec![8000.0, 9000.0]).into(),
+        ]);
+        chart.comparison = Some(FunnelComparison::Mirror);
+        chart.conversion_show = true;
+        chart.series_label_formatter = "{c}".to_string();
+        let svg = chart.svg().unwrap();
+        assert_eq!(include_str!("../../asset/funnel_chart/mirror.svg"), svg);
+    }
+
+    #[test]
+    fn funnel_chart_horizontal_overlay_json() {
+        let chart = FunnelChart::from_json(
+            r##"{
+                "horizontal": true,
+                "comparison": "overlay",
+                "conversion_show": true,
+                "conversion_formatter": "{r} / {o}",
+                "series_list": [
+                    {"name": "Visit", "data": [1000, 800]},
+                    {"name": "Cart", "data": [500, 200]},
+                    {"name": "Order", "data": [100, 100]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // two funnels of three stages, the second one see-through
+        assert_eq!(6, svg.matches("<polygon").count());
+        assert!(svg.contains(r#"fill-opacity="0.4""#));
+        // rates of both funnels
+        assert!(svg.contains("\n50% / 50%\n"));
+        assert!(svg.contains("\n20% / 10%\n"));
+        assert!(svg.contains("\n25% / 25%\n"));
+        assert!(svg.contains("\n50% / 12.5%\n"));
+    }
+
+    #[test]
+    fn funnel_chart_label_overlap() {
+        let new_svg = |overlap: &str| {
+            FunnelChart::from_json(&format!(
+                r##"{{
+                    "height": 120,
+                    "funnel_align": "left",
+                    "conversion_show": true,
+                    "series_label_overlap": "{overlap}",
+                    "series_list": [
+                        {{"name": "Visit", "data": [1000]}},
+                        {{"name": "Cart", "data": [500]}},
+                        {{"name": "Order", "data": [100]}},
+                        {{"name": "Paid", "data": [80]}}
+                    ]
+                }}"##
+            ))
+            .unwrap()
+            .svg()
+            .unwrap()
+        };
+        // the crowded conversion rates yield to the stage labels
+        let svg = new_svg("hide");
+        assert!(svg.contains("\nVisit: 1000\n"));
+        assert!(svg.contains("\nPaid: 80\n"));
+        assert!(!svg.contains("\n50%\n"));
+        assert!(new_svg("allow").contains("\n50%\n"));
+        let svg = new_svg("nudge");
+        assert!(svg.contains("\n50%\n"));
+        assert_ne!(new_svg("allow"), svg);
+    }
 }
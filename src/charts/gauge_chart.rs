@@ -11,13 +11,13 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme};
+use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
 
 /// Generate arc approximation points (360 segments per full revolution).
@@ -30,7 +30,45 @@ fn arc_points(cx: f32, cy: f32, r: f32, start: f32, end_deg: f32, n: usize) -> V
         .collect()
 }
 
+/// A threshold band of the gauge arc: the range from the previous band (or
+/// `min`) up to `to` is drawn in `color`.
+#[derive(Clone, Debug, Default)]
+pub struct GaugeColorBand {
+    /// Upper bound of the band.
+    pub to: f32,
+    /// Color of the band.
+    pub color: Color,
+}
+impl From<(f32, Color)> for GaugeColorBand {
+    fn from(val: (f32, Color)) -> Self {
+        GaugeColorBand {
+            to: val.0,
+            color: val.1,
+        }
+    }
+}
+
+fn get_gauge_color_band_list_from_value(
+    value: &serde_json::Value,
+) -> canvas::Result<Option<Vec<GaugeColorBand>>> {
+    let Some(arr) = value.get("color_bands").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+    let mut bands = vec![];
+    for item in arr.iter() {
+        if let Some(to) = get_f32_from_value(item, "to")
+            && let Some(color) = get_color_from_value(item, "color")?
+        {
+            bands.push(GaugeColorBand { to, color });
+        }
+    }
+    Ok(Some(bands))
+}
+
 /// A gauge (dial) chart displaying values on a circular scale.
+///
+/// Every series contributes its first value: with a needle each one gets its
+/// own pointer, in the progress style each one gets its own ring.
 #[derive(Clone, Debug, Default)]
 pub struct GaugeChart {
     /// The shared chart options (size, series, title/legend, axes); exposed
@@ -72,8 +110,24 @@ pub struct GaugeChart {
     /// Number of major tick divisions (default: 5).
     pub split_number: usize,
 
-    /// Value label formatter.  `{c}` is replaced with the current value (default: "{c}").
+    /// Value label formatter.  `{c}` is replaced with the current value and
+    /// `{t}` with its thousands representation, e.g. "{t} rpm" (default: "{c}").
     pub value_formatter: String,
+
+    /// Formatter of the tick labels, supporting `{c}` and `{t}` like
+    /// `value_formatter`.
+    pub axis_formatter: String,
+
+    /// Number of minor ticks between two major ticks (default: 0, none).
+    pub minor_split_number: usize,
+
+    /// Threshold bands drawn along the arc, in ascending order of `to`.
+    pub color_bands: Vec<GaugeColorBand>,
+
+    /// Renders a progress ring per series instead of needles; the ring takes
+    /// the color of the band its value falls into. Rings narrower than
+    /// `arc_width` are used when the series do not fit within the radius.
+    pub progress: bool,
 }
 
 impl std::ops::Deref for GaugeChart {
@@ -89,7 +143,7 @@ impl std::ops::DerefMut for GaugeChart {
 }
 
 impl GaugeChart {
-    fn fill_default(&mut self) {
+    fn fill_default(&mut self, t: &Theme) {
         if self.max <= self.min {
             self.max = self.min + 100.0;
         }
@@ -103,7 +157,7 @@ impl GaugeChart {
             self.arc_width = 15.0;
         }
         if self.background_arc_color.is_zero() {
-            self.background_arc_color = (230, 230, 230).into();
+            self.background_arc_color = t.gauge_background_arc_color;
         }
         if self.split_number == 0 {
             self.split_number = 5;
@@ -121,8 +175,9 @@ impl GaugeChart {
             ..Default::default()
         };
         c.series_list = series_list;
-        c.base.fill_theme(get_theme(theme), &mut c.y_axis_configs);
-        c.fill_default();
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.fill_default(&t);
         c
     }
 
@@ -150,13 +205,13 @@ impl GaugeChart {
         if let Some(v) = get_f32_from_value(&value, "arc_width") {
             c.arc_width = v;
         }
-        if let Some(v) = get_color_from_value(&value, "background_arc_color") {
+        if let Some(v) = get_color_from_value(&value, "background_arc_color")? {
             c.background_arc_color = v;
         }
         if let Some(v) = get_bool_from_value(&value, "show_pointer") {
             c.show_pointer = Some(v);
         }
-        if let Some(v) = get_color_from_value(&value, "pointer_color") {
+        if let Some(v) = get_color_from_value(&value, "pointer_color")? {
             c.pointer_color = v;
         }
         if let Some(v) = get_bool_from_value(&value, "show_axis_label") {
@@ -168,12 +223,27 @@ impl GaugeChart {
         if let Some(v) = get_string_from_value(&value, "value_formatter") {
             c.value_formatter = v;
         }
-        c.fill_default();
+        if let Some(v) = get_string_from_value(&value, "axis_formatter") {
+            c.axis_formatter = v;
+        }
+        if let Some(v) = get_usize_from_value(&value, "minor_split_number") {
+            c.minor_split_number = v;
+        }
+        if let Some(v) = get_gauge_color_band_list_from_value(&value)? {
+            c.color_bands = v;
+        }
+        if let Some(v) = get_bool_from_value(&value, "progress") {
+            c.progress = v;
+        }
+        c.fill_default(&get_theme_from_value(&value));
         Ok(c)
     }
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
         self.render_background(c.child(Box::default()));
         c.margin = self.margin.clone();
@@ -209,54 +279,139 @@ impl GaugeChart {
         let end = start + sweep;
 
         // ── Value extraction ──────────────────────────────────────────────────
-        let raw_value = self
+        let mut values: Vec<(usize, f32)> = self
             .series_list
-            .first()
-            .and_then(|s| s.data_values().first().copied())
-            .unwrap_or(self.min)
-            .clamp(self.min, self.max);
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let value = s
+                    .data_values()
+                    .first()
+                    .copied()
+                    .unwrap_or(self.min)
+                    .clamp(self.min, self.max);
+                (s.index.unwrap_or(i), value)
+            })
+            .collect();
+        if values.is_empty() {
+            values.push((0, self.min));
+        }
 
-        let ratio = if self.max > self.min {
-            (raw_value - self.min) / (self.max - self.min)
-        } else {
-            0.0
+        let get_ratio = |value: f32| -> f32 {
+            if self.max > self.min {
+                (value - self.min) / (self.max - self.min)
+            } else {
+                0.0
+            }
+        };
+        // the first series may use the configured pointer color
+        let get_series_color = |i: usize, index: usize| -> Color {
+            if i == 0 && !self.pointer_color.is_zero() {
+                self.pointer_color
+            } else {
+                get_color(&self.series_colors, index)
+            }
+        };
+        let get_band_color = |value: f32| -> Option<Color> {
+            self.color_bands
+                .iter()
+                .find(|band| value <= band.to)
+                .map(|band| band.color)
         };
-        let value_angle = start + ratio * sweep;
 
-        // arc center radius (middle of the stroke)
-        let arc_r = r - self.arc_width / 2.0;
+        // arc center radius (middle of the stroke), one ring per series in
+        // the progress style
+        let ring_count = if self.progress {
+            values.len().max(1)
+        } else {
+            1
+        };
+        // the rings shrink to fit within 80% of the radius, so many series
+        // never reach (and mirror through) the center
+        let ring_slot = r * 0.8 / ring_count as f32;
+        let ring_width = self.arc_width.min(ring_slot * 0.8);
+        let ring_gap = (ring_slot - ring_width).min(4.0);
+        let get_arc_r = |ring: usize| r - ring_width / 2.0 - ring as f32 * (ring_width + ring_gap);
 
         // ── Background arc (full sweep, many polyline segments) ───────────────
         let n_full = 360_usize;
-        let bg_pts = arc_points(cx, cy, arc_r, start, end, n_full);
-        body.polyline(Polyline {
-            color: Some(self.background_arc_color),
-            stroke_width: self.arc_width,
-            points: bg_pts,
-        });
+        for ring in 0..ring_count {
+            let arc_r = get_arc_r(ring);
+            if self.progress || self.color_bands.is_empty() {
+                let bg_pts = arc_points(cx, cy, arc_r, start, end, n_full);
+                body.polyline(Polyline {
+                    color: Some(self.background_arc_color),
+                    stroke_width: ring_width,
+                    points: bg_pts,
+                });
+                continue;
+            }
+            // threshold bands replace the background arc
+            let mut from = self.min;
+            let mut segments = vec![];
+            for band in self.color_bands.iter() {
+                let to = band.to.clamp(self.min, self.max);
+                if to > from {
+                    segments.push((from, to, band.color));
+                    from = to;
+                }
+            }
+            if from < self.max {
+                segments.push((from, self.max, self.background_arc_color));
+            }
+            for (from, to, color) in segments {
+                let n =
+                    ((n_full as f32 * (get_ratio(to) - get_ratio(from))).round() as usize).max(1);
+                body.polyline(Polyline {
+                    color: Some(color),
+                    stroke_width: ring_width,
+                    points: arc_points(
+                        cx,
+                        cy,
+                        arc_r,
+                        start + get_ratio(from) * sweep,
+                        start + get_ratio(to) * sweep,
+                        n,
+                    ),
+                });
+            }
+        }
 
         // ── Progress arc ──────────────────────────────────────────────────────
-        let progress_color = if !self.pointer_color.is_zero() {
-            self.pointer_color
+        // A single needle keeps its progress arc unless bands color the dial,
+        // the progress style draws one ring per series.
+        let progress_values = if self.progress {
+            values.len()
+        } else if values.len() == 1 && self.color_bands.is_empty() {
+            1
         } else {
-            get_color(&self.series_colors, 0)
+            0
         };
-
-        if ratio > 0.0 {
+        for (i, (index, value)) in values.iter().take(progress_values).enumerate() {
+            let ratio = get_ratio(*value);
+            if ratio <= 0.0 {
+                continue;
+            }
+            let color = if self.progress {
+                get_band_color(*value).unwrap_or_else(|| get_series_color(i, *index))
+            } else {
+                get_series_color(i, *index)
+            };
             let n_prog = (n_full as f32 * ratio).round() as usize;
             let n_prog = n_prog.max(1);
-            let prog_pts = arc_points(cx, cy, arc_r, start, value_angle, n_prog);
+            let prog_pts = arc_points(cx, cy, get_arc_r(i), start, start + ratio * sweep, n_prog);
             body.polyline(Polyline {
-                color: Some(progress_color),
-                stroke_width: self.arc_width,
+                color: Some(color),
+                stroke_width: ring_width,
                 points: prog_pts,
             });
         }
 
         // ── Tick marks ────────────────────────────────────────────────────────
         let tick_color = self.background_arc_color;
+        let split_angle = sweep / self.split_number as f32;
         for i in 0..=self.split_number {
-            let tick_angle = start + i as f32 * sweep / self.split_number as f32;
+            let tick_angle = start + i as f32 * split_angle;
             let outer = get_pie_point(cx, cy, r + 2.0, tick_angle);
             let inner = get_pie_point(cx, cy, r - self.arc_width - 2.0, tick_angle);
             body.line(Line {
@@ -269,6 +424,25 @@ impl GaugeChart {
                 ..Default::default()
             });
         }
+        if self.minor_split_number > 0 {
+            let minor_angle = split_angle / (self.minor_split_number + 1) as f32;
+            for i in 0..self.split_number {
+                for j in 1..=self.minor_split_number {
+                    let tick_angle = start + i as f32 * split_angle + j as f32 * minor_angle;
+                    let outer = get_pie_point(cx, cy, r + 2.0, tick_angle);
+                    let inner = get_pie_point(cx, cy, r - self.arc_width / 2.0, tick_angle);
+                    body.line(Line {
+                        color: Some(tick_color),
+                        stroke_width: 1.0,
+                        left: inner.x,
+                        top: inner.y,
+                        right: outer.x,
+                        bottom: outer.y,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
 
         // ── Axis labels (min / max) ───────────────────────────────────────────
         let show_label = self.show_axis_label.unwrap_or(true);
@@ -280,13 +454,7 @@ impl GaugeChart {
             let min_pt = get_pie_point(cx, cy, label_r, start);
             let max_pt = get_pie_point(cx, cy, label_r, end);
 
-            let fmt = |v: f32| -> String {
-                if v == v.round() {
-                    format!("{}", v as i64)
-                } else {
-                    format!("{:.1}", v)
-                }
-            };
+            let fmt = |v: f32| -> String { format_value(v, &self.axis_formatter) };
 
             body.text(Text {
                 text: fmt(self.min),
@@ -314,7 +482,7 @@ impl GaugeChart {
             // Tick value labels
             if self.split_number > 0 {
                 for i in 1..self.split_number {
-                    let tick_angle = start + i as f32 * sweep / self.split_number as f32;
+                    let tick_angle = start + i as f32 * split_angle;
                     let tick_v =
                         self.min + i as f32 * (self.max - self.min) / self.split_number as f32;
                     let pt = get_pie_point(cx, cy, label_r, tick_angle);
@@ -334,29 +502,34 @@ impl GaugeChart {
         }
 
         // ── Pointer / needle ──────────────────────────────────────────────────
-        let show_ptr = self.show_pointer.unwrap_or(true);
+        let show_ptr = self.show_pointer.unwrap_or(!self.progress);
         if show_ptr {
             let needle_len = r - self.arc_width - 8.0;
             let base_offset = 6.0;
 
-            let tip = get_pie_point(cx, cy, needle_len, value_angle);
-            let base_l = get_pie_point(cx, cy, base_offset, value_angle + 90.0);
-            let base_r = get_pie_point(cx, cy, base_offset, value_angle - 90.0);
-
-            body.polygon(Polygon {
-                color: Some(progress_color),
-                fill: Some(progress_color),
-                points: vec![tip, base_l, base_r],
-                ..Default::default()
-            });
+            for (i, (index, value)) in values.iter().enumerate() {
+                let value_angle = start + get_ratio(*value) * sweep;
+                let color = get_series_color(i, *index);
+                let tip = get_pie_point(cx, cy, needle_len, value_angle);
+                let base_l = get_pie_point(cx, cy, base_offset, value_angle + 90.0);
+                let base_r = get_pie_point(cx, cy, base_offset, value_angle - 90.0);
+
+                body.polygon(Polygon {
+                    color: Some(color),
+                    fill: Some(color),
+                    points: vec![tip, base_l, base_r],
+                    ..Default::default()
+                });
+            }
 
             // Center hub circle
+            let hub_color = get_series_color(0, values[0].0);
             body.circle(Circle {
                 cx,
                 cy,
                 r: base_offset + 2.0,
-                stroke_color: Some(progress_color),
-                fill: Some(progress_color),
+                stroke_color: Some(hub_color),
+                fill: Some(hub_color),
                 stroke_width: 1.0,
                 ..Default::default()
             });
@@ -364,22 +537,38 @@ impl GaugeChart {
 
         // ── Value label ───────────────────────────────────────────────────────
         let value_font_size = (r * 0.25).clamp(14.0, 36.0);
-        let formatter = if self.value_formatter.is_empty() {
-            "{c}".to_string()
-        } else {
-            self.value_formatter.clone()
-        };
-        let value_text = if raw_value == raw_value.round() {
-            formatter.replace("{c}", &format!("{}", raw_value as i64))
-        } else {
-            formatter.replace("{c}", &format!("{:.1}", raw_value))
-        };
 
         // Place the detail label at ~40% radius below center
         let detail_y = cy + r * 0.55;
 
+        if values.len() > 1 {
+            // one "name value" row per series, in the series color
+            let font_size = (value_font_size * 0.5).max(self.series_label_font_size);
+            for (i, (index, value)) in values.iter().enumerate() {
+                let name = self
+                    .series_list
+                    .get(i)
+                    .map(|s| s.name.clone())
+                    .unwrap_or_default();
+                let value_text = format_value(*value, &self.value_formatter);
+                body.text(Text {
+                    text: format!("{name} {value_text}").trim().to_string(),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(get_series_color(i, *index)),
+                    font_size: Some(font_size),
+                    font_weight: Some("bold".to_string()),
+                    dominant_baseline: Some("middle".to_string()),
+                    text_anchor: Some("middle".to_string()),
+                    x: Some(cx),
+                    y: Some(detail_y + i as f32 * (font_size + 4.0)),
+                    ..Default::default()
+                });
+            }
+            return c.svg();
+        }
+
         body.text(Text {
-            text: value_text,
+            text: format_value(values[0].1, &self.value_formatter),
             font_family: Some(self.font_family.clone()),
             font_color: Some(self.title_font_color),
             font_size: Some(value_font_size),
@@ -442,4 +631,87 @@ mod tests {
             chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn gauge_value_format() {
+        let mut chart = GaugeChart::new(vec![("Speed", vec![12300.0]).into()]);
+        chart.max = 20000.0;
+        chart.value_formatter = "{t} rpm".to_string();
+        assert!(chart.svg().unwrap().contains("\n12,300 rpm\n"));
+    }
+
+    #[test]
+    fn gauge_chart_color_bands_pointers() {
+        let chart = GaugeChart::from_json(
+            r##"{
+                "max": 8000,
+                "minor_split_number": 4,
+                "value_formatter": "{t} rpm",
+                "axis_formatter": "{t}",
+                "color_bands": [
+                    {"to": 5000, "color": "#91CC75"},
+                    {"to": 6500, "color": "#FAC858"},
+                    {"to": 8000, "color": "#EE6666"}
+                ],
+                "series_list": [
+                    {"name": "Engine 1", "data": [3200]},
+                    {"name": "Engine 2", "data": [7100]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // bands replace the background arc
+        assert!(svg.contains(r##"stroke="#91CC75""##));
+        assert!(svg.contains(r##"stroke="#EE6666""##));
+        assert_eq!(3, svg.matches("<polyline").count());
+        // one needle per series
+        assert_eq!(2, svg.matches("<polygon").count());
+        assert!(svg.contains("Engine 1 3,200 rpm"));
+        assert!(svg.contains("Engine 2 7,100 rpm"));
+        assert!(svg.contains("1,600"));
+        // 5 splits x 4 minor ticks + 6 major ticks + 2 legend lines
+        assert_eq!(28, svg.matches("<line").count());
+    }
+
+    #[test]
+    fn gauge_chart_progress() {
+        let mut chart = GaugeChart::new(vec![
+            ("CPU", vec![82.0]).into(),
+            ("Memory", vec![45.0]).into(),
+        ]);
+        chart.progress = true;
+        chart.color_bands = vec![
+            (60.0, (145, 204, 117).into()).into(),
+            (100.0, (238, 102, 102).into()).into(),
+        ];
+        let svg = chart.svg().unwrap();
+        // no needle in the progress style
+        assert!(!svg.contains("<polygon"));
+        // two background rings and two progress rings colored by band
+        assert_eq!(4, svg.matches("<polyline").count());
+        assert!(svg.contains(r##"stroke="#EE6666""##));
+        assert!(svg.contains(r##"stroke="#91CC75""##));
+        assert!(svg.contains("CPU 82"));
+    }
+
+    #[test]
+    fn gauge_chart_progress_many_rings() {
+        let mut chart = GaugeChart::new(
+            (0..20)
+                .map(|i| (format!("S{i}").as_str(), vec![50.0]).into())
+                .collect(),
+        );
+        chart.progress = true;
+        chart.arc_width = 30.0;
+        let svg = chart.svg().unwrap();
+        // every ring stays between the center and the outer radius
+        let cx = chart.width / 2.0;
+        for polyline in svg.split("<polyline").skip(1) {
+            let points = polyline.split(r#"points=""#).nth(1).unwrap();
+            let (x, _) = points.split_once(',').unwrap();
+            let x: f32 = x.parse().unwrap();
+            assert!(x < cx, "ring mirrored through the center: {x}");
+        }
+    }
 }
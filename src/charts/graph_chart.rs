@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -158,14 +158,15 @@ impl GraphChart {
                     if name.is_empty() {
                         return None;
                     }
-                    Some(GraphNode {
+                    let color = get_color_from_value(item, "color");
+                    Some(color.map(|color| GraphNode {
                         name,
                         value: get_f32_from_value(item, "value").unwrap_or_default(),
-                        color: get_color_from_value(item, "color"),
+                        color,
                         category: get_usize_from_value(item, "category"),
-                    })
+                    }))
                 })
-                .collect();
+                .collect::<canvas::Result<_>>()?;
         }
         if let Some(arr) = value.get("links").and_then(|v| v.as_array()) {
             c.links = arr
@@ -196,6 +197,9 @@ impl GraphChart {
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -11,14 +11,14 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
 use super::font::measure_max_text_width_family;
 use super::params::*;
-use super::theme::{get_default_theme_name, get_theme};
+use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
 use crate::charts::measure_text_width_family;
 
@@ -126,23 +126,23 @@ impl std::ops::DerefMut for HeatmapChart {
 }
 
 impl HeatmapChart {
-    fn fill_default(&mut self) {
+    fn fill_default(&mut self, t: &Theme) {
         if self.y_axis_configs[0].axis_stroke_color.is_zero() {
             self.y_axis_configs[0].axis_stroke_color = self.x_axis_stroke_color;
         }
         self.y_axis_configs[0].axis_name_align = Some(Align::Center);
         self.y_axis_configs[0].axis_split_number += 1;
         if self.series.max_color.is_zero() {
-            self.series.max_color = (191, 68, 76).into();
+            self.series.max_color = t.heatmap_max_color;
         }
         if self.series.min_color.is_zero() {
-            self.series.min_color = (240, 217, 156).into();
+            self.series.min_color = t.heatmap_min_color;
         }
         if self.series.min_font_color.is_zero() {
-            self.series.min_font_color = (70, 70, 70).into();
+            self.series.min_font_color = t.heatmap_min_font_color;
         }
         if self.series.max_font_color.is_zero() {
-            self.series.max_font_color = (238, 238, 238).into();
+            self.series.max_font_color = t.heatmap_max_font_color;
         }
         if self.series.max == 0.0 {
             let mut max = 0.0;
@@ -170,16 +170,16 @@ impl HeatmapChart {
             if let Some(max) = get_f32_from_value(value, "max") {
                 h.series.max = max;
             }
-            if let Some(min_color) = get_color_from_value(value, "min_color") {
+            if let Some(min_color) = get_color_from_value(value, "min_color")? {
                 h.series.min_color = min_color;
             }
-            if let Some(max_color) = get_color_from_value(value, "max_color") {
+            if let Some(max_color) = get_color_from_value(value, "max_color")? {
                 h.series.max_color = max_color;
             }
-            if let Some(min_font_color) = get_color_from_value(value, "min_font_color") {
+            if let Some(min_font_color) = get_color_from_value(value, "min_font_color")? {
                 h.series.min_font_color = min_font_color;
             }
-            if let Some(max_font_color) = get_color_from_value(value, "max_font_color") {
+            if let Some(max_font_color) = get_color_from_value(value, "max_font_color")? {
                 h.series.max_font_color = max_font_color;
             }
             if let Some(data) = value.get("data") {
@@ -200,7 +200,7 @@ impl HeatmapChart {
                 h.series.data = values;
             }
         }
-        h.fill_default();
+        h.fill_default(&get_theme_from_value(&value));
         Ok(h)
     }
     /// Creates a heatmap chart with default theme.
@@ -238,12 +238,15 @@ impl HeatmapChart {
         }
         h.series.data = data;
         let theme = get_theme(theme);
-        h.base.fill_theme(theme, &mut h.y_axis_configs);
-        h.fill_default();
+        h.base.fill_theme(theme.clone(), &mut h.y_axis_configs);
+        h.fill_default(&theme);
         h
     }
     /// Converts heatmap chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         if self.x_axis_data.is_empty() || self.y_axis_data.is_empty() {
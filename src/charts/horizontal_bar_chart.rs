@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::{ChartBase, get_y_axis_config};
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -87,6 +87,9 @@ impl HorizontalBarChart {
     }
     /// Converts horizontal bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -129,16 +132,31 @@ impl HorizontalBarChart {
             ..Default::default()
         });
 
-        let mut data_list = vec![];
-        for series in self.series_list.iter() {
-            data_list.append(&mut series.data_values());
-        }
+        let (data_list, percent) = if self.series_stack_mode == StackMode::Percent {
+            self.get_axis_data_list(None)?
+        } else {
+            (
+                self.series_list
+                    .iter()
+                    .flat_map(|series| series.data_values())
+                    .collect(),
+                false,
+            )
+        };
+        let max = if percent { Some(100.0) } else { None };
         let x_axis_config = get_y_axis_config(&self.y_axis_configs, 0);
-        let x_axis_values = get_axis_values(AxisValueParams {
+        let mut x_axis_values = get_axis_values(AxisValueParams {
             data_list,
             split_number: x_axis_config.axis_split_number,
+            max,
             ..Default::default()
         });
+        if percent {
+            x_axis_values
+                .data
+                .iter_mut()
+                .for_each(|item| item.push('%'));
+        }
 
         let x_axis_width = c.width() - y_axis_width;
         c.child(Box {
@@ -191,41 +209,94 @@ impl HorizontalBarChart {
             let bar_chart_margin = 5.0_f32;
             let bar_chart_gap = 3.0_f32;
 
+            // In the percent stack mode, series sharing a stack key (name +
+            // y_axis_index) share one row slot; otherwise every series gets a
+            // slot of its own.
+            let percent_stack = self.series_stack_mode == StackMode::Percent;
+            let get_stack = |series: &Series| series.stack.clone().filter(|_| percent_stack);
+            let mut stack_slots: Vec<(String, usize)> = vec![];
+            let mut series_slot_indices = Vec::with_capacity(self.series_list.len());
+            let mut slot_count = 0_usize;
+            for series in self.series_list.iter() {
+                if let Some(s) = get_stack(series) {
+                    let key = format!("{}_{}", s, series.y_axis_index);
+                    if let Some((_, slot)) = stack_slots.iter().find(|(k, _)| k == &key) {
+                        series_slot_indices.push(*slot);
+                        continue;
+                    }
+                    stack_slots.push((key, slot_count));
+                }
+                series_slot_indices.push(slot_count);
+                slot_count += 1;
+            }
+            let series_refs: Vec<&Series> = self.series_list.iter().collect();
+            let stack_totals = self.get_stack_totals(&series_refs, category_count);
+            let mut stack_acc: Vec<(String, Vec<f32>)> = stack_slots
+                .iter()
+                .map(|(k, _)| (k.clone(), vec![0.0_f32; category_count]))
+                .collect();
+
             let bar_chart_margin_height = bar_chart_margin * 2.0;
-            let bar_chart_gap_height = bar_chart_gap * (self.series_list.len() - 1) as f32;
-            let bar_height = (unit_height - bar_chart_margin_height - bar_chart_gap_height)
-                / self.series_list.len() as f32;
+            let bar_chart_gap_height = bar_chart_gap * (slot_count - 1) as f32;
+            let bar_height =
+                (unit_height - bar_chart_margin_height - bar_chart_gap_height) / slot_count as f32;
             let half_bar_height = bar_height / 2.0;
 
             let mut series_labels_list = vec![];
             for (index, series) in self.series_list.iter().enumerate() {
                 let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+                let stack_key = get_stack(series).map(|s| format!("{}_{}", s, series.y_axis_index));
+                let acc_idx = stack_key
+                    .as_ref()
+                    .and_then(|k| stack_acc.iter().position(|(ak, _)| ak == k));
+                let totals = stack_key
+                    .as_ref()
+                    .and_then(|k| stack_totals.iter().find(|(tk, _)| tk == k))
+                    .map(|(_, v)| v);
 
                 let mut series_labels = vec![];
                 let series_data_count = series.data.len();
                 for (i, p) in series.data_values().iter().enumerate() {
-                    let value = p.to_owned();
-                    if value == NIL_VALUE {
+                    let raw_value = p.to_owned();
+                    if raw_value == NIL_VALUE {
                         continue;
                     }
+                    let actual_i = i + series.start_index;
+                    let percentage = totals
+                        .and_then(|t| t.get(actual_i))
+                        .map(|t| get_percentage(raw_value, *t));
+                    let value = percentage.map_or(raw_value, |p| p * 100.0);
+                    let label_text =
+                        self.format_stack_label(series, actual_i, raw_value, percentage);
                     let mut top =
                         unit_height * (series_data_count - i - 1) as f32 + bar_chart_margin;
-                    top += (bar_height + bar_chart_gap) * index as f32;
+                    top += (bar_height + bar_chart_gap) * series_slot_indices[index] as f32;
 
-                    let x = max_width - x_axis_values.get_offset_height(value, max_width);
+                    // Stacked segments start where the previous one ended.
+                    let acc = acc_idx
+                        .and_then(|aidx| stack_acc[aidx].1.get(actual_i).copied())
+                        .unwrap_or(0.0);
+                    let left = if acc == 0.0 {
+                        0.0
+                    } else {
+                        max_width - x_axis_values.get_offset_height(acc, max_width)
+                    };
+                    let x = max_width - x_axis_values.get_offset_height(acc + value, max_width);
+                    if let Some(aidx) = acc_idx
+                        && actual_i < category_count
+                    {
+                        stack_acc[aidx].1[actual_i] += value;
+                    }
                     let tip = if self.tooltip_show {
-                        Some(format!(
-                            "{}: {}",
-                            series.name,
-                            format_series_value(value, &self.series_label_formatter)
-                        ))
+                        Some(format!("{}: {}", series.name, label_text))
                     } else {
                         None
                     };
                     c1.rect(Rect {
-                        fill: Some(color.into()),
+                        fill: Some(series.fill.clone().unwrap_or_else(|| color.into())),
+                        left,
                         top,
-                        width: x,
+                        width: x - left,
                         height: bar_height,
                         title: tip.clone(),
                         class: tip.as_ref().map(|_| "ct-trigger".to_string()),
@@ -245,10 +316,13 @@ impl HorizontalBarChart {
                             ..Default::default()
                         });
                     }
-                    series_labels.push(SeriesLabel {
-                        point: (x, top + half_bar_height).into(),
-                        text: format_series_value(value, &self.series_label_formatter),
-                    })
+                    series_labels.push((
+                        left,
+                        SeriesLabel {
+                            point: (x, top + half_bar_height).into(),
+                            text: label_text,
+                        },
+                    ))
                 }
                 if series.label_show {
                     series_labels_list.push(series_labels);
@@ -260,7 +334,7 @@ impl HorizontalBarChart {
                 .clone()
                 .unwrap_or(Position::Right);
             for series_labels in series_labels_list.iter() {
-                for series_label in series_labels.iter() {
+                for (left, series_label) in series_labels.iter() {
                     let mut dy = None;
                     let mut dx = Some(3.0);
                     let mut x = Some(series_label.point.x);
@@ -272,14 +346,14 @@ impl HorizontalBarChart {
                         dy = Some(value.height() / 2.0 - 2.0);
                         if series_label_position == Position::Inside {
                             dx = None;
-                            let offset = series_label.point.x - value.width();
+                            let offset = series_label.point.x - left - value.width();
                             if offset <= 0.0 {
-                                x = Some(1.0);
+                                x = Some(left + 1.0);
                             } else {
-                                x = Some(offset / 2.0);
+                                x = Some(left + offset / 2.0);
                             }
                         } else if series_label_position == Position::Left {
-                            x = Some(0.0);
+                            x = Some(*left);
                             dx = Some(-value.width());
                         }
                     }
@@ -450,4 +524,45 @@ mod tests {
         assert!(!svg.contains("inf"), "empty first series must not emit inf");
         assert!(!svg.contains("NaN"), "empty first series must not emit NaN");
     }
+
+    // Stacked series share one row and, in percent mode, every row spans the
+    // full 0–100% axis.
+    #[test]
+    fn horizontal_bar_chart_stack_percent() {
+        let chart = HorizontalBarChart::from_json(
+            r###"{
+                "series_stack_mode": "percent",
+                "series_list": [
+                    {"name": "A", "stack": "s", "label_show": true, "data": [1.0, 3.0]},
+                    {"name": "B", "stack": "s", "data": [3.0, 1.0]}
+                ],
+                "x_axis_data": ["x", "y"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n100%\n"), "missing 100% axis label");
+        assert!(svg.contains("\n25%\n"), "missing share label");
+        // The second segment starts where the first one ends.
+        assert!(svg.contains(r#"<rect x="17" y="203.5" width="144.5""#));
+        assert!(svg.contains(r#"<rect x="161.5" y="203.5" width="433.5""#));
+    }
+
+    // Outside of the percent mode, stacked series keep a row of their own.
+    #[test]
+    fn horizontal_bar_chart_stack_normal() {
+        let json = r###"{
+            "series_list": [
+                {"name": "A", "stack": "s", "data": [1.0, 3.0]},
+                {"name": "B", "stack": "s", "data": [3.0, 1.0]}
+            ],
+            "x_axis_data": ["x", "y"]
+        }"###;
+        let stacked = HorizontalBarChart::from_json(json).unwrap().svg().unwrap();
+        let plain = HorizontalBarChart::from_json(&json.replace(r#""stack": "s", "#, ""))
+            .unwrap()
+            .svg()
+            .unwrap();
+        assert_eq!(plain, stacked);
+    }
 }
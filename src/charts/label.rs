@@ -0,0 +1,149 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::common::LabelOverlap;
+use super::util::{Box, Point};
+
+/// Space kept between two labels moved apart.
+const LABEL_GAP: f32 = 2.0;
+
+/// A label to place: its box at the preferred position, the point it
+/// describes and its priority. Labels with a higher priority are placed
+/// first and keep their place.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LabelBox {
+    pub rect: Box,
+    pub anchor: Point,
+    pub priority: f32,
+}
+
+/// Where a label ends up: the vertical offset from its preferred position,
+/// whether it is dropped and the leader line from its anchor to its box.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LabelPlacement {
+    pub dy: f32,
+    pub hidden: bool,
+    pub leader_line: Option<(Point, Point)>,
+}
+
+fn is_overlapping(a: &Box, b: &Box) -> bool {
+    a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+}
+
+/// Resolves the overlapping labels with the strategy. Labels are placed by
+/// priority; a label that overlaps one already placed is moved up or down by
+/// its own height, upwards first, until it fits (nudge and leader line) or is dropped (hide).
+/// A label without a free place nearby stays where it is.
+pub(crate) fn place_labels(labels: &[LabelBox], overlap: LabelOverlap) -> Vec<LabelPlacement> {
+    let mut placements = vec![LabelPlacement::default(); labels.len()];
+    if overlap == LabelOverlap::Allow {
+        return placements;
+    }
+    let max_steps = match overlap {
+        LabelOverlap::Nudge => 2,
+        LabelOverlap::LeaderLine => 6,
+        _ => 0,
+    };
+    let mut order: Vec<usize> = (0..labels.len()).collect();
+    order.sort_by(|a, b| labels[*b].priority.total_cmp(&labels[*a].priority));
+
+    let mut placed: Vec<Box> = vec![];
+    for index in order {
+        let label = &labels[index];
+        let step = label.rect.height() + LABEL_GAP;
+        let found = std::iter::once(0.0)
+            .chain((1..=max_steps).map(|i| -(i as f32) * step))
+            .chain((1..=max_steps).map(|i| i as f32 * step))
+            .map(|dy| {
+                let rect = Box {
+                    top: label.rect.top + dy,
+                    bottom: label.rect.bottom + dy,
+                    ..label.rect.clone()
+                };
+                (dy, rect)
+            })
+            .find(|(_, rect)| !placed.iter().any(|item| is_overlapping(item, rect)));
+        let placement = &mut placements[index];
+        match found {
+            Some((dy, rect)) => {
+                placement.dy = dy;
+                if overlap == LabelOverlap::LeaderLine && dy != 0.0 {
+                    let end = Point {
+                        x: label.anchor.x.clamp(rect.left, rect.right),
+                        y: label.anchor.y.clamp(rect.top, rect.bottom),
+                    };
+                    placement.leader_line = Some((label.anchor, end));
+                }
+                placed.push(rect);
+            }
+            None if overlap == LabelOverlap::Hide => placement.hidden = true,
+            None => placed.push(label.rect.clone()),
+        }
+    }
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelBox, place_labels};
+    use crate::{Box, LabelOverlap, Point};
+    use pretty_assertions::assert_eq;
+
+    fn new_labels() -> Vec<LabelBox> {
+        [(0.0, 1.0), (10.0, 3.0), (100.0, 2.0)]
+            .into_iter()
+            .map(|(left, priority)| LabelBox {
+                rect: Box {
+                    left,
+                    top: 40.0,
+                    right: left + 30.0,
+                    bottom: 50.0,
+                },
+                anchor: Point {
+                    x: left + 15.0,
+                    y: 58.0,
+                },
+                priority,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn place_labels_strategy() {
+        let labels = new_labels();
+
+        let placements = place_labels(&labels, LabelOverlap::Allow);
+        assert!(placements.iter().all(|p| p.dy == 0.0 && !p.hidden));
+
+        // The first label yields to the second one, which has a higher priority.
+        let placements = place_labels(&labels, LabelOverlap::Nudge);
+        assert_eq!(
+            vec![-12.0, 0.0, 0.0],
+            placements.iter().map(|p| p.dy).collect::<Vec<_>>()
+        );
+        assert!(placements.iter().all(|p| p.leader_line.is_none()));
+
+        let placements = place_labels(&labels, LabelOverlap::LeaderLine);
+        assert_eq!(-12.0, placements[0].dy);
+        assert_eq!(
+            Some(((15.0, 58.0).into(), (15.0, 38.0).into())),
+            placements[0].leader_line
+        );
+        assert_eq!(None, placements[1].leader_line);
+
+        let placements = place_labels(&labels, LabelOverlap::Hide);
+        assert_eq!(
+            vec![true, false, false],
+            placements.iter().map(|p| p.hidden).collect::<Vec<_>>()
+        );
+    }
+}
@@ -11,11 +11,12 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::params::*;
 use super::theme::{get_default_theme_name, get_theme};
 use super::util::*;
 
@@ -34,6 +35,9 @@ pub struct LineChart {
     // grid
 
     // series
+    /// Baseline of the stacked series. A silhouette or wiggle baseline turns
+    /// the stacks into a stream graph and hides the y axis labels.
+    pub stack_baseline: StackBaseline,
 }
 
 impl std::ops::Deref for LineChart {
@@ -54,7 +58,10 @@ impl LineChart {
         let mut l = LineChart {
             ..Default::default()
         };
-        l.base.fill_option(data, &mut l.y_axis_configs)?;
+        let value = l.base.fill_option(data, &mut l.y_axis_configs)?;
+        if let Some(stack_baseline) = get_stack_baseline_from_value(&value, "stack_baseline")? {
+            l.stack_baseline = stack_baseline;
+        }
         Ok(l)
     }
     /// Creates a line chart with custom theme.
@@ -76,6 +83,38 @@ impl LineChart {
     pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> LineChart {
         LineChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
     }
+    /// Gets the y axis values for a moving stack baseline: the range covers
+    /// every band, since the offsets have no meaningful scale.
+    fn get_baseline_y_axis_values(&self, y_axis_index: usize) -> AxisValues {
+        let series_list: Vec<&Series> = self
+            .series_list
+            .iter()
+            .filter(|s| s.y_axis_index == y_axis_index)
+            .collect();
+        let mut data_list: Vec<f32> = series_list
+            .iter()
+            .filter(|s| s.stack.is_none())
+            .flat_map(|s| s.data_values())
+            .filter(|v| *v != NIL_VALUE)
+            .collect();
+        for (_, layers) in self.get_stack_layers(&series_list, self.x_axis_data.len()) {
+            let baselines = get_stack_baselines(&layers, &self.stack_baseline);
+            let (min, max) = get_stack_domain(&layers, &baselines);
+            data_list.extend([min, max]);
+        }
+        if data_list.is_empty() {
+            return AxisValues::default();
+        }
+        let y_axis_config = get_y_axis_config(&self.y_axis_configs, y_axis_index);
+        get_axis_values(AxisValueParams {
+            data_list,
+            split_number: y_axis_config.axis_split_number,
+            reverse: Some(true),
+            min: y_axis_config.axis_min,
+            max: y_axis_config.axis_max,
+            ..Default::default()
+        })
+    }
     fn render_mark_line(
         &self,
         c: Canvas,
@@ -169,6 +208,9 @@ impl LineChart {
     }
     /// Converts line chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let mut x_axis_height = self.x_axis_height;
@@ -177,8 +219,12 @@ impl LineChart {
         }
         let axis_top = self.render_header(&mut c);
 
-        let (left_y_axis_values, mut left_y_axis_width) =
-            self.get_y_axis_values(&self.y_axis_configs, 0);
+        let stream = self.stack_baseline != StackBaseline::Zero;
+        let (left_y_axis_values, mut left_y_axis_width) = if stream {
+            (self.get_baseline_y_axis_values(0), 0.0)
+        } else {
+            self.get_y_axis_values(&self.y_axis_configs, 0)?
+        };
         if self.y_axis_hidden {
             left_y_axis_width = 0.0;
         }
@@ -191,8 +237,11 @@ impl LineChart {
         let mut right_y_axis_values = AxisValues::default();
         let mut right_y_axis_width = 0.0_f32;
         if exist_right_y_axis {
-            (right_y_axis_values, right_y_axis_width) =
-                self.get_y_axis_values(&self.y_axis_configs, 1);
+            (right_y_axis_values, right_y_axis_width) = if stream {
+                (self.get_baseline_y_axis_values(1), 0.0)
+            } else {
+                self.get_y_axis_values(&self.y_axis_configs, 1)?
+            };
         }
 
         let axis_height = c.height() - x_axis_height - axis_top;
@@ -269,6 +318,7 @@ impl LineChart {
             max_height,
             axis_height,
             self.x_axis_data.len(),
+            &self.stack_baseline,
             self.animation.as_ref(),
             self.tooltip_show,
         );
@@ -796,4 +846,104 @@ mod tests {
         assert!(!svg.contains("NaN"), "empty data must not emit NaN");
         assert!(!svg.contains("inf"), "empty data must not emit inf");
     }
+
+    #[test]
+    fn line_chart_invalid_stack_baseline() {
+        let err = LineChart::from_json(r#"{"stack_baseline": "stream"}"#).unwrap_err();
+        assert_eq!(
+            r#"Params is invalid: stack baseline "stream" of "stack_baseline" is invalid"#,
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn line_chart_stack_percent() {
+        let chart = LineChart::from_json(
+            r###"{
+                "series_stack_mode": "percent",
+                "series_list": [
+                    {"name": "A", "stack": "s", "label_show": true, "data": [1.0, 3.0]},
+                    {"name": "B", "stack": "s", "label_show": true, "data": [3.0, 1.0]}
+                ],
+                "x_axis_data": ["x", "y"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n100%\n"), "missing 100% axis label");
+        assert!(svg.contains("\n75%\n"), "missing share label");
+        // The top series of the stack runs flat along 100%.
+        assert!(
+            svg.contains(r#"M 184.8 27 L 458.2 27"#),
+            "top line must sit at 100%"
+        );
+    }
+
+    #[test]
+    fn line_chart_stack_band_null() {
+        let chart = LineChart::from_json(
+            r###"{
+                "series_fill": true,
+                "series_list": [
+                    {"name": "A", "stack": "s", "data": [1.0, 2.0, 3.0]},
+                    {"name": "B", "stack": "s", "data": [2.0, null, 2.0]}
+                ],
+                "x_axis_data": ["a", "b", "c"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // The band of B runs through the missing point on top of A while
+        // its line stays broken.
+        assert!(svg.contains(
+            r#"<polygon points="124.2,177.2 312.5,239.8 500.8,52 500.8,177.2 312.5,239.8 124.2,302.4""#
+        ));
+        assert!(svg.contains(r#"<path d="M 124.2 177.2" stroke-width="2""#));
+    }
+
+    #[test]
+    fn line_chart_stack_baseline() {
+        let chart = LineChart::from_json(
+            r###"{
+                "series_fill": true,
+                "series_smooth": true,
+                "stack_baseline": "wiggle",
+                "series_list": [
+                    {"name": "A", "stack": "s", "data": [1.0, 2.0, 3.0]},
+                    {"name": "B", "stack": "s", "data": [3.0, 2.0, 1.0]}
+                ],
+                "x_axis_data": ["a", "b", "c"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // The smooth band closes along the smooth line below it.
+        assert!(svg.contains(
+            r#"<path d="M103.3,52 C152.5 59.9, 250.8 75.5, 300 83.3 C349.2 91.2, 447.5 106.8, 496.7 114.6 L496.7,177.2 C447.5 185, 349.2 200.7, 300 208.5 C250.8 216.3, 152.5 232, 103.3 239.8 Z""#
+        ));
+        // The offsets have no meaningful scale, so the y axis is unlabelled.
+        assert!(!svg.contains(r#"<text font-size="14" x="2""#));
+    }
+
+    #[test]
+    fn line_chart_stack_baseline_axis_range() {
+        let json = r###"{
+            "stack_baseline": "silhouette",
+            "series_list": [
+                {"name": "A", "stack": "s", "data": [1.0, 2.0, 3.0]},
+                {"name": "B", "stack": "s", "data": [3.0, 2.0, 1.0]}
+            ],
+            "x_axis_data": ["a", "b", "c"]
+        }"###;
+        let svg = LineChart::from_json(json).unwrap().svg().unwrap();
+        // The stack spans the whole plot area by default.
+        assert!(svg.contains(r#"<path d="M 103.3 43.1 L 300 43.1 L 496.7 43.1""#));
+        let json = json.replace(
+            r#""stack_baseline""#,
+            r#""y_axis_configs": [{"axis_min": -10, "axis_max": 10}], "stack_baseline""#,
+        );
+        let svg = LineChart::from_json(&json).unwrap().svg().unwrap();
+        // The configured range scales the -2..2 stack to a fifth of the height.
+        assert!(svg.contains(r#"<path d="M 103.3 162.2 L 300 162.2 L 496.7 162.2""#));
+    }
 }
@@ -15,8 +15,8 @@ use super::component::Rect;
 use super::component::generate_svg;
 use super::params::{get_color_from_value, get_f32_from_value, get_margin_from_value};
 use super::{
-    BarChart, CandlestickChart, HorizontalBarChart, LineChart, PieChart, RadarChart, ScatterChart,
-    TableChart,
+    BarChart, BulletChart, CandlestickChart, HorizontalBarChart, LineChart, PieChart, RadarChart,
+    ScatterChart, StatChart, TableChart,
 };
 use super::{Box, Color};
 
@@ -25,6 +25,8 @@ use super::{Box, Color};
 pub enum ChildChart {
     /// A bar chart child.
     Bar(BarChart, Option<(f32, f32)>),
+    /// A bullet chart child.
+    Bullet(BulletChart, Option<(f32, f32)>),
     /// A candlestick chart child.
     Candlestick(CandlestickChart, Option<(f32, f32)>),
     /// A horizontal bar chart child.
@@ -37,6 +39,8 @@ pub enum ChildChart {
     Radar(RadarChart, Option<(f32, f32)>),
     /// A scatter chart child.
     Scatter(ScatterChart, Option<(f32, f32)>),
+    /// A stat (KPI card) chart child.
+    Stat(StatChart, Option<(f32, f32)>),
     /// A table chart child.
     Table(TableChart, Option<(f32, f32)>),
 }
@@ -74,7 +78,7 @@ impl MultiChart {
         if let Some(gap) = get_f32_from_value(&value, "gap") {
             multi_chart.gap = gap;
         }
-        if let Some(background_color) = get_color_from_value(&value, "background_color") {
+        if let Some(background_color) = get_color_from_value(&value, "background_color")? {
             multi_chart.background_color = Some(background_color);
         }
         if let Some(child_charts) = value.get("child_charts")
@@ -105,7 +109,7 @@ impl MultiChart {
                 // item is a value parsed from json, so serialization will
                 // not fail in practice; propagate as an error just in case.
                 let mut str = serde_json::to_string(item)?;
-                if item.get("theme").is_none() {
+                if item.get("theme").is_none() && !theme.is_empty() {
                     // Splice the theme into the object: drop the closing '}'.
                     let body = str.strip_suffix('}').unwrap_or(&str);
                     str = format!(r###"{body},"theme":{theme}}}"###);
@@ -135,6 +139,14 @@ impl MultiChart {
                         let chart = ScatterChart::from_json(&str)?;
                         multi_chart.add(ChildChart::Scatter(chart, position));
                     }
+                    "bullet" => {
+                        let chart = BulletChart::from_json(&str)?;
+                        multi_chart.add(ChildChart::Bullet(chart, position));
+                    }
+                    "stat" => {
+                        let chart = StatChart::from_json(&str)?;
+                        multi_chart.add(ChildChart::Stat(chart, position));
+                    }
                     "candlestick" => {
                         let chart = CandlestickChart::from_json(&str)?;
                         multi_chart.add(ChildChart::Candlestick(chart, position));
@@ -201,6 +213,14 @@ impl MultiChart {
                         bottom: c.y + c.height,
                     }
                 }
+                ChildChart::Bullet(c, position) => {
+                    let c = place_child!(c, position);
+                    ChildChartResult {
+                        svg: c.svg()?,
+                        right: c.x + c.width,
+                        bottom: c.y + c.height,
+                    }
+                }
                 ChildChart::Candlestick(c, position) => {
                     let c = place_child!(c, position);
                     ChildChartResult {
@@ -249,6 +269,14 @@ impl MultiChart {
                         bottom: c.y + c.height,
                     }
                 }
+                ChildChart::Stat(c, position) => {
+                    let c = place_child!(c, position);
+                    ChildChartResult {
+                        svg: c.svg()?,
+                        right: c.x + c.width,
+                        bottom: c.y + c.height,
+                    }
+                }
                 ChildChart::Table(c, position) => {
                     let c = place_child!(c, position);
                     // the height is recomputed by the table itself
@@ -778,4 +806,57 @@ mod tests {
             charts.svg().unwrap()
         );
     }
+
+    #[test]
+    fn multi_chart_kpi_json() {
+        let charts = MultiChart::from_json(
+            r##"{
+                "child_charts": [
+                    {
+                        "type": "stat",
+                        "series_list": [{"name": "Revenue", "data": [210, 250]}]
+                    },
+                    {
+                        "type": "stat",
+                        "x": 280,
+                        "y": 10,
+                        "series_list": [{"name": "Orders", "data": [90, 80]}]
+                    },
+                    {
+                        "type": "bullet",
+                        "width": 540,
+                        "height": 120,
+                        "data": [{"name": "Revenue", "measure": 270, "target": 250, "ranges": [150, 225, 300]}]
+                    }
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(3, charts.charts.len());
+        assert!(matches!(charts.charts[0], ChildChart::Stat(_, None)));
+        assert!(matches!(charts.charts[2], ChildChart::Bullet(_, None)));
+        let svg = charts.svg().unwrap();
+        // two cards side by side, the bullet chart stacked below them
+        assert!(svg.starts_with(r#"<svg width="560" height="290""#));
+        assert!(svg.contains("19%"));
+        assert!(svg.contains("11.1%"));
+    }
+
+    #[test]
+    fn multi_chart_json_without_theme() {
+        // a missing top-level theme must not be spliced into the children
+        let charts = MultiChart::from_json(
+            r##"{
+                "child_charts": [
+                    {
+                        "type": "stat",
+                        "series_list": [{"name": "Revenue", "data": [210, 250]}]
+                    }
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(1, charts.charts.len());
+        assert!(charts.svg().is_ok());
+    }
 }
@@ -0,0 +1,419 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Palette generation for `series_colors`: named sequential, diverging and
+//! qualitative schemes, evenly spread distinguishable colors, and a check of
+//! adjacent colors under simulated color vision deficiencies.
+
+use super::color::{Color, srgb_to_linear};
+use std::fmt;
+
+/// The kind of a palette scheme.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PaletteKind {
+    /// Ordered data from low to high, light to dark.
+    Sequential,
+    /// Data diverging from a neutral middle value.
+    Diverging,
+    /// Unordered categories.
+    Qualitative,
+}
+
+/// A named color scheme, mostly from ColorBrewer.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Palette {
+    /// Sequential single hue blues.
+    Blues,
+    /// Sequential single hue greens.
+    Greens,
+    /// Sequential single hue oranges.
+    Oranges,
+    /// Sequential single hue reds.
+    Reds,
+    /// Sequential single hue purples.
+    Purples,
+    /// Sequential yellow, green and blue.
+    YellowGreenBlue,
+    /// Sequential yellow, orange and red.
+    YellowOrangeRed,
+    /// Sequential perceptually uniform viridis, colorblind safe.
+    Viridis,
+    /// Diverging red and blue.
+    RedBlue,
+    /// Diverging brown and blue green.
+    BrownBlueGreen,
+    /// Diverging pink and yellow green.
+    PinkYellowGreen,
+    /// Diverging spectral.
+    Spectral,
+    /// Qualitative pastel set.
+    Set2,
+    /// Qualitative dark set.
+    Dark2,
+    /// Qualitative Tableau 10.
+    Tableau10,
+    /// Qualitative Okabe-Ito, colorblind safe.
+    OkabeIto,
+    /// Qualitative Paul Tol bright, colorblind safe.
+    TolBright,
+    /// Qualitative Paul Tol muted, colorblind safe.
+    TolMuted,
+}
+
+impl Palette {
+    /// Returns the kind of the scheme.
+    pub fn kind(&self) -> PaletteKind {
+        match self {
+            Palette::Blues
+            | Palette::Greens
+            | Palette::Oranges
+            | Palette::Reds
+            | Palette::Purples
+            | Palette::YellowGreenBlue
+            | Palette::YellowOrangeRed
+            | Palette::Viridis => PaletteKind::Sequential,
+            Palette::RedBlue
+            | Palette::BrownBlueGreen
+            | Palette::PinkYellowGreen
+            | Palette::Spectral => PaletteKind::Diverging,
+            _ => PaletteKind::Qualitative,
+        }
+    }
+
+    /// Returns true if the scheme stays distinguishable under protanopia and
+    /// deuteranopia.
+    pub fn is_colorblind_safe(&self) -> bool {
+        matches!(
+            self,
+            Palette::Viridis | Palette::OkabeIto | Palette::TolBright | Palette::TolMuted
+        )
+    }
+
+    fn stops(&self) -> &'static [u32] {
+        match self {
+            Palette::Blues => &[
+                0xf7fbff, 0xdeebf7, 0xc6dbef, 0x9ecae1, 0x6baed6, 0x4292c6, 0x2171b5, 0x08519c,
+                0x08306b,
+            ],
+            Palette::Greens => &[
+                0xf7fcf5, 0xe5f5e0, 0xc7e9c0, 0xa1d99b, 0x74c476, 0x41ab5d, 0x238b45, 0x006d2c,
+                0x00441b,
+            ],
+            Palette::Oranges => &[
+                0xfff5eb, 0xfee6ce, 0xfdd0a2, 0xfdae6b, 0xfd8d3c, 0xf16913, 0xd94801, 0xa63603,
+                0x7f2704,
+            ],
+            Palette::Reds => &[
+                0xfff5f0, 0xfee0d2, 0xfcbba1, 0xfc9272, 0xfb6a4a, 0xef3b2c, 0xcb181d, 0xa50f15,
+                0x67000d,
+            ],
+            Palette::Purples => &[
+                0xfcfbfd, 0xefedf5, 0xdadaeb, 0xbcbddc, 0x9e9ac8, 0x807dba, 0x6a51a3, 0x54278f,
+                0x3f007d,
+            ],
+            Palette::YellowGreenBlue => &[
+                0xffffd9, 0xedf8b1, 0xc7e9b4, 0x7fcdbb, 0x41b6c4, 0x1d91c0, 0x225ea8, 0x253494,
+                0x081d58,
+            ],
+            Palette::YellowOrangeRed => &[
+                0xffffcc, 0xffeda0, 0xfed976, 0xfeb24c, 0xfd8d3c, 0xfc4e2a, 0xe31a1c, 0xbd0026,
+                0x800026,
+            ],
+            Palette::Viridis => &[
+                0x440154, 0x472d7b, 0x3b528b, 0x2c728e, 0x21918c, 0x28ae80, 0x5ec962, 0xaddc30,
+                0xfde725,
+            ],
+            Palette::RedBlue => &[
+                0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de,
+                0x4393c3, 0x2166ac, 0x053061,
+            ],
+            Palette::BrownBlueGreen => &[
+                0x543005, 0x8c510a, 0xbf812d, 0xdfc27d, 0xf6e8c3, 0xf5f5f5, 0xc7eae5, 0x80cdc1,
+                0x35978f, 0x01665e, 0x003c30,
+            ],
+            Palette::PinkYellowGreen => &[
+                0x8e0152, 0xc51b7d, 0xde77ae, 0xf1b6da, 0xfde0ef, 0xf7f7f7, 0xe6f5d0, 0xb8e186,
+                0x7fbc41, 0x4d9221, 0x276419,
+            ],
+            Palette::Spectral => &[
+                0x9e0142, 0xd53e4f, 0xf46d43, 0xfdae61, 0xfee08b, 0xffffbf, 0xe6f598, 0xabdda4,
+                0x66c2a5, 0x3288bd, 0x5e4fa2,
+            ],
+            Palette::Set2 => &[
+                0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3,
+            ],
+            Palette::Dark2 => &[
+                0x1b9e77, 0xd95f02, 0x7570b3, 0xe7298a, 0x66a61e, 0xe6ab02, 0xa6761d, 0x666666,
+            ],
+            Palette::Tableau10 => &[
+                0x4e79a7, 0xf28e2b, 0xe15759, 0x76b7b2, 0x59a14f, 0xedc948, 0xb07aa1, 0xff9da7,
+                0x9c755f, 0xbab0ac,
+            ],
+            Palette::OkabeIto => &[
+                0xe69f00, 0x56b4e9, 0x009e73, 0xf0e442, 0x0072b2, 0xd55e00, 0xcc79a7, 0x000000,
+            ],
+            Palette::TolBright => &[
+                0x4477aa, 0xee6677, 0x228833, 0xccbb44, 0x66ccee, 0xaa3377, 0xbbbbbb,
+            ],
+            Palette::TolMuted => &[
+                0x332288, 0x88ccee, 0x44aa99, 0x117733, 0x999933, 0xddcc77, 0xcc6677, 0x882255,
+                0xaa4499,
+            ],
+        }
+    }
+
+    /// Generates `count` colors of the scheme. Sequential and diverging
+    /// schemes are sampled evenly from end to end; qualitative schemes return
+    /// their colors in order, repeating when `count` exceeds them.
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        let stops: Vec<Color> = self.stops().iter().map(|&v| from_rgb(v)).collect();
+        if self.kind() == PaletteKind::Qualitative {
+            return (0..count).map(|i| stops[i % stops.len()]).collect();
+        }
+        (0..count)
+            .map(|i| {
+                let ratio = if count > 1 {
+                    i as f32 / (count - 1) as f32
+                } else {
+                    0.5
+                };
+                interpolate_color(&stops, ratio)
+            })
+            .collect()
+    }
+}
+
+fn from_rgb(value: u32) -> Color {
+    (
+        (value >> 16) as u8,
+        ((value >> 8) & 0xff) as u8,
+        (value & 0xff) as u8,
+    )
+        .into()
+}
+
+/// Interpolates evenly spaced color stops at `ratio` (0.0 to 1.0).
+pub(crate) fn interpolate_color(stops: &[Color], ratio: f32) -> Color {
+    if stops.len() < 2 {
+        return stops.first().copied().unwrap_or_default();
+    }
+    let position = ratio.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    stops[index].mix(stops[index + 1], position - index as f32)
+}
+
+/// Generates `count` distinguishable colors. Hues step by the golden angle so
+/// that neighbors are far apart on the color wheel, and the lightness cycles
+/// through three levels to separate colors of close hues.
+pub fn distinct_colors(count: usize) -> Vec<Color> {
+    const GOLDEN_ANGLE: f32 = 137.507_77;
+    (0..count)
+        .map(|i| {
+            let hue = 220.0 + i as f32 * GOLDEN_ANGLE;
+            let lightness = [0.5, 0.38, 0.64][(i / 3) % 3];
+            Color::from_hsl(hue, 0.65, lightness)
+        })
+        .collect()
+}
+
+/// A color vision deficiency to simulate.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColorVisionDeficiency {
+    /// Missing red cones.
+    Protanopia,
+    /// Missing green cones.
+    Deuteranopia,
+}
+
+impl fmt::Display for ColorVisionDeficiency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorVisionDeficiency::Protanopia => write!(f, "protanopia"),
+            ColorVisionDeficiency::Deuteranopia => write!(f, "deuteranopia"),
+        }
+    }
+}
+
+fn from_linear(v: f32) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let v = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0).round() as u8
+}
+
+/// Simulates how a color is seen with the deficiency (Machado et al. 2009,
+/// full severity).
+pub(crate) fn simulate_color_vision(color: Color, deficiency: ColorVisionDeficiency) -> Color {
+    let matrix = match deficiency {
+        ColorVisionDeficiency::Protanopia => [
+            [0.152_286, 1.052_583, -0.204_868],
+            [0.114_503, 0.786_281, 0.099_216],
+            [-0.003_882, -0.048_116, 1.051_998],
+        ],
+        ColorVisionDeficiency::Deuteranopia => [
+            [0.367_322, 0.860_646, -0.227_968],
+            [0.280_085, 0.672_501, 0.047_413],
+            [-0.011_820, 0.042_940, 0.968_881],
+        ],
+    };
+    let rgb = [
+        srgb_to_linear(color.r),
+        srgb_to_linear(color.g),
+        srgb_to_linear(color.b),
+    ];
+    let channel = |row: [f32; 3]| from_linear(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]);
+    Color {
+        r: channel(matrix[0]),
+        g: channel(matrix[1]),
+        b: channel(matrix[2]),
+        a: color.a,
+    }
+}
+
+/// Converts a color to CIE L*a*b* (D65).
+fn to_lab(color: Color) -> [f32; 3] {
+    let (r, g, b) = (
+        srgb_to_linear(color.r),
+        srgb_to_linear(color.g),
+        srgb_to_linear(color.b),
+    );
+    let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+    let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+    let f = |t: f32| {
+        if t > 0.008_856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Perceptual distance (CIE76 delta E) between two colors; below about 10
+/// colors are hard to tell apart in a chart.
+pub(crate) fn color_distance(a: Color, b: Color) -> f32 {
+    let (a, b) = (to_lab(a), to_lab(b));
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// The smallest distance under which adjacent series colors are reported.
+const MIN_COLOR_DISTANCE: f32 = 10.0;
+
+/// Two adjacent palette colors that are hard to tell apart with a color vision
+/// deficiency.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PaletteWarning {
+    /// Index of the first color.
+    pub index: usize,
+    /// The deficiency under which the colors look alike.
+    pub deficiency: ColorVisionDeficiency,
+    /// Distance of the simulated colors.
+    pub distance: f32,
+}
+
+impl fmt::Display for PaletteWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "colors {} and {} are hard to distinguish under {} (distance {:.1})",
+            self.index,
+            self.index + 1,
+            self.deficiency,
+            self.distance
+        )
+    }
+}
+
+/// Checks the adjacent colors of a palette, e.g. `series_colors`, under
+/// simulated protanopia and deuteranopia, returning a warning for every pair
+/// that looks alike.
+pub fn check_palette(colors: &[Color]) -> Vec<PaletteWarning> {
+    let mut warnings = vec![];
+    for (index, pair) in colors.windows(2).enumerate() {
+        for deficiency in [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+        ] {
+            let distance = color_distance(
+                simulate_color_vision(pair[0], deficiency),
+                simulate_color_vision(pair[1], deficiency),
+            );
+            if distance < MIN_COLOR_DISTANCE {
+                warnings.push(PaletteWarning {
+                    index,
+                    deficiency,
+                    distance,
+                });
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn palette_colors() {
+        let colors: Vec<String> = Palette::Blues.colors(3).iter().map(|c| c.hex()).collect();
+        assert_eq!(vec!["#F7FBFF", "#6BAED6", "#08306B"], colors);
+        let colors: Vec<String> = Palette::RedBlue.colors(5).iter().map(|c| c.hex()).collect();
+        assert_eq!(
+            vec!["#67001F", "#E58368", "#F7F7F7", "#6BACD1", "#053061"],
+            colors
+        );
+        // Qualitative colors repeat.
+        let colors = Palette::TolBright.colors(9);
+        assert_eq!(colors[0], colors[7]);
+        assert_eq!(PaletteKind::Diverging, Palette::Spectral.kind());
+    }
+
+    #[test]
+    fn palette_distinct_colors() {
+        let colors = distinct_colors(12);
+        assert_eq!(12, colors.len());
+        for (i, a) in colors.iter().enumerate() {
+            for b in colors.iter().skip(i + 1) {
+                assert!(color_distance(*a, *b) > 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn palette_check() {
+        for palette in [Palette::OkabeIto, Palette::TolBright, Palette::Viridis] {
+            assert!(palette.is_colorblind_safe());
+            assert_eq!(
+                Vec::<PaletteWarning>::new(),
+                check_palette(&palette.colors(7))
+            );
+        }
+        // Red next to green is the classic trap.
+        let warnings = check_palette(&["#d62728".into(), "#2ca02c".into(), "#1f77b4".into()]);
+        assert_eq!(1, warnings.len());
+        assert_eq!(
+            "colors 0 and 1 are hard to distinguish under deuteranopia (distance 7.3)",
+            warnings[0].to_string()
+        );
+        assert_eq!(
+            "#FFFFFF",
+            simulate_color_vision(Color::white(), ColorVisionDeficiency::Deuteranopia).hex()
+        );
+    }
+}
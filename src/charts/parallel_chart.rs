@@ -11,14 +11,102 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
-use super::theme::{get_default_theme_name, get_theme};
+use super::palette::interpolate_color;
+use super::params::*;
+use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
 
+// ── Axes ──────────────────────────────────────────────────────────────────────
+
+/// Options of one dimension axis, indexed like the dimensions.
+#[derive(Clone, Debug, Default)]
+pub struct ParallelAxis {
+    /// Fixed lower bound; `None` uses the smallest value of the dimension.
+    pub min: Option<f32>,
+    /// Fixed upper bound; `None` uses the largest value of the dimension.
+    pub max: Option<f32>,
+    /// Value scale of the axis. A log axis treats values that are not
+    /// positive as missing.
+    pub scale: AxisScale,
+    /// Whether the axis is flipped, drawing the min at the top.
+    pub inverse: bool,
+    /// Format of the min/max labels, supporting `{c}` value and `{t}`
+    /// thousands.
+    pub formatter: String,
+    /// Categories of a categorical dimension, spread evenly along the axis.
+    /// The value of a record on the dimension is the index of its category.
+    pub categories: Vec<String>,
+    /// Selected value range `(from, to)`; records outside it are dimmed.
+    pub brush: Option<(f32, f32)>,
+}
+
+fn parse_axis(value: &serde_json::Value) -> ParallelAxis {
+    ParallelAxis {
+        min: get_f32_from_value(value, "min"),
+        max: get_f32_from_value(value, "max"),
+        scale: get_axis_scale_from_value(value, "scale").unwrap_or_default(),
+        inverse: get_bool_from_value(value, "inverse").unwrap_or_default(),
+        formatter: get_string_from_value(value, "formatter").unwrap_or_default(),
+        categories: get_string_slice_from_value(value, "categories").unwrap_or_default(),
+        brush: None,
+    }
+}
+
+/// Gets the brush range of an axis, either as numbers or as category names.
+fn get_brush_from_value(value: &serde_json::Value, categories: &[String]) -> Option<(f32, f32)> {
+    let arr = value.get("brush")?.as_array()?;
+    let values: Vec<f32> = arr
+        .iter()
+        .filter_map(|item| {
+            if let Some(name) = item.as_str() {
+                categories
+                    .iter()
+                    .position(|category| category == name)
+                    .map(|index| index as f32)
+            } else {
+                item.as_f64().map(|v| v as f32)
+            }
+        })
+        .collect();
+    if values.len() != 2 {
+        return None;
+    }
+    Some((values[0], values[1]))
+}
+
+/// Alpha of the records outside a brush range.
+const DIMMED_ALPHA: u8 = 40;
+
+/// The computed range of one axis.
+struct AxisRange {
+    min: f32,
+    max: f32,
+    scale: AxisScale,
+}
+
+impl AxisRange {
+    /// Position of a value on the axis from 0.0 (min) to 1.0 (max), `None`
+    /// when a log axis gets a value that is not positive.
+    fn ratio(&self, value: f32) -> Option<f32> {
+        let ratio = match self.scale {
+            AxisScale::Linear => (value - self.min) / (self.max - self.min),
+            AxisScale::Log(base) => {
+                if value <= 0.0 {
+                    return None;
+                }
+                let log_min = self.min.log(base);
+                (value.log(base) - log_min) / (self.max.log(base) - log_min)
+            }
+        };
+        Some(ratio.clamp(0.0, 1.0))
+    }
+}
+
 // ── ParallelChart ────────────────────────────────────────────────────────────
 
 /// A parallel-coordinates chart: one vertical axis per dimension, and one
@@ -27,13 +115,22 @@ use super::util::*;
 ///
 /// Data reuses the shared model: `series_list` holds one [`Series`] per record
 /// (its `data` are the values, one per dimension) and `x_axis_data` holds the
-/// dimension names. Each axis is scaled independently to its own min..max.
+/// dimension names. Each axis is scaled independently to its own min..max
+/// unless configured otherwise in `axes`.
 #[derive(Clone, Debug, Default)]
 pub struct ParallelChart {
     /// The shared chart options (size, series, title/legend, axes); exposed
     /// directly on the chart through `Deref`, e.g. `chart.title_text`.
     pub base: ChartBase,
     y_axis_configs: Vec<YAxisConfig>,
+    /// Options of the dimension axes, indexed like the dimensions.
+    pub axes: Vec<ParallelAxis>,
+    /// Dimension whose value colors the records through `color_scale`
+    /// instead of the series colors.
+    pub color_dimension: Option<usize>,
+    /// Color stops from the min to the max of `color_dimension` (default: the
+    /// theme `parallel_color_scale`).
+    pub color_scale: Vec<Color>,
 }
 
 impl std::ops::Deref for ParallelChart {
@@ -49,6 +146,12 @@ impl std::ops::DerefMut for ParallelChart {
 }
 
 impl ParallelChart {
+    fn fill_default(&mut self, t: &Theme) {
+        if self.color_scale.is_empty() {
+            self.color_scale.clone_from(&t.parallel_color_scale);
+        }
+    }
+
     /// Creates a parallel-coordinates chart with the default theme.
     pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> ParallelChart {
         ParallelChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
@@ -65,7 +168,9 @@ impl ParallelChart {
         };
         c.series_list = series_list;
         c.x_axis_data = x_axis_data;
-        c.base.fill_theme(get_theme(theme), &mut c.y_axis_configs);
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.fill_default(&t);
         c
     }
 
@@ -75,12 +180,62 @@ impl ParallelChart {
             ..Default::default()
         };
         // `series_list` and `x_axis_data` are parsed by the derived fill_option.
-        c.base.fill_option(json, &mut c.y_axis_configs)?;
+        let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
+        let axis_values = value
+            .get("axes")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        c.axes = axis_values.iter().map(parse_axis).collect();
+        // String values belong to categorical dimensions: they are replaced by
+        // the index of their category, unknown ones are appended in order.
+        let series_values = value
+            .get("series_list")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let data_list = series_values.iter().filter_map(|item| {
+            item.get("data")
+                .and_then(|v| v.as_array())
+                .filter(|arr| !arr.is_empty())
+        });
+        for (series, data) in c.base.series_list.iter_mut().zip(data_list) {
+            for (j, item) in data.iter().enumerate() {
+                let Some(name) = item.as_str() else {
+                    continue;
+                };
+                if c.axes.len() <= j {
+                    c.axes.resize(j + 1, ParallelAxis::default());
+                }
+                let categories = &mut c.axes[j].categories;
+                let index = categories
+                    .iter()
+                    .position(|category| category == name)
+                    .unwrap_or_else(|| {
+                        categories.push(name.to_string());
+                        categories.len() - 1
+                    });
+                series.data[j] = Some(index as f32);
+            }
+        }
+        for (axis, item) in c.axes.iter_mut().zip(axis_values.iter()) {
+            axis.brush = get_brush_from_value(item, &axis.categories);
+        }
+        if let Some(v) = get_usize_from_value(&value, "color_dimension") {
+            c.color_dimension = Some(v);
+        }
+        if let Some(v) = get_color_slice_from_value(&value, "color_scale")? {
+            c.color_scale = v;
+        }
+        c.fill_default(&get_theme_from_value(&value));
         Ok(c)
     }
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -133,35 +288,74 @@ impl ParallelChart {
         // Value of a record on a dimension; `None` marks a missing point.
         let val = |s: &Series, j: usize| -> Option<f32> { s.data.get(j).copied().flatten() };
 
-        // Independent min/max per dimension.
-        let mut mins = vec![f32::MAX; n];
-        let mut maxs = vec![f32::MIN; n];
-        for s in &self.series_list {
-            for j in 0..n {
-                if let Some(v) = val(s, j) {
-                    mins[j] = mins[j].min(v);
-                    maxs[j] = maxs[j].max(v);
+        // Independent range per dimension, unless fixed by the axis options.
+        let ranges: Vec<AxisRange> = (0..n)
+            .map(|j| {
+                let axis = self.axes.get(j).cloned().unwrap_or_default();
+                if !axis.categories.is_empty() {
+                    // Categories are evenly spaced, each one in the middle of its
+                    // share of the axis.
+                    return AxisRange {
+                        min: -0.5,
+                        max: axis.categories.len() as f32 - 0.5,
+                        scale: AxisScale::Linear,
+                    };
                 }
-            }
-        }
-        for j in 0..n {
-            // Empty or flat dimension: fall back to a unit range so the mapping
-            // stays finite.
-            if !mins[j].is_finite() || !maxs[j].is_finite() {
-                mins[j] = 0.0;
-                maxs[j] = 1.0;
-            } else if (maxs[j] - mins[j]).abs() < f32::EPSILON {
-                maxs[j] = mins[j] + 1.0;
-            }
-        }
+                let log_base = match axis.scale {
+                    AxisScale::Log(base) => Some(base),
+                    AxisScale::Linear => None,
+                };
+                let mut min = f32::MAX;
+                let mut max = f32::MIN;
+                for v in self.series_list.iter().filter_map(|s| val(s, j)) {
+                    if log_base.is_some() && v <= 0.0 {
+                        continue;
+                    }
+                    min = min.min(v);
+                    max = max.max(v);
+                }
+                if let Some(v) = axis.min {
+                    min = v;
+                }
+                if let Some(v) = axis.max {
+                    max = v;
+                }
+                // Empty or flat dimension: fall back to a unit range (one order of
+                // magnitude on a log axis) so the mapping stays finite.
+                if let Some(base) = log_base {
+                    if !min.is_finite() || min <= 0.0 {
+                        min = 1.0;
+                    }
+                    if !max.is_finite() || max <= min {
+                        max = min * base;
+                    }
+                } else if !min.is_finite() || !max.is_finite() {
+                    min = 0.0;
+                    max = 1.0;
+                } else if (max - min).abs() < f32::EPSILON {
+                    max = min + 1.0;
+                }
+                AxisRange {
+                    min,
+                    max,
+                    scale: axis.scale,
+                }
+            })
+            .collect();
 
-        // Map a value on dimension `j` to a pixel y (top = max, bottom = min).
-        let y_of = |j: usize, v: f32| -> f32 {
-            plot_bottom - (v - mins[j]) / (maxs[j] - mins[j]) * plot_h
+        // Map a value on dimension `j` to a pixel y (top = max, bottom = min,
+        // flipped for an inverse axis).
+        let y_of = |j: usize, v: f32| -> Option<f32> {
+            let ratio = ranges[j].ratio(v)?;
+            if self.axes.get(j).is_some_and(|axis| axis.inverse) {
+                Some(plot_top + ratio * plot_h)
+            } else {
+                Some(plot_bottom - ratio * plot_h)
+            }
         };
 
         // ── Axes + labels ─────────────────────────────────────────────────────
-        for j in 0..n {
+        for (j, range) in ranges.iter().enumerate() {
             let x = x_at(j);
             content.line(Line {
                 color: Some(self.grid_stroke_color),
@@ -195,12 +389,43 @@ impl ParallelChart {
             } else {
                 (x + 3.0, "start")
             };
-            for (value, y) in [
-                (maxs[j], plot_top + font_size * 0.6),
-                (mins[j], plot_bottom - font_size * 0.6),
-            ] {
+            let axis = self.axes.get(j);
+            let mut labels = vec![];
+            if let Some(axis) = axis.filter(|axis| !axis.categories.is_empty()) {
+                // A tick and a label per category.
+                for (index, category) in axis.categories.iter().enumerate() {
+                    if let Some(y) = y_of(j, index as f32) {
+                        content.line(Line {
+                            color: Some(self.grid_stroke_color),
+                            stroke_width: self.grid_stroke_width.max(1.0),
+                            left: x - 3.0,
+                            top: y,
+                            right: x + 3.0,
+                            bottom: y,
+                            ..Default::default()
+                        });
+                        labels.push((category.clone(), y));
+                    }
+                }
+            } else {
+                let formatter = axis.map(|axis| axis.formatter.as_str()).unwrap_or_default();
+                let (top_value, bottom_value) = if axis.is_some_and(|axis| axis.inverse) {
+                    (range.min, range.max)
+                } else {
+                    (range.max, range.min)
+                };
+                labels.push((
+                    format_value(top_value, formatter),
+                    plot_top + font_size * 0.6,
+                ));
+                labels.push((
+                    format_value(bottom_value, formatter),
+                    plot_bottom - font_size * 0.6,
+                ));
+            }
+            for (text, y) in labels {
                 content.text(Text {
-                    text: format_float(value),
+                    text,
                     font_family: Some(self.font_family.clone()),
                     font_color: Some(self.series_label_font_color),
                     font_size: Some(font_size * 0.85),
@@ -211,20 +436,58 @@ impl ParallelChart {
                     ..Default::default()
                 });
             }
+            // The brushed range is shaded over the axis.
+            if let Some((from, to)) = axis.and_then(|axis| axis.brush)
+                && let (Some(y1), Some(y2)) = (y_of(j, from), y_of(j, to))
+            {
+                content.rect(Rect {
+                    fill: Some(self.x_axis_font_color.with_alpha(60).into()),
+                    left: x - 5.0,
+                    top: y1.min(y2),
+                    width: 10.0,
+                    height: (y1 - y2).abs(),
+                    ..Default::default()
+                });
+            }
         }
 
         // ── Record polylines ──────────────────────────────────────────────────
+        // A record is selected when it is inside every brush range.
+        let selected = |s: &Series| -> bool {
+            self.axes.iter().enumerate().all(|(j, axis)| {
+                let Some((from, to)) = axis.brush else {
+                    return true;
+                };
+                val(s, j).is_some_and(|v| v >= from.min(to) && v <= from.max(to))
+            })
+        };
+        let mut lines = vec![];
         for (i, s) in self.series_list.iter().enumerate() {
-            let color = get_color(&self.series_colors, s.index.unwrap_or(i));
+            let mut color = get_color(&self.series_colors, s.index.unwrap_or(i));
+            if let Some(dimension) = self.color_dimension.filter(|&d| d < n)
+                && !self.color_scale.is_empty()
+                && let Some(ratio) = val(s, dimension).and_then(|v| ranges[dimension].ratio(v))
+            {
+                color = interpolate_color(&self.color_scale, ratio);
+            }
+            let is_selected = selected(s);
+            if !is_selected {
+                color = color.with_alpha(DIMMED_ALPHA);
+            }
             let mut points: Vec<Point> = Vec::with_capacity(n);
             for j in 0..n {
-                if let Some(v) = val(s, j) {
-                    points.push((x_at(j), y_of(j, v)).into());
+                if let Some(y) = val(s, j).and_then(|v| y_of(j, v)) {
+                    points.push((x_at(j), y).into());
                 }
             }
             if points.len() < 2 {
                 continue;
             }
+            lines.push((is_selected, color, points));
+        }
+        // Dimmed records are drawn first so the selected ones stay on top.
+        lines.sort_by_key(|(is_selected, _, _)| *is_selected);
+        for (_, color, points) in lines {
             content.polyline(Polyline {
                 color: Some(color),
                 stroke_width: self.series_stroke_width.max(1.0),
@@ -293,6 +556,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parallel_axes_json() {
+        let chart = ParallelChart::from_json(
+            r##"{
+                "title_text": "Cars",
+                "legend_show": false,
+                "x_axis_data": ["Origin", "MPG", "Horsepower", "Weight", "Price"],
+                "axes": [
+                    {},
+                    {"min": 10, "max": 40, "inverse": true},
+                    {"brush": [150, 300]},
+                    {"formatter": "{t} lbs"},
+                    {"scale": "log", "formatter": "${c}k"}
+                ],
+                "color_dimension": 1,
+                "series_list": [
+                    {"name": "Sedan", "data": ["Japan", 32, 180, 3200, 28]},
+                    {"name": "SUV", "data": ["USA", 24, 280, 4500, 42]},
+                    {"name": "Sports", "data": ["Europe", 21, 450, 3400, 75]},
+                    {"name": "Hatchback", "data": ["Japan", 38, 150, 2800, 22]},
+                    {"name": "Truck", "data": ["USA", 18, 310, 5200, 55]},
+                    {"name": "Coupe", "data": ["Europe", 27, 240, 3300, 38]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["Japan".to_string(), "USA".to_string(), "Europe".to_string()],
+            chart.axes[0].categories
+        );
+        assert_eq!(Some(1.0), chart.series_list[1].data[0]);
+        assert_eq!(
+            include_str!("../../asset/parallel_chart/axes_json.svg"),
+            chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn parallel_brush_category() {
+        let chart = ParallelChart::from_json(
+            r##"{
+                "x_axis_data": ["Origin", "MPG"],
+                "axes": [{"categories": ["Japan", "USA"], "brush": ["USA", "USA"]}],
+                "series_list": [
+                    {"name": "Sedan", "data": ["Japan", 32]},
+                    {"name": "SUV", "data": ["USA", 24]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(Some((1.0, 1.0)), chart.axes[0].brush);
+        let svg = chart.svg().unwrap();
+        // Only the record outside the brush is dimmed, and drawn first.
+        assert_eq!(1, svg.matches("stroke-opacity=").count());
+        let first = svg.split("<polyline").nth(1).unwrap();
+        assert!(
+            first
+                .split("/>")
+                .next()
+                .unwrap()
+                .contains("stroke-opacity=")
+        );
+    }
+
     #[test]
     fn parallel_flat_dimension_no_panic() {
         // A dimension where every record has the same value must not divide by 0.
@@ -10,10 +10,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{Align, Box, Color, LegendCategory, Series, SeriesCategory, Theme, YAxisConfig};
+use super::error::{Error, Result};
+use super::theme::get_theme;
+use super::{
+    Align, Box, Color, ColorStop, Fill, LegendCategory, PatternKind, Series, SeriesCategory, Theme,
+    YAxisConfig,
+};
 use crate::{
-    AxisScale, MarkLine, MarkLineCategory, MarkPoint, MarkPointCategory, NIL_VALUE, Position,
-    Symbol,
+    AxisScale, FunnelComparison, LabelOverlap, LabelRotate, MarkLine, MarkLineCategory, MarkPoint,
+    MarkPointCategory, NIL_VALUE, NodeSort, Position, StackBaseline, StackMode, Symbol,
 };
 use std::sync::Arc;
 
@@ -226,8 +231,29 @@ pub(crate) fn get_string_slice_from_value(
     None
 }
 
+/// Gets stack baseline value from serde json: `"zero"`, `"silhouette"` or
+/// `"wiggle"`.
+pub(crate) fn get_stack_baseline_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Result<Option<StackBaseline>> {
+    let Some(value) = get_string_from_value(value, key) else {
+        return Ok(None);
+    };
+    let baseline = match value.to_lowercase().as_str() {
+        "zero" => StackBaseline::Zero,
+        "silhouette" => StackBaseline::Silhouette,
+        "wiggle" => StackBaseline::Wiggle,
+        _ => return Err(invalid_option("stack baseline", &value, key)),
+    };
+    Ok(Some(baseline))
+}
+
 /// Gets y axis config value from serde json.
-pub(crate) fn get_y_axis_config_from_value(t: Arc<Theme>, item: &serde_json::Value) -> YAxisConfig {
+pub(crate) fn get_y_axis_config_from_value(
+    t: Arc<Theme>,
+    item: &serde_json::Value,
+) -> Result<YAxisConfig> {
     let mut y_config = YAxisConfig {
         axis_font_size: t.y_axis_font_size,
         axis_font_color: t.y_axis_font_color,
@@ -239,13 +265,13 @@ pub(crate) fn get_y_axis_config_from_value(t: Arc<Theme>, item: &serde_json::Val
     if let Some(axis_font_size) = get_f32_from_value(item, "axis_font_size") {
         y_config.axis_font_size = axis_font_size;
     }
-    if let Some(axis_font_color) = get_color_from_value(item, "axis_font_color") {
+    if let Some(axis_font_color) = get_color_from_value(item, "axis_font_color")? {
         y_config.axis_font_color = axis_font_color;
     }
     if let Some(axis_font_weight) = get_string_from_value(item, "axis_font_weight") {
         y_config.axis_font_weight = Some(axis_font_weight);
     }
-    if let Some(axis_stroke_color) = get_color_from_value(item, "axis_stroke_color") {
+    if let Some(axis_stroke_color) = get_color_from_value(item, "axis_stroke_color")? {
         y_config.axis_stroke_color = axis_stroke_color;
     }
     if let Some(axis_width) = get_f32_from_value(item, "axis_width") {
@@ -272,7 +298,7 @@ pub(crate) fn get_y_axis_config_from_value(t: Arc<Theme>, item: &serde_json::Val
     if let Some(scale) = get_axis_scale_from_value(item, "axis_scale") {
         y_config.axis_scale = scale;
     }
-    y_config
+    Ok(y_config)
 }
 
 /// Gets axis scale value from serde json.
@@ -303,41 +329,140 @@ pub(crate) fn get_axis_scale_from_value(value: &serde_json::Value, key: &str) ->
     None
 }
 
+/// Gets label overlap value from serde json: `"allow"`, `"nudge"`,
+/// `"leader_line"` or `"hide"`.
+pub(crate) fn get_label_overlap_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Result<Option<LabelOverlap>> {
+    let Some(value) = get_string_from_value(value, key) else {
+        return Ok(None);
+    };
+    let overlap = match value.to_lowercase().as_str() {
+        "allow" => LabelOverlap::Allow,
+        "nudge" => LabelOverlap::Nudge,
+        "leader_line" => LabelOverlap::LeaderLine,
+        "hide" => LabelOverlap::Hide,
+        _ => return Err(invalid_option("label overlap", &value, key)),
+    };
+    Ok(Some(overlap))
+}
+
+/// Gets label rotate value from serde json, `"tangential"`, `"radial"` or
+/// `"auto"`.
+pub(crate) fn get_label_rotate_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Result<Option<LabelRotate>> {
+    let Some(value) = get_string_from_value(value, key) else {
+        return Ok(None);
+    };
+    let rotate = match value.to_lowercase().as_str() {
+        "tangential" => LabelRotate::Tangential,
+        "radial" => LabelRotate::Radial,
+        "auto" => LabelRotate::Auto,
+        _ => return Err(invalid_option("label rotate", &value, key)),
+    };
+    Ok(Some(rotate))
+}
+
+/// Gets funnel comparison value from serde json, `"mirror"` or `"overlay"`;
+/// any other string draws a single funnel.
+pub(crate) fn get_funnel_comparison_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<FunnelComparison> {
+    let value = get_string_from_value(value, key)?;
+    match value.to_lowercase().as_str() {
+        "mirror" => Some(FunnelComparison::Mirror),
+        "overlay" => Some(FunnelComparison::Overlay),
+        _ => None,
+    }
+}
+
+/// Gets node sort value from serde json, `"asc"` or `"desc"`; any other
+/// string keeps the relaxed order.
+pub(crate) fn get_node_sort_from_value(value: &serde_json::Value, key: &str) -> Option<NodeSort> {
+    let value = get_string_from_value(value, key)?;
+    match value.to_lowercase().as_str() {
+        "asc" => Some(NodeSort::Asc),
+        "desc" => Some(NodeSort::Desc),
+        _ => None,
+    }
+}
+
+/// Gets stack mode value from serde json, `"normal"` or `"percent"` (which
+/// normalizes every stack to 100%).
+pub(crate) fn get_stack_mode_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Result<Option<StackMode>> {
+    let Some(value) = get_string_from_value(value, key) else {
+        return Ok(None);
+    };
+    let mode = match value.to_lowercase().as_str() {
+        "normal" => StackMode::Normal,
+        "percent" => StackMode::Percent,
+        _ => return Err(invalid_option("stack mode", &value, key)),
+    };
+    Ok(Some(mode))
+}
+
+/// The error of an option set to a name it doesn't know.
+fn invalid_option(kind: &str, value: &str, key: &str) -> Error {
+    Error::Params {
+        message: format!("{kind} {value:?} of \"{key}\" is invalid"),
+    }
+}
+
 /// Gets y axis config value from serde json.
 pub(crate) fn get_y_axis_configs_from_value(
     t: Arc<Theme>,
     value: &serde_json::Value,
     key: &str,
-) -> Option<Vec<YAxisConfig>> {
+) -> Result<Option<Vec<YAxisConfig>>> {
     if let Some(arr) = value.get(key)
         && let Some(values) = arr.as_array()
     {
-        return Some(
-            values
-                .iter()
-                .map(|item| get_y_axis_config_from_value(t.clone(), item))
-                .collect(),
-        );
+        return values
+            .iter()
+            .map(|item| get_y_axis_config_from_value(t.clone(), item))
+            .collect::<Result<_>>()
+            .map(Some);
     }
-    None
+    Ok(None)
+}
+
+/// Parses a color of the `key` option, naming the option when it is invalid.
+fn parse_color(value: &serde_json::Value, key: &str) -> Result<Color> {
+    value
+        .as_str()
+        .and_then(|color| Color::parse(color).ok())
+        .ok_or_else(|| Error::Params {
+            message: format!("color {value} of \"{key}\" is invalid"),
+        })
 }
 
 /// Gets color value from serde json.
 pub(crate) fn get_color_slice_from_value(
     value: &serde_json::Value,
     key: &str,
-) -> Option<Vec<Color>> {
+) -> Result<Option<Vec<Color>>> {
     if let Some(arr) = value.get(key)
         && let Some(values) = arr.as_array()
     {
-        return Some(
-            values
-                .iter()
-                .map(|item| item.as_str().unwrap_or_default().into())
-                .collect(),
-        );
+        return values
+            .iter()
+            .map(|item| parse_color(item, key))
+            .collect::<Result<_>>()
+            .map(Some);
     }
-    None
+    Ok(None)
+}
+
+/// Gets the theme named by the `theme` option, the default theme when unset.
+pub(crate) fn get_theme_from_value(value: &serde_json::Value) -> Arc<Theme> {
+    get_theme(&get_string_from_value(value, "theme").unwrap_or_default())
 }
 
 /// Gets string value from serde json.
@@ -369,14 +494,82 @@ pub(crate) fn get_position_from_value(value: &serde_json::Value, key: &str) -> O
 }
 
 /// Gets color value from serde json.
-pub(crate) fn get_color_from_value(value: &serde_json::Value, key: &str) -> Option<Color> {
-    if let Some(s) = get_string_from_value(value, key) {
-        return Some(s.as_str().into());
+/// A string that is not a valid color is an error naming `key`.
+pub(crate) fn get_color_from_value(value: &serde_json::Value, key: &str) -> Result<Option<Color>> {
+    match value.get(key) {
+        Some(color) if color.is_string() => parse_color(color, key).map(Some),
+        _ => Ok(None),
     }
-    None
 }
 
-fn get_series_category_from_value(value: &serde_json::Value, key: &str) -> Option<SeriesCategory> {
+fn get_color_stops_from_value(value: &serde_json::Value, key: &str) -> Result<Vec<ColorStop>> {
+    let Some(arr) = value.get(key).and_then(|v| v.as_array()) else {
+        return Ok(vec![]);
+    };
+    let step = 1.0 / (arr.len().max(2) - 1) as f32;
+    let mut stops = vec![];
+    for (index, item) in arr.iter().enumerate() {
+        if item.is_string() {
+            stops.push((index as f32 * step, parse_color(item, key)?).into());
+        } else if let Some(offset) = get_f32_from_value(item, "offset")
+            && let Some(color) = get_color_from_value(item, "color")?
+        {
+            stops.push(ColorStop { offset, color });
+        }
+    }
+    Ok(stops)
+}
+
+/// Gets fill value from serde json.
+/// Accepts a color string (solid fill), or an object with a `type` of
+/// "linear" (`stops`, `angle`), "radial" (`stops`) or "pattern" (`pattern`
+/// "hatch"|"cross_hatch"|"dots", `color`, `background`, `size`). Stops are
+/// color strings spread evenly or `{"offset": 0.5, "color": "#fff"}` objects.
+pub(crate) fn get_fill_from_value(value: &serde_json::Value, key: &str) -> Result<Option<Fill>> {
+    let Some(v) = value.get(key) else {
+        return Ok(None);
+    };
+    if v.is_string() {
+        return Ok(Some(Fill::Solid(parse_color(v, key)?)));
+    }
+    let Some(fill_type) = get_string_from_value(v, "type") else {
+        return Ok(None);
+    };
+    let fill = match fill_type.as_str() {
+        "linear" => Fill::MultiStopGradient {
+            stops: get_color_stops_from_value(v, "stops")?,
+            angle: get_f32_from_value(v, "angle").unwrap_or_default(),
+        },
+        "radial" => Fill::RadialGradient {
+            stops: get_color_stops_from_value(v, "stops")?,
+        },
+        "pattern" => Fill::Pattern {
+            kind: match get_string_from_value(v, "pattern")
+                .unwrap_or_default()
+                .as_str()
+            {
+                "cross_hatch" => PatternKind::CrossHatch,
+                "dots" => PatternKind::Dots,
+                _ => PatternKind::Hatch,
+            },
+            color: get_color_from_value(v, "color")?.unwrap_or_else(Color::black),
+            background: get_color_from_value(v, "background")?.unwrap_or_default(),
+            size: get_f32_from_value(v, "size").unwrap_or(8.0),
+        },
+        _ => return Ok(None),
+    };
+    if let Fill::MultiStopGradient { stops, .. } | Fill::RadialGradient { stops } = &fill
+        && stops.is_empty()
+    {
+        return Ok(None);
+    }
+    Ok(Some(fill))
+}
+
+pub(crate) fn get_series_category_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Option<SeriesCategory> {
     if let Some(value) = value.get(key) {
         if value.is_null() {
             return None;
@@ -395,12 +588,17 @@ fn get_series_category_from_value(value: &serde_json::Value, key: &str) -> Optio
 /// Gets series symbol value from serde json.
 /// Accepts `null` (→ None marker), or an object with optional fields:
 /// `type` ("circle"|"rect"|"triangle"|"diamond"), `size`/`radius`, `color`.
-pub(crate) fn get_series_symbol_from_value(value: &serde_json::Value, key: &str) -> Option<Symbol> {
-    let v = value.get(key)?;
+pub(crate) fn get_series_symbol_from_value(
+    value: &serde_json::Value,
+    key: &str,
+) -> Result<Option<Symbol>> {
+    let Some(v) = value.get(key) else {
+        return Ok(None);
+    };
     if v.is_null() {
-        return Some(Symbol::None);
+        return Ok(Some(Symbol::None));
     }
-    let color = get_color_from_value(v, "color");
+    let color = get_color_from_value(v, "color")?;
     let size = get_f32_from_value(v, "size")
         .or_else(|| get_f32_from_value(v, "radius"))
         .unwrap_or(3.0);
@@ -409,12 +607,12 @@ pub(crate) fn get_series_symbol_from_value(value: &serde_json::Value, key: &str)
         .and_then(|t| t.as_str())
         .unwrap_or("circle")
         .to_lowercase();
-    Some(match symbol_type.as_str() {
+    Ok(Some(match symbol_type.as_str() {
         "rect" | "square" => Symbol::Rect(size, color),
         "triangle" => Symbol::Triangle(size, color),
         "diamond" => Symbol::Diamond(size, color),
         _ => Symbol::Circle(size, color),
-    })
+    }))
 }
 
 fn get_mark_lines(value: &serde_json::Value, key: &str) -> Vec<MarkLine> {
@@ -457,7 +655,7 @@ fn get_mark_points(value: &serde_json::Value, key: &str) -> Vec<MarkPoint> {
 fn get_series_colors_from_value(
     value: &serde_json::Value,
     key: &str,
-) -> Option<Vec<Option<Color>>> {
+) -> Result<Option<Vec<Option<Color>>>> {
     if let Some(data) = value.get(key)
         && let Some(arr) = data.as_array()
     {
@@ -465,22 +663,22 @@ fn get_series_colors_from_value(
         for item in arr.iter() {
             if item.is_null() {
                 colors.push(None);
-            } else if let Some(str) = item.as_str() {
-                colors.push(Some(str.into()))
+            } else {
+                colors.push(Some(parse_color(item, key)?))
             }
         }
-        return Some(colors);
+        return Ok(Some(colors));
     }
-    None
+    Ok(None)
 }
 
-fn get_series_from_value(value: &serde_json::Value) -> Option<Series> {
+fn get_series_from_value(value: &serde_json::Value) -> Result<Option<Series>> {
     let name = get_string_from_value(value, "name").unwrap_or_default();
     let data = get_f32_slice_from_value_support_nil(value, "data").unwrap_or_default();
     if data.is_empty() {
-        return None;
+        return Ok(None);
     }
-    Some(Series {
+    Ok(Some(Series {
         name,
         data,
         index: get_usize_from_value(value, "index"),
@@ -490,27 +688,28 @@ fn get_series_from_value(value: &serde_json::Value) -> Option<Series> {
         start_index: get_usize_from_value(value, "start_index").unwrap_or_default(),
         mark_lines: get_mark_lines(value, "mark_lines"),
         mark_points: get_mark_points(value, "mark_points"),
-        colors: get_series_colors_from_value(value, "colors"),
+        colors: get_series_colors_from_value(value, "colors")?,
+        fill: get_fill_from_value(value, "fill")?,
         stroke_dash_array: get_string_from_value(value, "stroke_dash_array"),
         stack: get_string_from_value(value, "stack"),
-    })
+    }))
 }
 
 /// Gets series list value from serde json.
-pub(crate) fn get_series_list_from_value(value: &serde_json::Value) -> Option<Vec<Series>> {
+pub(crate) fn get_series_list_from_value(value: &serde_json::Value) -> Result<Option<Vec<Series>>> {
     if let Some(data) = value.get("series_list")
         && let Some(arr) = data.as_array()
     {
         let mut series_list = vec![];
         for (index, item) in arr.iter().enumerate() {
-            if let Some(mut series) = get_series_from_value(item) {
+            if let Some(mut series) = get_series_from_value(item)? {
                 if series.index.is_none() {
                     series.index = Some(index)
                 }
                 series_list.push(series);
             }
         }
-        return Some(series_list);
+        return Ok(Some(series_list));
     }
-    None
+    Ok(None)
 }
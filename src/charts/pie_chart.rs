@@ -11,17 +11,92 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::label::{LabelBox, LabelPlacement, place_labels};
 use super::params::*;
 use super::theme::{get_default_theme_name, get_theme};
 use super::util::*;
 use crate::charts::measure_text_width_family;
 use core::f32;
 
+/// One ring of a nested pie chart, with its own slices and radius range.
+#[derive(Clone, Debug, Default)]
+pub struct PieRing {
+    /// Name of the ring; prefixes the tooltips of its slices.
+    pub name: String,
+    /// Slices of the ring, one per series.
+    pub series_list: Vec<Series>,
+    /// Outer radius of the ring. Zero splits the radius of the chart evenly
+    /// between the rings, the ring taking the band of its index.
+    pub radius: f32,
+    /// Inner radius of the ring; zero renders a full pie, or starts at the
+    /// band of its index when `radius` is zero too.
+    pub inner_radius: f32,
+    /// Position of the slice labels: `"inside"`, `"none"`, or outside with
+    /// leader lines by default. `None` follows `series_label_position`.
+    pub label_position: Option<String>,
+}
+
+fn get_pie_ring_list_from_value(value: &serde_json::Value) -> canvas::Result<Option<Vec<PieRing>>> {
+    let Some(arr) = value.get("rings").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+    let mut rings = vec![];
+    // Slices of all rings share one palette so every legend entry has its
+    // own color.
+    let mut color_index = 0;
+    for item in arr.iter() {
+        let mut series_list = get_series_list_from_value(item)?.unwrap_or_default();
+        let series_values = item.get("series_list").and_then(|v| v.as_array());
+        for (index, series) in series_list.iter_mut().enumerate() {
+            let has_index = series_values
+                .and_then(|arr| arr.get(index))
+                .is_some_and(|v| v.get("index").is_some());
+            if !has_index {
+                series.index = Some(color_index + index);
+            }
+        }
+        color_index += series_list.len();
+        rings.push(PieRing {
+            name: get_string_from_value(item, "name").unwrap_or_default(),
+            series_list,
+            radius: get_f32_from_value(item, "radius").unwrap_or_default(),
+            inner_radius: get_f32_from_value(item, "inner_radius").unwrap_or_default(),
+            label_position: get_string_from_value(item, "label_position"),
+        });
+    }
+    Ok(Some(rings))
+}
+
+/// A slice label with its leader line, drawn once the labels are placed.
+struct PieLabel {
+    text: String,
+    left: f32,
+    top: f32,
+    width: f32,
+    anchor: Point,
+    points: Vec<Point>,
+    color: Color,
+    class: Option<String>,
+    priority: f32,
+}
+
+/// The resolved layout of one ring of slices.
+struct PieRingLayout<'a> {
+    series_list: &'a [Series],
+    cx: f32,
+    cy: f32,
+    r: f32,
+    inner_radius: f32,
+    rose_type: bool,
+    label_position: Option<&'a str>,
+    tooltip_prefix: &'a str,
+}
+
 /// A pie / nightingale rose chart; each series contributes one value.
 #[derive(Clone, Debug, Default)]
 pub struct PieChart {
@@ -50,6 +125,11 @@ pub struct PieChart {
     // series
     /// Position of the slice labels.
     pub series_label_position: Option<String>,
+
+    /// Concentric rings, each with its own slices; when set they replace
+    /// `series_list` and render as plain pies (no rose), and every ring
+    /// forms its own legend group.
+    pub rings: Vec<PieRing>,
 }
 
 impl std::ops::Deref for PieChart {
@@ -90,6 +170,9 @@ impl PieChart {
         if let Some(border_radius) = get_f32_from_value(&value, "border_radius") {
             p.border_radius = Some(border_radius);
         }
+        if let Some(rings) = get_pie_ring_list_from_value(&value)? {
+            p.rings = rings;
+        }
         Ok(p)
     }
     /// Creates a pie chart with custom theme.
@@ -106,19 +189,29 @@ impl PieChart {
     pub fn new(series_list: Vec<Series>) -> PieChart {
         PieChart::new_with_theme(series_list, &get_default_theme_name())
     }
-    /// Converts pie chart to svg.
-    pub fn svg(&self) -> canvas::Result<String> {
-        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
-
-        let axis_top = self.render_header(&mut c);
-        if axis_top > 0.0 {
-            c = c.child(Box {
-                top: axis_top,
+    /// Renders the title and one legend group per ring, each group starting
+    /// on its own row.
+    fn render_ring_header(&self, c: &mut Canvas) -> f32 {
+        let mut base = self.base.clone();
+        base.legend_show = Some(false);
+        let title_height = base.render_header(c);
+        if !self.legend_show.unwrap_or(true) {
+            return title_height;
+        }
+        let mut legend_height = 0.0;
+        for ring in self.rings.iter() {
+            base.legend_show = Some(true);
+            base.series_list = ring.series_list.clone();
+            legend_height += base.render_legend(c.child(Box {
+                top: legend_height,
                 ..Default::default()
-            });
+            }));
         }
-
-        let values: Vec<f32> = self
+        title_height.max(legend_height)
+    }
+    /// Renders the slices of one ring.
+    fn render_ring(&self, c: &mut Canvas, ring: &PieRingLayout) {
+        let values: Vec<f32> = ring
             .series_list
             .iter()
             .map(|item| item.data_values().iter().sum())
@@ -144,31 +237,26 @@ impl PieChart {
         let mut delta = 360.0 / values.len() as f32;
         let mut half_delta = delta / 2.0;
         let mut start_angle = self.start_angle;
-        let mut radius_double = c.height();
-
-        if c.width() < radius_double {
-            radius_double = c.width();
-        }
-        radius_double *= 0.8;
-        let mut r = radius_double / 2.0;
-        if r > self.radius {
-            r = self.radius;
-        }
-
-        let cx = (c.width() - radius_double) / 2.0 + r;
-        let cy = (c.height() - radius_double) / 2.0 + r;
+        let PieRingLayout {
+            cx,
+            cy,
+            r,
+            inner_radius,
+            ..
+        } = *ring;
         let label_offset = 20.0;
         let mut series_label_formatter = self.series_label_formatter.clone();
         if series_label_formatter.is_empty() {
             series_label_formatter = "{a}: {d}".to_string();
         }
-        let rose_type = self.rose_type.unwrap_or_default();
+        let rose_type = ring.rose_type;
+        let mut labels = vec![];
 
         let mut prev_quadrant = u8::MAX;
         let mut prev_end_y = f32::MAX;
-        for (index, series) in self.series_list.iter().enumerate() {
+        for (index, series) in ring.series_list.iter().enumerate() {
             let value = values[index];
-            let mut cr = value / max * (r - self.inner_radius) + self.inner_radius;
+            let mut cr = value / max * (r - inner_radius) + inner_radius;
             let color = get_color(&self.series_colors, series.index.unwrap_or(index));
             // normal pie
             if !rose_type {
@@ -176,8 +264,8 @@ impl PieChart {
                 delta = value / sum * 360.0;
                 half_delta = delta / 2.0;
             }
-            if cr - self.inner_radius < 1.0 {
-                cr = self.inner_radius + 1.0;
+            if cr - inner_radius < 1.0 {
+                cr = inner_radius + 1.0;
             }
             let (anim_class, anim_style, fade_class) = if let Some(ref a) = self.animation {
                 (
@@ -189,11 +277,11 @@ impl PieChart {
                 (None, None, None)
             };
             let mut pie = Pie {
-                fill: color.into(),
+                fill: series.fill.clone().unwrap_or_else(|| color.into()),
                 cx,
                 cy,
                 r: cr,
-                ir: self.inner_radius,
+                ir: inner_radius,
                 start_angle,
                 delta,
                 class: anim_class,
@@ -205,14 +293,15 @@ impl PieChart {
             }
             let tooltip_text = if self.tooltip_show {
                 Some(
-                    LabelOption {
-                        series_name: series.name.clone(),
-                        value,
-                        percentage: value / sum,
-                        formatter: "{a}: {c} ({d})".to_string(),
-                        ..Default::default()
-                    }
-                    .format(),
+                    ring.tooltip_prefix.to_string()
+                        + &LabelOption {
+                            series_name: series.name.clone(),
+                            value,
+                            percentage: value / sum,
+                            formatter: "{a}: {c} ({d})".to_string(),
+                            ..Default::default()
+                        }
+                        .format(),
                 )
             } else {
                 None
@@ -228,13 +317,13 @@ impl PieChart {
 
             c.pie(pie);
 
-            let is_inside = self.series_label_position == Some("inside".to_string());
+            let is_inside = ring.label_position == Some("inside");
 
             let angle = start_angle + half_delta;
             // Hidden hover label, drawn immediately after the slice so the
             // adjacent-sibling CSS rule reveals it on hover.
             if let Some(text) = tooltip_text {
-                let p = get_pie_point(cx, cy, (cr + self.inner_radius) / 2.0, angle);
+                let p = get_pie_point(cx, cy, (cr + inner_radius) / 2.0, angle);
                 c.text(Text {
                     text,
                     class: Some("ct-tip".to_string()),
@@ -248,6 +337,10 @@ impl PieChart {
                     ..Default::default()
                 });
             }
+            if ring.label_position == Some("none") {
+                start_angle += delta;
+                continue;
+            }
             let label_option = LabelOption {
                 series_name: series.name.clone(),
                 value,
@@ -257,22 +350,26 @@ impl PieChart {
             };
             let label_text = label_option.format();
 
-            let label_margin = if is_inside {
+            let label_width = measure_text_width_family(
+                &self.font_family,
+                self.series_label_font_size,
+                &label_text,
+            )
+            .map(|b| b.width())
+            .ok();
+            let label = if is_inside {
                 let label_point = get_pie_point(cx, cy, 2. * cr / 3., angle);
-                let mut label_margin = Box {
-                    left: label_point.x,
+                PieLabel {
+                    text: label_text,
+                    left: label_point.x - label_width.unwrap_or_default() / 2.,
                     top: label_point.y,
-                    ..Default::default()
-                };
-                if let Ok(b) = measure_text_width_family(
-                    &self.font_family,
-                    self.series_label_font_size,
-                    &label_text,
-                ) {
-                    label_margin.left -= b.width() / 2.;
+                    width: label_width.unwrap_or_default(),
+                    anchor: label_point,
+                    points: vec![],
+                    color,
+                    class: fade_class,
+                    priority: value,
                 }
-
-                label_margin
             } else {
                 let mut points = vec![];
                 points.push(get_pie_point(cx, cy, cr, angle));
@@ -302,46 +399,179 @@ impl PieChart {
                 } else {
                     end.x += label_offset;
                 }
-                let mut label_margin = Box {
-                    left: end.x,
-                    top: end.y + 5.0,
-                    ..Default::default()
-                };
-
+                let mut left = end.x;
                 if is_left {
-                    if let Ok(b) = measure_text_width_family(
-                        &self.font_family,
-                        self.series_label_font_size,
-                        &label_text,
-                    ) {
-                        label_margin.left -= b.width();
+                    if let Some(width) = label_width {
+                        left -= width;
                     }
                 } else {
-                    label_margin.left += 3.0;
+                    left += 3.0;
                 }
 
                 points.push(end);
 
-                c.smooth_line(SmoothLine {
-                    color: Some(color),
+                PieLabel {
+                    text: label_text,
+                    left,
+                    top: end.y + 5.0,
+                    width: label_width.unwrap_or_default(),
+                    anchor: points[0],
                     points,
-                    symbol: None,
-                    class: fade_class.clone(),
-                    ..Default::default()
-                });
-                label_margin
+                    color,
+                    class: fade_class,
+                    priority: value,
+                }
             };
+            // Without an overlap strategy the labels are drawn right away,
+            // otherwise they are placed together once the ring is drawn.
+            if self.series_label_overlap == LabelOverlap::Allow {
+                self.render_label(c, label, LabelPlacement::default());
+            } else {
+                labels.push(label);
+            }
+
+            start_angle += delta;
+        }
+        let label_boxes: Vec<LabelBox> = labels
+            .iter()
+            .map(|label| LabelBox {
+                rect: Box {
+                    left: label.left,
+                    top: label.top - self.series_label_font_size,
+                    right: label.left + label.width,
+                    bottom: label.top,
+                },
+                anchor: label.anchor,
+                priority: label.priority,
+            })
+            .collect();
+        let placements = place_labels(&label_boxes, self.series_label_overlap);
+        for (label, placement) in labels.into_iter().zip(placements) {
+            self.render_label(c, label, placement);
+        }
+    }
+    /// Draws a slice label and its leader line moved by the placement.
+    fn render_label(&self, c: &mut Canvas, label: PieLabel, placement: LabelPlacement) {
+        if placement.hidden {
+            return;
+        }
+        let mut points = label.points;
+        if !points.is_empty() {
+            // the bend and the end of the leader line follow the label
+            for point in points.iter_mut().skip(1) {
+                point.y += placement.dy;
+            }
+            c.smooth_line(SmoothLine {
+                color: Some(label.color),
+                points,
+                symbol: None,
+                class: label.class.clone(),
+                ..Default::default()
+            });
+        } else if let Some((start, end)) = placement.leader_line {
+            c.line(Line {
+                color: Some(label.color),
+                left: start.x,
+                top: start.y,
+                right: end.x,
+                bottom: end.y,
+                ..Default::default()
+            });
+        }
+        c.child(Box {
+            left: label.left,
+            top: label.top + placement.dy,
+            ..Default::default()
+        })
+        .text(Text {
+            text: label.text,
+            font_family: Some(self.font_family.clone()),
+            font_size: Some(self.series_label_font_size),
+            font_color: Some(self.series_label_font_color),
+            class: label.class,
+            ..Default::default()
+        });
+    }
+    /// Converts pie chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
-            c.child(label_margin).text(Text {
-                text: label_text,
-                font_family: Some(self.font_family.clone()),
-                font_size: Some(self.series_label_font_size),
-                font_color: Some(self.series_label_font_color),
-                class: fade_class,
+        let axis_top = if self.rings.is_empty() {
+            self.render_header(&mut c)
+        } else {
+            self.render_ring_header(&mut c)
+        };
+        if axis_top > 0.0 {
+            c = c.child(Box {
+                top: axis_top,
                 ..Default::default()
             });
+        }
 
-            start_angle += delta;
+        let mut radius_double = c.height();
+
+        if c.width() < radius_double {
+            radius_double = c.width();
+        }
+        radius_double *= 0.8;
+        let mut r = radius_double / 2.0;
+        if r > self.radius {
+            r = self.radius;
+        }
+
+        let cx = (c.width() - radius_double) / 2.0 + r;
+        let cy = (c.height() - radius_double) / 2.0 + r;
+        let label_position = self.series_label_position.as_deref();
+        if self.rings.is_empty() {
+            self.render_ring(
+                &mut c,
+                &PieRingLayout {
+                    series_list: &self.series_list,
+                    cx,
+                    cy,
+                    r,
+                    inner_radius: self.inner_radius,
+                    rose_type: self.rose_type.unwrap_or_default(),
+                    label_position,
+                    tooltip_prefix: "",
+                },
+            );
+        } else {
+            // Rings keep their radii relative to each other and shrink
+            // together when the outermost one does not fit.
+            let max_radius = self.rings.iter().map(|item| item.radius).fold(r, f32::max);
+            let scale = r / max_radius;
+            let band = max_radius / self.rings.len() as f32;
+            for (index, item) in self.rings.iter().enumerate() {
+                let (radius, inner_radius) = if item.radius > 0.0 {
+                    (item.radius, item.inner_radius)
+                } else if item.inner_radius > 0.0 {
+                    (band * (index + 1) as f32, item.inner_radius)
+                } else {
+                    (band * (index + 1) as f32, band * index as f32)
+                };
+                let tooltip_prefix = if item.name.is_empty() {
+                    "".to_string()
+                } else {
+                    format!("{}: ", item.name)
+                };
+                self.render_ring(
+                    &mut c,
+                    &PieRingLayout {
+                        series_list: &item.series_list,
+                        cx,
+                        cy,
+                        r: radius * scale,
+                        inner_radius: inner_radius * scale,
+                        rose_type: false,
+                        label_position: item.label_position.as_deref().or(label_position),
+                        tooltip_prefix: &tooltip_prefix,
+                    },
+                );
+            }
         }
 
         let mut css = String::new();
@@ -376,6 +606,7 @@ impl PieChart {
 #[cfg(test)]
 mod tests {
     use super::PieChart;
+    use crate::LabelOverlap;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -544,6 +775,61 @@ mod tests {
         assert!(!off_svg.contains("ct-tip"));
     }
 
+    #[test]
+    fn pie_chart_fill() {
+        let chart = PieChart::from_json(
+            r##"{
+                "rose_type": false,
+                "inner_radius": 0,
+                "series_list": [
+                    {"name": "a", "data": [10], "fill": {"type": "radial", "stops": [{"offset": 0, "color": "#fff"}, {"offset": 1, "color": "#5470c6"}]}},
+                    {"name": "b", "data": [30], "fill": {"type": "radial", "stops": [{"offset": 0, "color": "#fff"}, {"offset": 1, "color": "#5470c6"}]}},
+                    {"name": "c", "data": [20], "fill": {"type": "pattern", "pattern": "cross_hatch"}}
+                ]
+            }"##,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // Both slices share one gradient centered on the pie.
+        assert_eq!(1, svg.matches("<radialGradient").count());
+        assert!(svg.contains(
+            r#"<radialGradient id="radial_FFFFFFFF_0_5470C6FF_100_294_194_150" cx="294" cy="194" r="150" gradientUnits="userSpaceOnUse">"#
+        ));
+        assert_eq!(
+            2,
+            svg.matches(r#"fill="url(#radial_FFFFFFFF_0_5470C6FF_100_294_194_150)""#)
+                .count()
+        );
+        assert_eq!(1, svg.matches("<pattern").count());
+        assert!(svg.contains(r#"fill="url(#pattern_cross_000000FF_00000000_80)""#));
+    }
+
+    #[test]
+    fn pie_chart_label_overlap() {
+        let new_chart = |overlap: LabelOverlap| {
+            let mut pie_chart = PieChart::new(
+                [400.0, 3.0, 2.0, 2.0, 1.0, 1.0]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (format!("slice {i}").as_str(), vec![*v]).into())
+                    .collect(),
+            );
+            pie_chart.rose_type = Some(false);
+            pie_chart.legend_show = Some(false);
+            pie_chart.series_label_position = Some("inside".to_string());
+            pie_chart.series_label_overlap = overlap;
+            pie_chart.svg().unwrap()
+        };
+        let count_labels = |svg: &str| svg.matches("slice ").count();
+        assert_eq!(6, count_labels(&new_chart(LabelOverlap::Allow)));
+        // The small slices crowd together, only some of them keep a label.
+        let shown = count_labels(&new_chart(LabelOverlap::Hide));
+        assert!(shown > 1 && shown < 6, "{shown} labels");
+        let svg = new_chart(LabelOverlap::LeaderLine);
+        assert_eq!(6, count_labels(&svg));
+        assert!(svg.contains("<line "), "missing leader line");
+    }
+
     // All-zero totals previously divided by zero, producing `LNaN,NaN` paths.
     #[test]
     fn all_zero_values_no_nan() {
@@ -554,4 +840,62 @@ mod tests {
         let svg = chart.svg().unwrap();
         assert!(!svg.contains("NaN"), "all-zero pie must not emit NaN");
     }
+
+    #[test]
+    fn pie_chart_rings() {
+        let chart = PieChart::from_json(
+            r###"{
+                "legend_show": true,
+                "tooltip_show": true,
+                "rings": [
+                    {"name": "Region", "radius": 70, "inner_radius": 0, "label_position": "inside",
+                     "series_list": [{"name": "Asia", "data": [60]}, {"name": "Europe", "data": [40]}]},
+                    {"name": "Country", "radius": 150, "inner_radius": 90,
+                     "series_list": [
+                        {"name": "China", "data": [35]}, {"name": "Japan", "data": [25]},
+                        {"name": "France", "data": [22]}, {"name": "Germany", "data": [18]}
+                    ]}
+                ]
+            }"###,
+        )
+        .unwrap();
+        assert_eq!(2, chart.rings.len());
+        assert_eq!(Some(2), chart.rings[1].series_list[0].index);
+        let svg = chart.svg().unwrap();
+        // Each ring has its own legend row.
+        assert!(svg.contains(r#"<text font-size="14" x="259.5" y="19""#));
+        assert!(svg.contains(r#"<text font-size="14" x="170.5" y="41""#));
+        // The outer ring continues the palette, with a leader line to its
+        // outside label.
+        assert!(svg.contains(r#"<title>Country: China: 35 (35%)</title>"#));
+        assert!(svg.contains(
+            r##"<path d="M423.3,159.2 C427.8 156.9, 436.4 151.2, 441.1 150.1 C445.9 149, 456.1 150.1, 461.1 150.1" stroke-width="1" fill="none" stroke="#FAC858"/>"##
+        ));
+        assert!(svg.contains("\nChina: 35%\n"));
+        // The inner ring is a full pie labelled inside.
+        assert!(svg.contains(r#"<path d="M300,222 L300,165.4"#));
+        assert!(svg.contains("\nAsia: 60%\n"));
+    }
+
+    #[test]
+    fn pie_chart_rings_default_radius() {
+        let chart = PieChart::from_json(
+            r###"{
+                "rings": [
+                    {"name": "Region",
+                     "series_list": [{"name": "Asia", "data": [60]}, {"name": "Europe", "data": [40]}]},
+                    {"name": "Country",
+                     "series_list": [{"name": "China", "data": [35]}, {"name": "Japan", "data": [65]}]}
+                ]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // The rings split the 150px radius: a full pie of 75px inside a
+        // ring from 75px to 150px.
+        assert!(svg.contains(r#"<path d="M294,194 L294,127 A8 8 0 0 1 296.6,119 A75 75 0 0 1"#));
+        assert!(svg.contains(
+            r#"<path d="M294,111 L294,52 A8 8 0 0 1 299.2,44.1 A150 150 0 0 1 418.4,277.9 A8 8 0 0 1 408.9,277.5 L361.1,242.8 A8 8 0 0 1 356.2,235.9 A75 75 0 0 0 296.6,119"#
+        ));
+    }
 }
@@ -0,0 +1,508 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Canvas;
+use super::base::{ChartBase, render_color_scheme};
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{get_default_theme_name, get_theme};
+use super::util::*;
+
+/// A bar chart in polar coordinates. By default every category of
+/// `x_axis_data` takes an equal sector and the bars grow outwards along the
+/// radius; with `radial` every category is a concentric ring and the value
+/// sets the sweep of its arc (radial progress rings).
+///
+/// Series sharing a `stack` are stacked, the others sit side by side.
+#[derive(Clone, Debug, Default)]
+pub struct PolarBarChart {
+    /// The shared chart options (size, series, title/legend, axes); exposed
+    /// directly on the chart through `Deref`, e.g. `chart.title_text`.
+    pub base: ChartBase,
+    /// Value axis configuration (min/max, split number and formatter).
+    pub y_axis_configs: Vec<YAxisConfig>,
+
+    /// Outer radius of the chart.
+    pub radius: f32,
+    /// Inner radius; the bars start from this ring.
+    pub inner_radius: f32,
+    /// Angle the first category (or the value axis) starts at, in degrees.
+    pub start_angle: f32,
+    /// Renders categories as concentric rings whose arcs sweep by value.
+    pub radial: bool,
+    /// Corner radius of the bars.
+    pub border_radius: f32,
+    /// Share (0–0.9) of every category left empty between the bars.
+    pub bar_category_gap: f32,
+}
+
+impl std::ops::Deref for PolarBarChart {
+    type Target = ChartBase;
+    fn deref(&self) -> &ChartBase {
+        &self.base
+    }
+}
+impl std::ops::DerefMut for PolarBarChart {
+    fn deref_mut(&mut self) -> &mut ChartBase {
+        &mut self.base
+    }
+}
+
+impl PolarBarChart {
+    fn fill_default(&mut self) {
+        self.radius = 150.0;
+        self.inner_radius = 20.0;
+        self.bar_category_gap = 0.2;
+    }
+    /// Creates a polar bar chart from json.
+    pub fn from_json(data: &str) -> canvas::Result<PolarBarChart> {
+        let mut p = PolarBarChart {
+            ..Default::default()
+        };
+        p.fill_default();
+        let value = p.base.fill_option(data, &mut p.y_axis_configs)?;
+        if let Some(radius) = get_f32_from_value(&value, "radius") {
+            p.radius = radius;
+        }
+        if let Some(inner_radius) = get_f32_from_value(&value, "inner_radius") {
+            p.inner_radius = inner_radius;
+        }
+        if let Some(start_angle) = get_f32_from_value(&value, "start_angle") {
+            p.start_angle = start_angle;
+        }
+        if let Some(radial) = get_bool_from_value(&value, "radial") {
+            p.radial = radial;
+        }
+        if let Some(border_radius) = get_f32_from_value(&value, "border_radius") {
+            p.border_radius = border_radius;
+        }
+        if let Some(bar_category_gap) = get_f32_from_value(&value, "bar_category_gap") {
+            p.bar_category_gap = bar_category_gap;
+        }
+        Ok(p)
+    }
+    /// Creates a polar bar chart with custom theme.
+    pub fn new_with_theme(
+        series_list: Vec<Series>,
+        x_axis_data: Vec<String>,
+        theme: &str,
+    ) -> PolarBarChart {
+        let mut p = PolarBarChart {
+            ..Default::default()
+        };
+        p.series_list = series_list;
+        p.x_axis_data = x_axis_data;
+        p.fill_default();
+        p.base.fill_theme(get_theme(theme), &mut p.y_axis_configs);
+        p
+    }
+    /// Creates a polar bar chart with default theme.
+    pub fn new(series_list: Vec<Series>, x_axis_data: Vec<String>) -> PolarBarChart {
+        PolarBarChart::new_with_theme(series_list, x_axis_data, &get_default_theme_name())
+    }
+    /// Converts polar bar chart to svg.
+    pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+
+        let axis_top = self.render_header(&mut c);
+        if axis_top > 0.0 {
+            c = c.child(Box {
+                top: axis_top,
+                ..Default::default()
+            });
+        }
+        let category_count = self
+            .series_list
+            .iter()
+            .map(|s| s.data.len() + s.start_index)
+            .max()
+            .unwrap_or_default()
+            .max(self.x_axis_data.len());
+        if category_count == 0 {
+            return c.svg();
+        }
+
+        let mut r = c.width().min(c.height()) * 0.8 / 2.0;
+        if r > self.radius {
+            r = self.radius;
+        }
+        let ir = self.inner_radius.clamp(0.0, r * 0.9);
+        let cx = c.width() / 2.0;
+        let cy = c.height() / 2.0;
+        let (axis_values, _) = self.get_y_axis_values(&self.y_axis_configs, 0)?;
+        // Share of the full value range a value covers, from the axis min.
+        let get_ratio = |value: f32| -> f32 {
+            let offset = axis_values.get_offset();
+            if offset <= 0.0 {
+                0.0
+            } else {
+                ((value - axis_values.min) / offset).clamp(0.0, 1.0)
+            }
+        };
+
+        // Series of one stack share a slot, the others get their own.
+        let mut slot_keys: Vec<String> = vec![];
+        let mut series_slots = Vec::with_capacity(self.series_list.len());
+        for (index, series) in self.series_list.iter().enumerate() {
+            let key = series
+                .stack
+                .clone()
+                .unwrap_or_else(|| format!("__series_{index}"));
+            let slot = if let Some(slot) = slot_keys.iter().position(|k| k == &key) {
+                slot
+            } else {
+                slot_keys.push(key);
+                slot_keys.len() - 1
+            };
+            series_slots.push(slot);
+        }
+        let slot_count = slot_keys.len().max(1) as f32;
+        let gap = self.bar_category_gap.clamp(0.0, 0.9);
+
+        // The category band: an angle for polar bars, a ring for radial bars.
+        let band = if self.radial {
+            (r - ir) / category_count as f32
+        } else {
+            360.0 / category_count as f32
+        };
+        let slot_size = band * (1.0 - gap) / slot_count;
+        let axis_color = self.x_axis_stroke_color;
+
+        // Grid: value rings with spokes for polar bars, a track per ring for
+        // radial bars.
+        if self.radial {
+            for index in 0..category_count {
+                let ring_ir = ir + band * index as f32;
+                c.circle(Circle {
+                    stroke_color: Some(self.grid_stroke_color),
+                    fill: Some(Color::transparent()),
+                    stroke_width: band * (1.0 - gap),
+                    cx,
+                    cy,
+                    r: ring_ir + band / 2.0,
+                    ..Default::default()
+                });
+                if let Some(name) = self.x_axis_data.get(index) {
+                    let p = get_pie_point(cx, cy, ring_ir + band / 2.0, self.start_angle);
+                    c.text(Text {
+                        text: name.clone(),
+                        font_family: Some(self.font_family.clone()),
+                        font_color: Some(self.x_axis_font_color),
+                        font_size: Some(self.x_axis_font_size),
+                        x: Some(p.x - 5.0),
+                        y: Some(p.y),
+                        text_anchor: Some("end".to_string()),
+                        dominant_baseline: Some("central".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
+        } else {
+            let split_count = axis_values.data.len().max(2) - 1;
+            for index in 0..=split_count {
+                let ring_r = ir + (r - ir) * index as f32 / split_count as f32;
+                c.circle(Circle {
+                    stroke_color: Some(self.grid_stroke_color),
+                    fill: Some(Color::transparent()),
+                    cx,
+                    cy,
+                    r: ring_r,
+                    ..Default::default()
+                });
+                // The axis labels are reversed (max first).
+                if let Some(label) = axis_values.data.get(split_count - index) {
+                    let p = get_pie_point(cx, cy, ring_r, self.start_angle);
+                    c.text(Text {
+                        text: label.clone(),
+                        font_family: Some(self.font_family.clone()),
+                        font_color: Some(self.x_axis_font_color),
+                        font_size: Some(self.x_axis_font_size * 0.85),
+                        x: Some(p.x + 3.0),
+                        y: Some(p.y),
+                        dominant_baseline: Some("central".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
+            for index in 0..category_count {
+                let angle = self.start_angle + band * index as f32;
+                let p1 = get_pie_point(cx, cy, ir, angle);
+                let p2 = get_pie_point(cx, cy, r, angle);
+                c.line(Line {
+                    color: Some(self.grid_stroke_color),
+                    left: p1.x,
+                    top: p1.y,
+                    right: p2.x,
+                    bottom: p2.y,
+                    ..Default::default()
+                });
+                if let Some(name) = self.x_axis_data.get(index) {
+                    let mid = angle + band / 2.0;
+                    let p = get_pie_point(cx, cy, r + self.x_axis_name_gap + 8.0, mid);
+                    let normalized = mid.rem_euclid(360.0);
+                    let anchor = if normalized < 10.0 || (170.0..190.0).contains(&normalized) {
+                        "middle"
+                    } else if normalized < 180.0 {
+                        "start"
+                    } else {
+                        "end"
+                    };
+                    c.text(Text {
+                        text: name.clone(),
+                        font_family: Some(self.font_family.clone()),
+                        font_color: Some(self.x_axis_font_color),
+                        font_size: Some(self.x_axis_font_size),
+                        x: Some(p.x),
+                        y: Some(p.y),
+                        text_anchor: Some(anchor.to_string()),
+                        dominant_baseline: Some("central".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
+            c.circle(Circle {
+                stroke_color: Some(axis_color),
+                fill: Some(Color::transparent()),
+                cx,
+                cy,
+                r,
+                ..Default::default()
+            });
+        }
+
+        // Bars, each stacked on the accumulated value of its slot.
+        let mut stack_acc = vec![vec![axis_values.min.max(0.0); category_count]; slot_keys.len()];
+        let mut labels: Vec<(Point, String)> = vec![];
+        let series_list: Vec<&Series> = self.series_list.iter().collect();
+        let stack_totals = self.get_stack_totals(&series_list, category_count);
+        for (series_index, series) in self.series_list.iter().enumerate() {
+            let slot = series_slots[series_index];
+            let totals = series
+                .stack
+                .as_ref()
+                .map(|stack| format!("{}_{}", stack, series.y_axis_index))
+                .and_then(|key| stack_totals.iter().find(|(k, _)| *k == key))
+                .map(|(_, v)| v);
+            let color = get_color(&self.series_colors, series.index.unwrap_or(series_index));
+            for (i, value) in series.data.iter().enumerate() {
+                let Some(value) = value else {
+                    continue;
+                };
+                let category = i + series.start_index;
+                let percentage = totals.map(|t| get_percentage(*value, t[category]));
+                let acc = stack_acc[slot][category];
+                let end = acc + percentage.map_or(*value, |p| p * 100.0).max(0.0);
+                stack_acc[slot][category] = end;
+                let slot_start =
+                    band * category as f32 + band * gap / 2.0 + slot_size * slot as f32;
+
+                let (pie_r, pie_ir, start_angle, delta, label_point) = if self.radial {
+                    let start_angle = self.start_angle + get_ratio(acc) * 360.0;
+                    let end_angle = self.start_angle + get_ratio(end) * 360.0;
+                    let ring_ir = ir + slot_start;
+                    let ring_r = ring_ir + slot_size;
+                    let label_point =
+                        get_pie_point(cx, cy, (ring_r + ring_ir) / 2.0, end_angle + 4.0);
+                    (
+                        ring_r,
+                        ring_ir,
+                        start_angle,
+                        end_angle - start_angle,
+                        label_point,
+                    )
+                } else {
+                    let start_angle = self.start_angle + slot_start;
+                    let bar_ir = ir + (r - ir) * get_ratio(acc);
+                    let bar_r = ir + (r - ir) * get_ratio(end);
+                    let label_point =
+                        get_pie_point(cx, cy, bar_r + 10.0, start_angle + slot_size / 2.0);
+                    (bar_r, bar_ir, start_angle, slot_size, label_point)
+                };
+                if delta <= 0.0 || pie_r <= pie_ir {
+                    continue;
+                }
+                let label_text = self.format_stack_label(series, category, *value, percentage);
+                let (class, style) = if let Some(ref a) = self.animation {
+                    (
+                        Some("pie-anim".to_string()),
+                        Some(format!("animation-delay:{}ms", category as u32 * a.delay)),
+                    )
+                } else {
+                    (None, None)
+                };
+                let mut pie = Pie {
+                    fill: color.into(),
+                    cx,
+                    cy,
+                    r: pie_r,
+                    ir: pie_ir,
+                    start_angle,
+                    delta,
+                    border_radius: self.border_radius,
+                    class,
+                    style,
+                    ..Default::default()
+                };
+                if self.tooltip_show {
+                    let category_name = self.x_axis_data.get(category).cloned().unwrap_or_default();
+                    pie.title = Some(format!("{} {}: {}", series.name, category_name, label_text));
+                    let trigger = match pie.class.take() {
+                        Some(c) => format!("{c} ct-trigger"),
+                        None => "ct-trigger".to_string(),
+                    };
+                    pie.class = Some(trigger);
+                }
+                let tooltip_text = pie.title.clone();
+                c.pie(pie);
+                // Hidden hover label right after the bar, revealed by the
+                // adjacent-sibling CSS rule.
+                if let Some(text) = tooltip_text {
+                    let p =
+                        get_pie_point(cx, cy, (pie_r + pie_ir) / 2.0, start_angle + delta / 2.0);
+                    c.text(Text {
+                        text,
+                        class: Some("ct-tip".to_string()),
+                        font_family: Some(self.font_family.clone()),
+                        font_color: Some(self.series_label_font_color),
+                        font_size: Some(self.series_label_font_size),
+                        x: Some(p.x),
+                        y: Some(p.y),
+                        text_anchor: Some("middle".to_string()),
+                        dominant_baseline: Some("central".to_string()),
+                        ..Default::default()
+                    });
+                }
+                if series.label_show {
+                    labels.push((label_point, label_text));
+                }
+            }
+        }
+        let fade_class = self.animation.as_ref().map(|_| "pie-fade".to_string());
+        for (point, text) in labels {
+            c.text(Text {
+                text,
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(self.series_label_font_size),
+                font_weight: self.series_label_font_weight.clone(),
+                x: Some(point.x),
+                y: Some(point.y),
+                text_anchor: Some("middle".to_string()),
+                dominant_baseline: Some("central".to_string()),
+                class: fade_class.clone(),
+                ..Default::default()
+            });
+        }
+
+        let mut css = String::new();
+        if let Some(ref anim) = self.animation {
+            css.push_str(&format!(
+                "@keyframes pie-grow{{from{{transform:scale(0)}}to{{transform:scale(1)}}}} \
+                 @keyframes pie-fade{{from{{opacity:0}}to{{opacity:1}}}} \
+                 .pie-anim{{transform-origin:{}px {}px;animation:pie-grow {}ms {} both}} \
+                 .pie-fade{{animation:pie-fade {}ms {} both}}",
+                format_float(cx + c.margin.left),
+                format_float(cy + c.margin.top),
+                anim.duration,
+                anim.safe_easing(),
+                anim.duration,
+                anim.safe_easing()
+            ));
+        }
+        if self.tooltip_show {
+            if !css.is_empty() {
+                css.push(' ');
+            }
+            css.push_str(TOOLTIP_STYLE);
+        }
+        if css.is_empty() {
+            c.svg()
+        } else {
+            c.svg_with_style(&css)
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::PolarBarChart;
+    use crate::Series;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn polar_bar_basic() {
+        let mut series_list = vec![
+            Series::new("A".to_string(), vec![3.0, 4.0, 5.0, 2.0, 6.0]),
+            Series::new("B".to_string(), vec![1.0, 2.0, 2.0, 3.0, 1.0]),
+            Series::new("C".to_string(), vec![2.0, 3.0, 1.0, 4.0, 2.0]),
+        ];
+        series_list[0].stack = Some("total".to_string());
+        series_list[0].label_show = true;
+        series_list[1].stack = Some("total".to_string());
+        let mut chart = PolarBarChart::new(
+            series_list,
+            vec![
+                "Mon".to_string(),
+                "Tue".to_string(),
+                "Wed".to_string(),
+                "Thu".to_string(),
+                "Fri".to_string(),
+            ],
+        );
+        chart.legend_show = Some(false);
+        assert_eq!(
+            include_str!("../../asset/polar_bar_chart/basic.svg"),
+            chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn polar_bar_radial() {
+        let chart = PolarBarChart::from_json(
+            r###"{
+                "radial": true,
+                "y_axis_configs": [{"axis_max": 100}],
+                "animation": {"duration": 600},
+                "series_list": [
+                    {"name": "Usage", "label_show": true, "data": [30.0, 60.0, 85.0]}
+                ],
+                "x_axis_data": ["CPU", "Mem", "Disk"]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // One track per category.
+        assert_eq!(
+            3,
+            svg.matches(r##"stroke-width="33.9" stroke="#E0E6F2""##)
+                .count()
+        );
+        assert!(svg.contains("\nDisk\n"));
+        // 85% of the outer ring, sweeping clockwise from the top.
+        assert!(svg.contains(
+            r#"<path d="M300,102 L300,68 A0 0 0 0 1 305,68.1 A143 143 0 0 1 305,353.9 A143 143 0 0 1 181.5,131.1"#
+        ));
+        assert!(svg.contains(r#"class="pie-anim" style="animation-delay:160ms""#));
+        assert!(svg.contains(r#"class="pie-fade""#));
+    }
+
+    #[test]
+    fn polar_bar_empty() {
+        let chart = PolarBarChart::from_json(r#"{"series_list": []}"#).unwrap();
+        assert!(!chart.svg().unwrap().contains("<path"));
+    }
+}
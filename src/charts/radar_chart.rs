@@ -11,29 +11,43 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::font::text_wrap_fit;
 use super::params::*;
 use super::theme::{get_default_theme_name, get_theme};
 use super::util::*;
 use crate::charts::measure_text_width_family;
 
-/// One radar axis: its name and maximum value.
+/// One radar axis: its name and value range.
 #[derive(Clone, Debug, Default)]
 pub struct RadarIndicator {
     /// Name of the indicator axis.
     pub name: String,
     /// Maximum value of the indicator.
     pub max: f32,
+    /// Minimum value of the indicator, at the center (default: 0).
+    pub min: f32,
 }
 impl From<(&str, f32)> for RadarIndicator {
     fn from(val: (&str, f32)) -> Self {
         RadarIndicator {
             name: val.0.to_string(),
             max: val.1,
+            ..Default::default()
+        }
+    }
+}
+impl From<(&str, f32, f32)> for RadarIndicator {
+    /// Creates an indicator from `(name, min, max)`.
+    fn from(val: (&str, f32, f32)) -> Self {
+        RadarIndicator {
+            name: val.0.to_string(),
+            min: val.1,
+            max: val.2,
         }
     }
 }
@@ -46,8 +60,9 @@ fn get_radar_indicator_list_from_value(value: &serde_json::Value) -> Option<Vec<
         for item in arr.iter() {
             let name = get_string_from_value(item, "name").unwrap_or_default();
             let max = get_f32_from_value(item, "max").unwrap_or_default();
+            let min = get_f32_from_value(item, "min").unwrap_or_default();
             if !name.is_empty() {
-                indicators.push(RadarIndicator { name, max });
+                indicators.push(RadarIndicator { name, max, min });
             }
         }
         return Some(indicators);
@@ -55,6 +70,42 @@ fn get_radar_indicator_list_from_value(value: &serde_json::Value) -> Option<Vec<
     None
 }
 
+/// Wraps an indicator name at the spaces to `max_width`, breaking words that
+/// are still too long with `text_wrap_fit`.
+fn wrap_indicator_name(
+    font_family: &str,
+    font_size: f32,
+    name: &str,
+    max_width: f32,
+) -> Vec<String> {
+    let measure = |text: &str| {
+        measure_text_width_family(font_family, font_size, text)
+            .map(|b| b.width())
+            .unwrap_or_default()
+    };
+    if max_width <= 0.0 || measure(name) <= max_width {
+        return vec![name.to_string()];
+    }
+    let mut lines: Vec<String> = vec![];
+    for word in name.split_whitespace() {
+        if let Some(line) = lines.last_mut() {
+            let text = format!("{line} {word}");
+            if measure(&text) <= max_width {
+                *line = text;
+                continue;
+            }
+        }
+        if measure(word) > max_width
+            && let Ok(parts) = text_wrap_fit(font_family, font_size, word, max_width)
+        {
+            lines.extend(parts);
+        } else {
+            lines.push(word.to_string());
+        }
+    }
+    lines
+}
+
 /// A radar chart plotting each series against a ring of indicators.
 #[derive(Clone, Debug, Default)]
 pub struct RadarChart {
@@ -68,8 +119,22 @@ pub struct RadarChart {
     pub y_axis_configs: Vec<YAxisConfig>,
 
     // grid
+    /// Draws the grid as circles instead of polygons.
+    pub circular: bool,
+    /// Number of grid rings (default: 5).
+    pub split_number: usize,
+    /// Draws the value of each ring along the first indicator.
+    pub axis_label_show: bool,
+    /// Formatter of the ring values, supporting `{c}` and `{t}`.
+    pub axis_formatter: String,
 
     // series
+    /// Fill opacity (0.0 to 1.0) of the series areas by series index; the
+    /// areas are filled when `series_fill` is set (default: 0.2).
+    pub fill_opacities: Vec<f32>,
+    /// Draws every series in a radar of its own, titled with the series
+    /// name, at most three per row.
+    pub small_multiples: bool,
 
     // indicators
     /// The indicator axes of the radar.
@@ -101,6 +166,24 @@ impl RadarChart {
         if data.get("series_fill").is_none() {
             r.series_fill = true;
         }
+        if let Some(circular) = get_bool_from_value(&data, "circular") {
+            r.circular = circular;
+        }
+        if let Some(split_number) = get_usize_from_value(&data, "split_number") {
+            r.split_number = split_number;
+        }
+        if let Some(axis_label_show) = get_bool_from_value(&data, "axis_label_show") {
+            r.axis_label_show = axis_label_show;
+        }
+        if let Some(axis_formatter) = get_string_from_value(&data, "axis_formatter") {
+            r.axis_formatter = axis_formatter;
+        }
+        if let Some(fill_opacities) = get_f32_slice_from_value(&data, "fill_opacities") {
+            r.fill_opacities = fill_opacities;
+        }
+        if let Some(small_multiples) = get_bool_from_value(&data, "small_multiples") {
+            r.small_multiples = small_multiples;
+        }
         Ok(r)
     }
     /// Creates a radar chart with custom theme.
@@ -125,6 +208,9 @@ impl RadarChart {
     }
     /// Converts bar chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         if self.indicators.len() < 3 {
             return Err(canvas::Error::Params {
                 message: "The count of indicator should be >= 3".to_string(),
@@ -157,13 +243,93 @@ impl RadarChart {
         }
 
         let offset = 40.0;
-        let r = c.height() / 2.0 - offset;
+        if !self.small_multiples {
+            let r = c.height() / 2.0 - offset;
+            let cx = c.width() / 2.0;
+            let cy = c.height() / 2.0;
+            let series_list: Vec<(usize, &Series)> = self.series_list.iter().enumerate().collect();
+            // the names may take the margin up to the edge of the chart
+            let bounds = (-self.margin.left, self.width - self.margin.left);
+            self.render_radar(&mut c, (cx, cy, r), bounds, &indicators, &series_list);
+            return c.svg();
+        }
+
+        // ── Small multiples ───────────────────────────────────────────────────
+        let count = self.series_list.len().max(1);
+        let columns = count.min(3);
+        let rows = count.div_ceil(columns);
+        let cell_width = c.width() / columns as f32;
+        let caption_height = self.series_label_font_size + 10.0;
+        let cell_height = c.height() / rows as f32;
+        let r = ((cell_width / 2.0).min((cell_height - caption_height) / 2.0) - offset).max(10.0);
+        // the caption and the radar are centered in the cell
+        let block_height = caption_height + r * 2.0 + offset * 1.5;
+        for (index, series) in self.series_list.iter().enumerate() {
+            let left = cell_width * (index % columns) as f32;
+            let top = cell_height * (index / columns) as f32
+                + ((cell_height - block_height) / 2.0).max(0.0);
+            let mut caption_x = left + cell_width / 2.0;
+            if let Ok(b) = measure_text_width_family(
+                &self.font_family,
+                self.series_label_font_size,
+                &series.name,
+            ) {
+                caption_x -= b.width() / 2.0;
+            }
+            c.text(Text {
+                text: series.name.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(self.series_label_font_size),
+                font_weight: Some("bold".to_string()),
+                dominant_baseline: Some("hanging".to_string()),
+                x: Some(caption_x),
+                y: Some(top),
+                ..Default::default()
+            });
+            let cx = left + cell_width / 2.0;
+            let cy = top + caption_height + r + offset / 2.0;
+            self.render_radar(
+                &mut c,
+                (cx, cy, r),
+                (left, left + cell_width),
+                &indicators,
+                &[(index, series)],
+            );
+        }
+        c.svg()
+    }
+
+    /// Renders the grid, the indicators and the series of one radar centered
+    /// at `(cx, cy)` with radius `r`; the indicator names are wrapped between
+    /// `min_x` and `max_x`.
+    fn render_radar(
+        &self,
+        c: &mut Canvas,
+        (cx, cy, r): (f32, f32, f32),
+        (min_x, max_x): (f32, f32),
+        indicators: &[RadarIndicator],
+        series_list: &[(usize, &Series)],
+    ) {
         let angle = 360.0 / indicators.len() as f32;
-        let cx = c.width() / 2.0;
-        let cy = c.height() / 2.0;
-        let round_count = 5;
+        let round_count = if self.split_number > 0 {
+            self.split_number
+        } else {
+            5
+        };
         for i in 1..=round_count {
             let ir = r / round_count as f32 * i as f32;
+            if self.circular {
+                c.circle(Circle {
+                    stroke_color: Some(self.grid_stroke_color),
+                    stroke_width: self.grid_stroke_width,
+                    cx,
+                    cy,
+                    r: ir,
+                    ..Default::default()
+                });
+                continue;
+            }
             let mut points = vec![];
             for index in 0..indicators.len() {
                 points.push(get_pie_point(cx, cy, ir, angle * index as f32));
@@ -180,45 +346,72 @@ impl RadarChart {
         for (index, item) in indicators.iter().enumerate() {
             let current_angle = angle * index as f32;
             let p = get_pie_point(cx, cy, r, current_angle);
-            let mut x = p.x;
-            let mut y = p.y;
             let x_offset = 3.0;
-            if let Ok(measurement) = measure_text_width_family(
+            let is_top = current_angle < 10.0 || (360.0 - current_angle) < 10.0;
+            let is_bottom = (current_angle - 180.0).abs() < 10.0;
+            let is_centered = current_angle == 0.0 || current_angle == 180.0;
+            // wrap the name to the space beside the indicator
+            let max_width = if is_centered {
+                max_x - min_x
+            } else if current_angle < 180.0 {
+                max_x - p.x - x_offset
+            } else {
+                p.x - min_x - x_offset
+            };
+            let lines = wrap_indicator_name(
                 &self.font_family,
                 self.series_label_font_size,
                 &item.name,
-            ) {
-                if current_angle < 10.0 || (360.0 - current_angle) < 10.0 {
-                    y -= 5.0;
-                } else if (current_angle - 180.0).abs() < 10.0 {
-                    y += measurement.height();
-                } else if p.y > cy {
-                    let x_angle = if current_angle <= 180.0 {
-                        current_angle - 90.0
+                max_width,
+            );
+            // the extra lines grow away from the radar
+            let line_count = lines.len() as f32;
+            for (line_index, line) in lines.iter().enumerate() {
+                let mut x = p.x;
+                let mut y = p.y;
+                if let Ok(measurement) =
+                    measure_text_width_family(&self.font_family, self.series_label_font_size, line)
+                {
+                    let line_offset = if is_top {
+                        line_index as f32 - (line_count - 1.0)
+                    } else if is_bottom {
+                        line_index as f32
                     } else {
-                        270.0 - current_angle
+                        line_index as f32 - (line_count - 1.0) / 2.0
                     };
-                    let y_offset = (x_angle / 180.0).cos() * (measurement.height() / 2.0);
-                    y += y_offset;
-                }
+                    y += line_offset * measurement.height();
+                    if is_top {
+                        y -= 5.0;
+                    } else if is_bottom {
+                        y += measurement.height();
+                    } else if p.y > cy {
+                        let x_angle = if current_angle <= 180.0 {
+                            current_angle - 90.0
+                        } else {
+                            270.0 - current_angle
+                        };
+                        let y_offset = (x_angle / 180.0).cos() * (measurement.height() / 2.0);
+                        y += y_offset;
+                    }
 
-                if current_angle == 0.0 || current_angle == 180.0 {
-                    x -= measurement.width() / 2.0;
-                } else if current_angle < 180.0 {
-                    x += x_offset;
-                } else {
-                    x -= measurement.width() + x_offset;
+                    if is_centered {
+                        x -= measurement.width() / 2.0;
+                    } else if current_angle < 180.0 {
+                        x += x_offset;
+                    } else {
+                        x -= measurement.width() + x_offset;
+                    }
                 }
+                c.text(Text {
+                    text: line.clone(),
+                    font_size: Some(self.series_label_font_size),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.series_label_font_color),
+                    x: Some(x),
+                    y: Some(y),
+                    ..Default::default()
+                });
             }
-            c.text(Text {
-                text: item.name.clone(),
-                font_size: Some(self.series_label_font_size),
-                font_family: Some(self.font_family.clone()),
-                font_color: Some(self.series_label_font_color),
-                x: Some(x),
-                y: Some(y),
-                ..Default::default()
-            });
             c.child(Box::default()).line(Line {
                 color: Some(self.grid_stroke_color),
                 stroke_width: self.grid_stroke_width,
@@ -231,19 +424,19 @@ impl RadarChart {
         }
 
         let mut label_positions = vec![];
-        for (index, series) in self.series_list.iter().enumerate() {
-            let color = get_color(&self.series_colors, series.index.unwrap_or(index));
+        for (index, series) in series_list.iter() {
+            let color = get_color(&self.series_colors, series.index.unwrap_or(*index));
             let mut points = vec![];
             let values = series.data_values();
             for (i, item) in indicators.iter().enumerate() {
                 if let Some(value) = values.get(i) {
-                    // Treat a missing point (`NIL_VALUE`) or a non-positive
-                    // indicator max as the center, so the sentinel `f32::MIN`
+                    // Treat a missing point (`NIL_VALUE`) or an empty
+                    // indicator range as the center, so the sentinel `f32::MIN`
                     // cannot leak into the polygon as a huge coordinate.
-                    let mut ir = if item.max <= 0.0 || *value == NIL_VALUE {
+                    let mut ir = if item.max <= item.min || *value == NIL_VALUE {
                         0.0
                     } else {
-                        *value / item.max * r
+                        (*value - item.min) / (item.max - item.min) * r
                     };
 
                     ir = ir.clamp(0.0, r);
@@ -257,7 +450,10 @@ impl RadarChart {
                 }
             }
             let fill = if self.series_fill {
-                Some(color.with_alpha(50))
+                let alpha = self.fill_opacities.get(*index).map_or(50, |opacity| {
+                    (opacity.clamp(0.0, 1.0) * 255.0).round() as u8
+                });
+                Some(color.with_alpha(alpha))
             } else {
                 None
             };
@@ -270,6 +466,29 @@ impl RadarChart {
                 ..Default::default()
             });
         }
+
+        // the ring values along the first indicator
+        if self.axis_label_show
+            && let Some(indicator) = indicators.first()
+        {
+            let font_size = self.series_label_font_size * 0.85;
+            for i in 0..=round_count {
+                let value =
+                    indicator.min + (indicator.max - indicator.min) / round_count as f32 * i as f32;
+                let ir = r / round_count as f32 * i as f32;
+                c.text(Text {
+                    text: format_value(value, &self.axis_formatter),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.x_axis_font_color),
+                    font_size: Some(font_size),
+                    dominant_baseline: Some("central".to_string()),
+                    x: Some(cx + 4.0),
+                    y: Some(cy - ir),
+                    ..Default::default()
+                });
+            }
+        }
+
         for item in label_positions.iter() {
             let mut dx = None;
             let text = item.1.clone();
@@ -292,8 +511,6 @@ impl RadarChart {
                 ..Default::default()
             });
         }
-
-        c.svg()
     }
 }
 
@@ -476,4 +693,62 @@ mod tests {
         }
         assert!(max_run < 7, "overflowed radar coordinate leaked into SVG");
     }
+
+    #[test]
+    fn radar_circular() {
+        let mut radar_chart = RadarChart::new(
+            vec![
+                Series::new(
+                    "Allocated Budget".to_string(),
+                    vec![4200.0, 3000.0, 20000.0, 35000.0, 50000.0, 18000.0],
+                ),
+                Series::new(
+                    "Actual Spending".to_string(),
+                    vec![5000.0, 14000.0, 28000.0, 26000.0, 42000.0, 21000.0],
+                ),
+            ],
+            vec![
+                ("Sales", 1000.0, 6500.0).into(),
+                ("Administration", 16000.0).into(),
+                ("Information Technology", 30000.0).into(),
+                ("Customer Support", 38000.0).into(),
+                ("Development", 52000.0).into(),
+                ("Marketing", 25000.0).into(),
+            ],
+        );
+        radar_chart.circular = true;
+        radar_chart.axis_label_show = true;
+        radar_chart.axis_formatter = "{t}".to_string();
+        radar_chart.fill_opacities = vec![0.6, 0.1];
+        let svg = radar_chart.svg().unwrap();
+        assert_eq!(include_str!("../../asset/radar_chart/circular.svg"), svg);
+    }
+
+    #[test]
+    fn radar_small_multiples_json() {
+        let radar_chart = RadarChart::from_json(
+            r###"{
+                "small_multiples": true,
+                "legend_show": false,
+                "indicators": [
+                    {"name": "Attack", "max": 100},
+                    {"name": "Defense", "max": 100},
+                    {"name": "Speed", "min": 20, "max": 120}
+                ],
+                "series_list": [
+                    {"name": "Knight", "data": [80, 90, 40]},
+                    {"name": "Archer", "data": [70, 30, 100]},
+                    {"name": "Mage", "data": [95, 20, 60]},
+                    {"name": "Thief", "data": [50, 40, 120]}
+                ]
+            }"###,
+        )
+        .unwrap();
+        let svg = radar_chart.svg().unwrap();
+        // one titled radar per series, with five grid rings each
+        assert!(svg.contains("\nKnight\n"));
+        assert!(svg.contains("\nThief\n"));
+        assert_eq!(4, svg.matches("\nSpeed\n").count());
+        assert_eq!(4 * (5 + 1), svg.matches("<path").count());
+    }
 }
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -88,6 +88,11 @@ struct LayoutLink {
     sy: f32,
     /// Vertical offset of the band within the target node.
     ty: f32,
+    /// Whether the link closes a cycle; such links are drawn as back-links
+    /// looping below the columns and ignored by the layering.
+    back: bool,
+    /// Vertical center of the lane a back-link runs through.
+    lane: f32,
 }
 
 /// Samples a cubic Bézier with horizontal tangents from `(x0, y0)` to
@@ -108,6 +113,19 @@ fn sample_link_edge(x0: f32, y0: f32, x1: f32, y1: f32, segments: usize, out: &m
     }
 }
 
+/// Samples the cubic Bézier a back-link turns around with: it leaves `(x, y0)`
+/// horizontally, bulges `bend * 0.75` pixels along the flow and comes back to
+/// `(x, y1)`.
+fn sample_back_link_turn(x: f32, y0: f32, y1: f32, bend: f32, out: &mut Vec<Point>) {
+    for i in 0..=LINK_SEGMENTS {
+        let t = i as f32 / LINK_SEGMENTS as f32;
+        let mt = 1.0 - t;
+        let x = x + 3.0 * mt * t * bend;
+        let y = (mt * mt * mt + 3.0 * mt * mt * t) * y0 + (3.0 * mt * t * t + t * t * t) * y1;
+        out.push((x, y).into());
+    }
+}
+
 // ── SankeyChart ────────────────────────────────────────────────────────────────
 
 /// A sankey diagram of directed flows between nodes.
@@ -137,6 +155,20 @@ pub struct SankeyChart {
     /// When `true`, each link is filled with a source→target color gradient
     /// instead of a translucent source color. Default: false.
     pub link_gradient: bool,
+    /// Adds the throughput of each node as a second label line.
+    pub node_value_show: bool,
+    /// Formatter of the node value line, supporting `{c}` and `{t}`.
+    pub node_value_formatter: String,
+    /// Draws a label in the middle of each link.
+    pub link_label_show: bool,
+    /// Formatter of the link labels: `{a}` is the source name, `{b}` the
+    /// target name, `{c}` / `{t}` the value and `{d}` its share of the total.
+    pub link_label_formatter: String,
+    /// Orders the nodes within a column by value instead of relaxing them
+    /// toward their neighbours.
+    pub node_sort: Option<NodeSort>,
+    /// Lays the flow out from top to bottom instead of left to right.
+    pub vertical: bool,
 }
 
 impl std::ops::Deref for SankeyChart {
@@ -206,12 +238,10 @@ impl SankeyChart {
                     if name.is_empty() {
                         return None;
                     }
-                    Some(SankeyNode {
-                        name,
-                        color: get_color_from_value(item, "color"),
-                    })
+                    let color = get_color_from_value(item, "color");
+                    Some(color.map(|color| SankeyNode { name, color }))
                 })
-                .collect();
+                .collect::<canvas::Result<_>>()?;
         }
         if let Some(arr) = value.get("links").and_then(|v| v.as_array()) {
             c.links = arr
@@ -245,6 +275,24 @@ impl SankeyChart {
         if let Some(b) = get_bool_from_value(&value, "link_gradient") {
             c.link_gradient = b;
         }
+        if let Some(b) = get_bool_from_value(&value, "node_value_show") {
+            c.node_value_show = b;
+        }
+        if let Some(s) = get_string_from_value(&value, "node_value_formatter") {
+            c.node_value_formatter = s;
+        }
+        if let Some(b) = get_bool_from_value(&value, "link_label_show") {
+            c.link_label_show = b;
+        }
+        if let Some(s) = get_string_from_value(&value, "link_label_formatter") {
+            c.link_label_formatter = s;
+        }
+        if let Some(sort) = get_node_sort_from_value(&value, "node_sort") {
+            c.node_sort = Some(sort);
+        }
+        if let Some(b) = get_bool_from_value(&value, "vertical") {
+            c.vertical = b;
+        }
         c.fill_default();
         Ok(c)
     }
@@ -286,6 +334,8 @@ impl SankeyChart {
                 width: 0.0,
                 sy: 0.0,
                 ty: 0.0,
+                back: false,
+                lane: 0.0,
             });
         }
         if links.is_empty() || names.is_empty() {
@@ -325,11 +375,20 @@ impl SankeyChart {
             node.value = in_sum[i].max(out_sum[i]);
         }
 
+        // Links closing a cycle become back-links, so the remaining flows are
+        // acyclic and can be layered.
+        let mut state = vec![0_u8; node_count];
+        for ni in 0..node_count {
+            if state[ni] == 0 {
+                mark_back_links(ni, &nodes, &mut links, &mut state);
+            }
+        }
+
         // Longest-path layering: every link pushes its target at least one
         // column to the right of its source (Bellman-Ford style relaxation).
         for _ in 0..node_count {
             let mut changed = false;
-            for link in &links {
+            for link in links.iter().filter(|link| !link.back) {
                 let want = nodes[link.source].layer + 1;
                 if nodes[link.target].layer < want {
                     nodes[link.target].layer = want;
@@ -357,7 +416,7 @@ impl SankeyChart {
                 let mut backward = vec![0usize; node_count];
                 for _ in 0..node_count {
                     let mut changed = false;
-                    for link in &links {
+                    for link in links.iter().filter(|link| !link.back) {
                         let want = backward[link.target] + 1;
                         if backward[link.source] < want {
                             backward[link.source] = want;
@@ -375,37 +434,36 @@ impl SankeyChart {
             _ => {}
         }
 
-        // Column x positions.
-        for node in nodes.iter_mut() {
-            node.x = if layer_count <= 1 {
-                0.0
-            } else {
-                node.layer as f32 / (layer_count - 1) as f32 * (cw - self.node_width)
-            };
-        }
-
         // Group node indices by layer (input order within a layer).
         let mut layers_nodes: Vec<Vec<usize>> = vec![vec![]; layer_count];
         for (i, node) in nodes.iter().enumerate() {
             layers_nodes[node.layer].push(i);
         }
 
-        // Vertical scale: pick the factor so the busiest column just fits.
+        // Vertical scale: pick the factor so the busiest column just fits,
+        // together with the lanes of the back-links below it.
+        let back_count = links.iter().filter(|link| link.back).count();
+        let back_sum: f32 = links
+            .iter()
+            .filter(|link| link.back)
+            .map(|link| link.value)
+            .sum();
         let mut ky = f32::MAX;
         for layer in &layers_nodes {
             let sum: f32 = layer.iter().map(|&i| nodes[i].value).sum();
             if sum <= 0.0 {
                 continue;
             }
-            let avail = ch - (layer.len() as f32 - 1.0) * self.node_gap;
+            let avail = ch - (layer.len() + back_count) as f32 * self.node_gap + self.node_gap;
             if avail <= 0.0 {
                 return None;
             }
-            ky = ky.min(avail / sum);
+            ky = ky.min(avail / (sum + back_sum));
         }
         if !ky.is_finite() || ky <= 0.0 {
             return None;
         }
+        let node_height = ch - back_sum * ky - back_count as f32 * self.node_gap;
 
         for node in nodes.iter_mut() {
             node.dy = node.value * ky;
@@ -414,31 +472,97 @@ impl SankeyChart {
             link.width = link.value * ky;
         }
 
-        // Initial stacking: index within column, then resolve overlaps.
-        for layer in &layers_nodes {
-            for (i, &ni) in layer.iter().enumerate() {
-                nodes[ni].y = i as f32;
-            }
+        // One lane per back-link, the shortest loops closest to the columns.
+        let mut back_links: Vec<usize> = (0..links.len()).filter(|&li| links[li].back).collect();
+        back_links.sort_by_key(|&li| {
+            nodes[links[li].source]
+                .layer
+                .saturating_sub(nodes[links[li].target].layer)
+        });
+        let mut lane_top = node_height + self.node_gap;
+        for li in back_links {
+            links[li].lane = lane_top + links[li].width / 2.0;
+            lane_top += links[li].width + self.node_gap;
         }
-        resolve_collisions(&mut nodes, &layers_nodes, ch, self.node_gap);
 
-        // Relaxation to reduce crossings (deterministic, fixed iterations).
-        let mut alpha = 1.0_f32;
-        for _ in 0..RELAX_ITERATIONS {
-            alpha *= 0.99;
-            relax(&mut nodes, &links, &layers_nodes, alpha, true);
-            resolve_collisions(&mut nodes, &layers_nodes, ch, self.node_gap);
-            relax(&mut nodes, &links, &layers_nodes, alpha, false);
-            resolve_collisions(&mut nodes, &layers_nodes, ch, self.node_gap);
+        // Column x positions, leaving room for the back-links turning around
+        // at both ends.
+        let back_padding = links
+            .iter()
+            .filter(|link| link.back)
+            .map(|link| get_back_link_bulge(link.width) + link.width / 2.0)
+            .fold(0.0_f32, f32::max);
+        for node in nodes.iter_mut() {
+            node.x = if layer_count <= 1 {
+                back_padding
+            } else {
+                back_padding
+                    + node.layer as f32 / (layer_count - 1) as f32
+                        * (cw - 2.0 * back_padding - self.node_width)
+            };
+        }
+
+        if let Some(sort) = self.node_sort {
+            // Sorted columns keep their order and are centered vertically.
+            for layer in layers_nodes.iter_mut() {
+                layer.sort_by(|&a, &b| {
+                    let ordering = nodes[a]
+                        .value
+                        .partial_cmp(&nodes[b].value)
+                        .unwrap_or(std::cmp::Ordering::Equal);
+                    if sort == NodeSort::Asc {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    }
+                });
+            }
+            for layer in &layers_nodes {
+                let extent: f32 = layer.iter().map(|&i| nodes[i].dy).sum::<f32>()
+                    + (layer.len() as f32 - 1.0) * self.node_gap;
+                let mut y = ((node_height - extent) / 2.0).max(0.0);
+                for &ni in layer {
+                    nodes[ni].y = y;
+                    y += nodes[ni].dy + self.node_gap;
+                }
+            }
+        } else {
+            // Initial stacking: index within column, then resolve overlaps.
+            for layer in &layers_nodes {
+                for (i, &ni) in layer.iter().enumerate() {
+                    nodes[ni].y = i as f32;
+                }
+            }
+            resolve_collisions(&mut nodes, &layers_nodes, node_height, self.node_gap);
+
+            // Relaxation to reduce crossings (deterministic, fixed iterations).
+            let mut alpha = 1.0_f32;
+            for _ in 0..RELAX_ITERATIONS {
+                alpha *= 0.99;
+                relax(&mut nodes, &links, &layers_nodes, alpha, true);
+                resolve_collisions(&mut nodes, &layers_nodes, node_height, self.node_gap);
+                relax(&mut nodes, &links, &layers_nodes, alpha, false);
+                resolve_collisions(&mut nodes, &layers_nodes, node_height, self.node_gap);
+            }
         }
 
         // Stack link bands within each node, ordered by the opposite endpoint's
         // vertical center so ribbons fan out without crossing inside a node.
-        for ni in 0..node_count {
-            let mut outs = nodes[ni].out_links.clone();
+        // Back-links leave and enter at the bottom, the innermost lane last.
+        let band_key = |link: &LayoutLink, other: usize| {
+            if link.back {
+                2.0 * ch - link.lane
+            } else {
+                center(&nodes[other])
+            }
+        };
+        let out_keys: Vec<f32> = links.iter().map(|l| band_key(l, l.target)).collect();
+        let in_keys: Vec<f32> = links.iter().map(|l| band_key(l, l.source)).collect();
+        for node in &nodes {
+            let mut outs = node.out_links.clone();
             outs.sort_by(|&a, &b| {
-                center(&nodes[links[a].target])
-                    .partial_cmp(&center(&nodes[links[b].target]))
+                out_keys[a]
+                    .partial_cmp(&out_keys[b])
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
             let mut sy = 0.0;
@@ -447,10 +571,10 @@ impl SankeyChart {
                 sy += links[li].width;
             }
 
-            let mut ins = nodes[ni].in_links.clone();
+            let mut ins = node.in_links.clone();
             ins.sort_by(|&a, &b| {
-                center(&nodes[links[a].source])
-                    .partial_cmp(&center(&nodes[links[b].source]))
+                in_keys[a]
+                    .partial_cmp(&in_keys[b])
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
             let mut ty = 0.0;
@@ -465,6 +589,9 @@ impl SankeyChart {
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -479,8 +606,28 @@ impl SankeyChart {
         if cw <= 0.0 || ch <= 0.0 {
             return c.svg();
         }
+        let font_size = self.series_label_font_size.max(10.0);
+        let font_color = self.series_label_font_color;
+        let line_height = font_size * 1.2;
+        let label_lines = if self.node_value_show { 2.0 } else { 1.0 };
 
-        let Some((nodes, links)) = self.layout(cw, ch) else {
+        // The layout runs along the flow (x) and across it (y); a vertical
+        // chart swaps both when drawing and keeps room for the labels below
+        // the last row of nodes.
+        let (flow_width, cross_height) = if self.vertical {
+            (ch - label_lines * line_height - 5.0, cw)
+        } else {
+            (cw, ch)
+        };
+        let to_point = |flow: f32, cross: f32| -> Point {
+            if self.vertical {
+                (cross, flow).into()
+            } else {
+                (flow, cross).into()
+            }
+        };
+
+        let Some((nodes, links)) = self.layout(flow_width, cross_height) else {
             return c.svg();
         };
 
@@ -503,6 +650,21 @@ impl SankeyChart {
             let top_s = source.y + link.sy;
             let top_t = target.y + link.ty;
 
+            if link.back {
+                // Turn around after the source, run back through the lane
+                // below the columns and turn into the target.
+                let bend = get_back_link_bulge(link.width) / 0.75;
+                let mut points: Vec<Point> = vec![];
+                sample_back_link_turn(x0, top_s + link.width / 2.0, link.lane, bend, &mut points);
+                sample_back_link_turn(x1, link.lane, top_t + link.width / 2.0, -bend, &mut points);
+                content.polyline(Polyline {
+                    color: Some(source.color.with_alpha(alpha)),
+                    stroke_width: link.width,
+                    points: points.iter().map(|p| to_point(p.x, p.y)).collect(),
+                });
+                continue;
+            }
+
             let mut points: Vec<Point> = vec![];
             // Top edge: source → target.
             sample_link_edge(x0, top_s, x1, top_t, LINK_SEGMENTS, &mut points);
@@ -522,8 +684,9 @@ impl SankeyChart {
                     Some(Fill::LinearGradient {
                         start_color: source.color.with_alpha(alpha),
                         end_color: target.color.with_alpha(alpha),
-                        // 90 degrees = left (source) to right (target).
-                        angle: 90.0,
+                        // 90 degrees = left (source) to right (target),
+                        // 0 degrees = top to bottom.
+                        angle: if self.vertical { 0.0 } else { 90.0 },
                     }),
                 )
             } else {
@@ -533,7 +696,7 @@ impl SankeyChart {
                 color: None,
                 fill,
                 gradient,
-                points,
+                points: points.iter().map(|p| to_point(p.x, p.y)).collect(),
                 class: self.animation.as_ref().map(|_| "sankey-anim".to_string()),
                 style: self
                     .animation
@@ -548,12 +711,14 @@ impl SankeyChart {
             if node.dy <= 0.0 {
                 continue;
             }
+            let top_left = to_point(node.x, node.y);
+            let size = to_point(self.node_width, node.dy);
             content.rect(Rect {
                 fill: Some(node.color.into()),
-                left: node.x,
-                top: node.y,
-                width: self.node_width,
-                height: node.dy,
+                left: top_left.x,
+                top: top_left.y,
+                width: size.x,
+                height: size.y,
                 class: self.animation.as_ref().map(|_| "sankey-anim".to_string()),
                 style: self
                     .animation
@@ -563,9 +728,42 @@ impl SankeyChart {
             });
         }
 
+        // ── Link labels ───────────────────────────────────────────────────────
+        if self.link_label_show {
+            for link in &links {
+                let source = &nodes[link.source];
+                let target = &nodes[link.target];
+                let text = LabelOption {
+                    series_name: source.name.clone(),
+                    category_name: target.name.clone(),
+                    value: link.value,
+                    percentage: get_percentage(link.value, grand_total),
+                    formatter: self.link_label_formatter.clone(),
+                }
+                .format();
+                let flow = (source.x + self.node_width + target.x) / 2.0;
+                let cross = if link.back {
+                    link.lane
+                } else {
+                    (source.y + link.sy + target.y + link.ty + link.width) / 2.0
+                };
+                let point = to_point(flow, cross);
+                content.text(Text {
+                    text,
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(font_color),
+                    font_size: Some(font_size),
+                    x: Some(point.x),
+                    y: Some(point.y),
+                    text_anchor: Some("middle".to_string()),
+                    dominant_baseline: Some("central".to_string()),
+                    class: self.animation.as_ref().map(|_| "sankey-fade".to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+
         // ── Node labels ───────────────────────────────────────────────────────
-        let font_size = self.series_label_font_size.max(10.0);
-        let font_color = self.series_label_font_color;
         for node in &nodes {
             if node.dy <= 0.0 {
                 continue;
@@ -586,37 +784,60 @@ impl SankeyChart {
                 }
                 .format()
             };
-            if text.is_empty() {
+            let mut lines = vec![];
+            if !text.is_empty() {
+                lines.push(text);
+            }
+            if self.node_value_show {
+                lines.push(format_value(node.value, &self.node_value_formatter));
+            }
+            if lines.is_empty() {
                 continue;
             }
-            // Label outside the node: to the right for left-half nodes, to the
-            // left otherwise, keeping text within the content area.
-            let mid_y = node.y + node.dy / 2.0;
-            let (x, anchor) = if node.x + self.node_width / 2.0 < cw / 2.0 {
-                (node.x + self.node_width + 5.0, "start")
-            } else {
-                (node.x - 5.0, "end")
-            };
-            content.text(Text {
-                text,
-                font_family: Some(self.font_family.clone()),
-                font_color: Some(font_color),
-                font_size: Some(font_size),
-                font_weight: self.series_label_font_weight.clone(),
-                x: Some(x),
-                y: Some(mid_y),
-                text_anchor: Some(anchor.to_string()),
-                dominant_baseline: Some("central".to_string()),
-                class: self.animation.as_ref().map(|_| "sankey-fade".to_string()),
-                ..Default::default()
-            });
+            // Label outside the node: below it in a vertical chart, otherwise
+            // to the right for left-half nodes and to the left for the others,
+            // keeping text within the content area.
+            let mid = node.y + node.dy / 2.0;
+            let after = node.x + self.node_width / 2.0 < flow_width / 2.0;
+            let count = lines.len() as f32;
+            for (i, text) in lines.into_iter().enumerate() {
+                let (x, y, anchor, baseline) = if self.vertical {
+                    let y = node.x + self.node_width + 5.0 + i as f32 * line_height;
+                    (mid, y, "middle", "hanging")
+                } else {
+                    let y = mid + (i as f32 - (count - 1.0) / 2.0) * line_height;
+                    if after {
+                        (node.x + self.node_width + 5.0, y, "start", "central")
+                    } else {
+                        (node.x - 5.0, y, "end", "central")
+                    }
+                };
+                content.text(Text {
+                    text,
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(font_color),
+                    font_size: Some(font_size),
+                    font_weight: self.series_label_font_weight.clone(),
+                    x: Some(x),
+                    y: Some(y),
+                    text_anchor: Some(anchor.to_string()),
+                    dominant_baseline: Some(baseline.to_string()),
+                    class: self.animation.as_ref().map(|_| "sankey-fade".to_string()),
+                    ..Default::default()
+                });
+            }
         }
 
         if let Some(ref anim) = self.animation {
+            let (scale, origin) = if self.vertical {
+                ("scaleY", "center top")
+            } else {
+                ("scaleX", "left center")
+            };
             let css = format!(
-                "@keyframes sankey-grow{{from{{transform:scaleX(0)}}to{{transform:scaleX(1)}}}} \
+                "@keyframes sankey-grow{{from{{transform:{scale}(0)}}to{{transform:{scale}(1)}}}} \
                  @keyframes sankey-fade{{from{{opacity:0}}to{{opacity:1}}}} \
-                 .sankey-anim{{transform-box:fill-box;transform-origin:left center;\
+                 .sankey-anim{{transform-box:fill-box;transform-origin:{origin};\
                  animation:sankey-grow {}ms {} both}} \
                  .sankey-fade{{animation:sankey-fade {}ms {} both}}",
                 anim.duration, anim.easing, anim.duration, anim.easing
@@ -633,6 +854,38 @@ fn center(node: &LayoutNode) -> f32 {
     node.y + node.dy / 2.0
 }
 
+/// How far the center line of a back-link of the given width bulges out of
+/// the node it turns around at.
+fn get_back_link_bulge(width: f32) -> f32 {
+    width / 2.0 + 6.0
+}
+
+/// Depth-first walk marking the links that point back to a node still on
+/// the stack, i.e. the links closing a cycle.  The walk keeps its own stack
+/// of `(node, next out link)` so long chains cannot overflow the call stack.
+fn mark_back_links(ni: usize, nodes: &[LayoutNode], links: &mut [LayoutLink], state: &mut [u8]) {
+    // 1: on the stack, 2: done
+    state[ni] = 1;
+    let mut stack = vec![(ni, 0)];
+    while let Some((node, index)) = stack.last_mut() {
+        let Some(&li) = nodes[*node].out_links.get(*index) else {
+            state[*node] = 2;
+            stack.pop();
+            continue;
+        };
+        *index += 1;
+        let target = links[li].target;
+        match state[target] {
+            0 => {
+                state[target] = 1;
+                stack.push((target, 0));
+            }
+            1 => links[li].back = true,
+            _ => {}
+        }
+    }
+}
+
 /// Pulls each node toward the weighted center of its connected neighbours.
 /// `use_targets` selects the right-to-left pass (align to outgoing targets);
 /// otherwise the left-to-right pass aligns to incoming sources.
@@ -663,7 +916,7 @@ fn relax(
                 } else {
                     let mut sum_v = 0.0;
                     let mut acc = 0.0;
-                    for &li in link_ids {
+                    for &li in link_ids.iter().filter(|&&li| !links[li].back) {
                         let other = if use_targets {
                             &nodes[links[li].target]
                         } else {
@@ -736,6 +989,7 @@ fn resolve_collisions(
 #[cfg(test)]
 mod tests {
     use super::{SankeyChart, SankeyLink, SankeyNode};
+    use crate::NodeSort;
     use pretty_assertions::assert_eq;
 
     fn make_links() -> Vec<SankeyLink> {
@@ -878,4 +1132,61 @@ mod tests {
         let chart = SankeyChart::new(vec![SankeyNode::from("a")], vec![]);
         assert!(chart.svg().unwrap().starts_with("<svg"));
     }
+
+    #[test]
+    fn sankey_chart_cycle() {
+        // Visitors move back and forth between pages, so three of the flows
+        // close a cycle and are drawn as back-links.
+        let mut chart = SankeyChart::new(
+            vec![],
+            vec![
+                ("Home", "Search", 60.0).into(),
+                ("Home", "Product", 40.0).into(),
+                ("Search", "Product", 45.0).into(),
+                ("Search", "Home", 15.0).into(),
+                ("Product", "Cart", 50.0).into(),
+                ("Product", "Search", 20.0).into(),
+                ("Cart", "Checkout", 35.0).into(),
+                ("Cart", "Product", 10.0).into(),
+            ],
+        );
+        chart.title_text = "Site Flow".to_string();
+        chart.node_value_show = true;
+        chart.link_label_show = true;
+        chart.node_sort = Some(NodeSort::Desc);
+        let svg = chart.svg().unwrap();
+        assert_eq!(3, svg.matches("<polyline").count());
+        assert_eq!(include_str!("../../asset/sankey_chart/cycle.svg"), svg);
+    }
+
+    #[test]
+    fn sankey_chart_vertical_json() {
+        let chart = SankeyChart::from_json(
+            r##"{
+                "width": 400,
+                "height": 600,
+                "vertical": true,
+                "node_sort": "asc",
+                "link_label_show": true,
+                "link_label_formatter": "{a}-{b}: {d}",
+                "node_value_show": true,
+                "node_value_formatter": "{c} GWh",
+                "links": [
+                    {"source": "a", "target": "b", "value": 30},
+                    {"source": "a", "target": "c", "value": 10},
+                    {"source": "b", "target": "a", "value": 5}
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert!(chart.vertical);
+        let svg = chart.svg().unwrap();
+        // nodes are horizontal bars, one node width high
+        assert_eq!(3, svg.matches(r#"height="16""#).count());
+        assert!(svg.contains("\na-b: 75%\n"));
+        assert!(svg.contains("\nb-a: 12.5%\n"));
+        assert!(svg.contains("\n40 GWh\n"));
+        assert!(svg.contains(r#"dominant-baseline="hanging" text-anchor="middle""#));
+        assert_eq!(1, svg.matches("<polyline").count());
+    }
 }
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::{ChartBase, get_y_axis_config};
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -153,24 +153,23 @@ impl ScatterChart {
             s.series_symbol_sizes = series_symbol_sizes;
         }
         if let Some(arr) = value.get("series_symbols").and_then(|v| v.as_array()) {
-            s.series_symbols = arr
-                .iter()
-                .filter_map(|item| {
-                    get_series_symbol_from_value(item, "type").or_else(|| {
-                        // also allow bare string: "circle", "triangle", etc.
-                        item.as_str().map(|t| match t {
-                            "rect" | "square" => Symbol::Rect(3.0, None),
-                            "triangle" => Symbol::Triangle(3.0, None),
-                            "diamond" => Symbol::Diamond(3.0, None),
-                            _ => Symbol::Circle(3.0, None),
-                        })
+            s.series_symbols = vec![];
+            for item in arr.iter() {
+                let symbol = get_series_symbol_from_value(item, "type")?.or_else(|| {
+                    // also allow bare string: "circle", "triangle", etc.
+                    item.as_str().map(|t| match t {
+                        "rect" | "square" => Symbol::Rect(3.0, None),
+                        "triangle" => Symbol::Triangle(3.0, None),
+                        "diamond" => Symbol::Diamond(3.0, None),
+                        _ => Symbol::Circle(3.0, None),
                     })
-                })
-                .collect();
+                });
+                s.series_symbols.extend(symbol);
+            }
         }
         let theme = get_string_from_value(&value, "theme").unwrap_or_default();
         if let Some(x_axis_config) = value.get("x_axis_config") {
-            s.x_axis_config = get_y_axis_config_from_value(get_theme(&theme), x_axis_config);
+            s.x_axis_config = get_y_axis_config_from_value(get_theme(&theme), x_axis_config)?;
         }
         Ok(s)
     }
@@ -201,6 +200,9 @@ impl ScatterChart {
     }
     /// Converts scatter chart to svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let mut x_axis_height = self.x_axis_height;
@@ -339,6 +341,7 @@ impl ScatterChart {
             ..Default::default()
         });
         let default_symbol_size = 10.0_f32;
+        let mut series_labels_list = vec![];
         for (index, series) in self.series_list.iter().enumerate() {
             let series_idx = series.index.unwrap_or(index);
             let mut color = get_color(&self.series_colors, series_idx);
@@ -358,6 +361,7 @@ impl ScatterChart {
                 DEFAULT_SYMBOLS[index % DEFAULT_SYMBOLS.len()].clone()
             };
 
+            let mut series_labels = vec![];
             for chunk in series.data_values().chunks(2) {
                 if chunk.len() != 2 {
                     continue;
@@ -375,8 +379,16 @@ impl ScatterChart {
                     None
                 };
                 render_scatter_symbol(&mut content_canvas, &symbol, cx, cy, size, color, title);
+                series_labels.push(SeriesLabel {
+                    point: (cx, cy - size).into(),
+                    text: format_series_value(chunk[1], &self.series_label_formatter),
+                });
+            }
+            if series.label_show {
+                series_labels_list.push(series_labels);
             }
         }
+        self.render_series_label(content_canvas, series_labels_list);
 
         if self.tooltip_show {
             c.svg_with_style(TOOLTIP_STYLE)
@@ -389,7 +401,7 @@ impl ScatterChart {
 #[cfg(test)]
 mod tests {
     use super::ScatterChart;
-    use crate::Align;
+    use crate::{Align, LabelOverlap};
     use pretty_assertions::assert_eq;
 
     fn make_scatter() -> ScatterChart {
@@ -441,6 +453,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scatter_chart_label() {
+        let new_svg = |overlap: LabelOverlap| {
+            let mut scatter_chart = make_scatter();
+            scatter_chart.series_list[0].label_show = true;
+            scatter_chart.series_label_overlap = overlap;
+            scatter_chart.svg().unwrap()
+        };
+        let svg = new_svg(LabelOverlap::Allow);
+        assert!(svg.contains("\n51.6\n"), "missing point label");
+        // one label per point of the first series
+        let count =
+            svg.matches("<text").count() - make_scatter().svg().unwrap().matches("<text").count();
+        assert_eq!(25, count);
+        let hidden = new_svg(LabelOverlap::Hide);
+        assert!(hidden.matches("<text").count() < svg.matches("<text").count());
+    }
+
     #[test]
     fn scatter_chart_no_axis() {
         let mut scatter_chart = make_scatter();
@@ -0,0 +1,398 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Canvas;
+use super::base::{ChartBase, render_color_scheme};
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{Theme, get_default_theme_name, get_theme};
+use super::util::*;
+
+static DEFAULT_SPARKLINE_WIDTH: f32 = 120.0;
+static DEFAULT_SPARKLINE_HEIGHT: f32 = 30.0;
+static SPARKLINE_POINT_RADIUS: f32 = 2.0;
+
+/// A word-sized trend chart: the first series drawn as a line or as bars
+/// without axes, grid, legend or title, filling exactly `width` x `height`.
+///
+/// It is also the cell renderer of [`crate::TableCellRenderer::Sparkline`].
+#[derive(Clone, Debug, Default)]
+pub struct SparklineChart {
+    /// The shared chart options (size, series, title/legend, axes); exposed
+    /// directly on the chart through `Deref`, e.g. `chart.title_text`.
+    pub base: ChartBase,
+    y_axis_configs: Vec<YAxisConfig>,
+
+    // ── Sparkline-specific fields ─────────────────────────────────────────────
+    /// Draws the values as a line (default) or as bars.
+    pub category: Option<SeriesCategory>,
+
+    /// Marks the minimum value with a dot in `min_point_color`.
+    pub min_point_show: bool,
+    /// Color of the minimum marker (default: red).
+    pub min_point_color: Color,
+
+    /// Marks the maximum value with a dot in `max_point_color`.
+    pub max_point_show: bool,
+    /// Color of the maximum marker (default: green).
+    pub max_point_color: Color,
+
+    /// Marks the last value with a dot in the series color.
+    pub last_point_show: bool,
+
+    /// A reference range, e.g. the normal range of a metric, drawn as a band
+    /// behind the values.
+    pub reference_band: Option<(f32, f32)>,
+    /// Color of the reference band.  Defaults to a translucent gray fitting
+    /// the light or dark theme.
+    pub reference_band_color: Color,
+}
+
+impl std::ops::Deref for SparklineChart {
+    type Target = ChartBase;
+    fn deref(&self) -> &ChartBase {
+        &self.base
+    }
+}
+impl std::ops::DerefMut for SparklineChart {
+    fn deref_mut(&mut self) -> &mut ChartBase {
+        &mut self.base
+    }
+}
+
+impl SparklineChart {
+    fn fill_default(&mut self, t: &Theme) {
+        if self.min_point_color.is_zero() {
+            self.min_point_color = t.sparkline_min_point_color;
+        }
+        if self.max_point_color.is_zero() {
+            self.max_point_color = t.sparkline_max_point_color;
+        }
+        if self.reference_band_color.is_zero() {
+            self.reference_band_color = t.sparkline_reference_band_color;
+        }
+    }
+
+    /// Creates a sparkline chart with the default theme.
+    pub fn new(data: Vec<f32>) -> SparklineChart {
+        SparklineChart::new_with_theme(data, &get_default_theme_name())
+    }
+
+    /// Creates a sparkline chart with the given theme.
+    pub fn new_with_theme(data: Vec<f32>, theme: &str) -> SparklineChart {
+        let mut c = SparklineChart {
+            ..Default::default()
+        };
+        c.series_list = vec![("", data).into()];
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.width = DEFAULT_SPARKLINE_WIDTH;
+        c.height = DEFAULT_SPARKLINE_HEIGHT;
+        c.margin = Box::default();
+        c.series_stroke_width = 1.5;
+        c.fill_default(&t);
+        c
+    }
+
+    /// Creates a sparkline chart from JSON options.
+    pub fn from_json(json: &str) -> canvas::Result<SparklineChart> {
+        let mut c = SparklineChart {
+            ..Default::default()
+        };
+        let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
+        c.fill_sparkline_option(&value)?;
+        Ok(c)
+    }
+
+    /// Fills the sparkline options of a parsed JSON value; unlike the other
+    /// charts the size, margin and stroke width default to a tiny layout.
+    fn fill_sparkline_option(&mut self, value: &serde_json::Value) -> canvas::Result<()> {
+        if value.get("width").is_none() {
+            self.width = DEFAULT_SPARKLINE_WIDTH;
+        }
+        if value.get("height").is_none() {
+            self.height = DEFAULT_SPARKLINE_HEIGHT;
+        }
+        if value.get("margin").is_none() {
+            self.margin = Box::default();
+        }
+        if value.get("series_stroke_width").is_none() {
+            self.series_stroke_width = 1.5;
+        }
+        if let Some(v) = get_series_category_from_value(value, "category") {
+            self.category = Some(v);
+        }
+        if let Some(v) = get_bool_from_value(value, "min_point_show") {
+            self.min_point_show = v;
+        }
+        if let Some(v) = get_color_from_value(value, "min_point_color")? {
+            self.min_point_color = v;
+        }
+        if let Some(v) = get_bool_from_value(value, "max_point_show") {
+            self.max_point_show = v;
+        }
+        if let Some(v) = get_color_from_value(value, "max_point_color")? {
+            self.max_point_color = v;
+        }
+        if let Some(v) = get_bool_from_value(value, "last_point_show") {
+            self.last_point_show = v;
+        }
+        if let Some(v) = get_f32_slice_from_value(value, "reference_band")
+            && v.len() == 2
+        {
+            self.reference_band = Some((v[0], v[1]));
+        }
+        if let Some(v) = get_color_from_value(value, "reference_band_color")? {
+            self.reference_band_color = v;
+        }
+        self.fill_default(&get_theme_from_value(value));
+        Ok(())
+    }
+
+    /// Renders the values into the `width` x `height` area at the origin of
+    /// the canvas.
+    pub(crate) fn render_values(
+        &self,
+        c: &mut Canvas,
+        values: &[Option<f32>],
+        width: f32,
+        height: f32,
+    ) {
+        let data: Vec<f32> = values.iter().flatten().copied().collect();
+        if data.is_empty() || width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let is_bar = self.category == Some(SeriesCategory::Bar);
+        let color = get_color(&self.series_colors, 0);
+
+        let mut min = data.iter().copied().fold(f32::INFINITY, f32::min);
+        let mut max = data.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        if is_bar {
+            // bars grow from zero
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+        let range = if max > min { max - min } else { 1.0 };
+
+        // keep the markers and the stroke inside the area
+        let has_point = self.min_point_show || self.max_point_show || self.last_point_show;
+        let padding = if has_point {
+            SPARKLINE_POINT_RADIUS + 0.5
+        } else {
+            self.series_stroke_width / 2.0
+        };
+        let inner_height = (height - padding * 2.0).max(1.0);
+        let get_y = |value: f32| -> f32 {
+            let ratio = if max > min {
+                (value - min) / range
+            } else {
+                0.5
+            };
+            padding + inner_height * (1.0 - ratio.clamp(0.0, 1.0))
+        };
+
+        if let Some((from, to)) = self.reference_band {
+            let top = get_y(from.max(to));
+            let bottom = get_y(from.min(to));
+            c.rect(Rect {
+                fill: Some(self.reference_band_color.into()),
+                left: 0.0,
+                top,
+                width,
+                height: bottom - top,
+                ..Default::default()
+            });
+        }
+
+        let count = values.len();
+        let mut points: Vec<Option<Point>> = vec![];
+        if is_bar {
+            let unit = width / count as f32;
+            let gap = if unit > 3.0 { 1.0 } else { 0.0 };
+            let zero_y = get_y(0.0);
+            for (i, value) in values.iter().enumerate() {
+                let Some(value) = value else {
+                    points.push(None);
+                    continue;
+                };
+                let y = get_y(*value);
+                c.rect(Rect {
+                    fill: Some(color.into()),
+                    left: i as f32 * unit + gap / 2.0,
+                    top: y.min(zero_y),
+                    width: unit - gap,
+                    height: (y - zero_y).abs().max(1.0),
+                    ..Default::default()
+                });
+                points.push(Some((i as f32 * unit + unit / 2.0, y).into()));
+            }
+        } else {
+            let unit = if count > 1 {
+                (width - padding * 2.0) / (count - 1) as f32
+            } else {
+                0.0
+            };
+            for (i, value) in values.iter().enumerate() {
+                points.push(value.map(|value| (padding + i as f32 * unit, get_y(value)).into()));
+            }
+            // null values split the line
+            for segment in points.split(|p| p.is_none()) {
+                let segment: Vec<Point> = segment.iter().flatten().copied().collect();
+                if segment.is_empty() {
+                    continue;
+                }
+                if self.series_fill && segment.len() > 1 {
+                    let bottom = height - padding;
+                    if self.series_smooth {
+                        c.smooth_line_fill(SmoothLineFill {
+                            fill: color.with_alpha(50).into(),
+                            points: segment.clone(),
+                            bottom,
+                            ..Default::default()
+                        });
+                    } else {
+                        c.straight_line_fill(StraightLineFill {
+                            fill: color.with_alpha(50).into(),
+                            points: segment.clone(),
+                            bottom,
+                            ..Default::default()
+                        });
+                    }
+                }
+                if self.series_smooth {
+                    c.smooth_line(SmoothLine {
+                        color: Some(color),
+                        points: segment,
+                        stroke_width: self.series_stroke_width,
+                        symbol: None,
+                        ..Default::default()
+                    });
+                } else {
+                    c.polyline(Polyline {
+                        color: Some(color),
+                        stroke_width: self.series_stroke_width,
+                        points: segment,
+                    });
+                }
+            }
+        }
+
+        // ── Markers ───────────────────────────────────────────────────────────
+        let mut marks = vec![];
+        let find_index = |f: fn(f32, f32) -> bool| -> Option<usize> {
+            let mut found: Option<(usize, f32)> = None;
+            for (i, value) in values.iter().enumerate() {
+                if let Some(value) = value
+                    && found.is_none_or(|(_, v)| f(*value, v))
+                {
+                    found = Some((i, *value));
+                }
+            }
+            found.map(|(i, _)| i)
+        };
+        if self.min_point_show
+            && let Some(index) = find_index(|a, b| a < b)
+        {
+            marks.push((index, self.min_point_color));
+        }
+        if self.max_point_show
+            && let Some(index) = find_index(|a, b| a > b)
+        {
+            marks.push((index, self.max_point_color));
+        }
+        if self.last_point_show
+            && let Some(index) = values.iter().rposition(|v| v.is_some())
+        {
+            marks.push((index, color));
+        }
+        for (index, mark_color) in marks {
+            if let Some(Some(point)) = points.get(index) {
+                c.circle(Circle {
+                    cx: point.x,
+                    cy: point.y,
+                    r: SPARKLINE_POINT_RADIUS,
+                    stroke_color: Some(mark_color),
+                    fill: Some(mark_color),
+                    stroke_width: 1.0,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    /// Renders the chart to an SVG string.
+    pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        self.render_background(c.child(Box::default()));
+        c.margin = self.margin.clone();
+
+        let mut body = c.child(Box::default());
+        let width = body.width();
+        let height = body.height();
+        let values = self
+            .series_list
+            .first()
+            .map(|s| s.data.clone())
+            .unwrap_or_default();
+        self.render_values(&mut body, &values, width, height);
+
+        c.svg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparklineChart;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sparkline_chart_basic() {
+        let mut chart = SparklineChart::new(vec![5.0, 8.0, 3.0, 9.0, 12.0, 7.0, 10.0, 6.0, 11.0]);
+        chart.min_point_show = true;
+        chart.max_point_show = true;
+        chart.last_point_show = true;
+        chart.reference_band = Some((6.0, 10.0));
+        chart.width = 100.0;
+        chart.height = 24.0;
+        assert_eq!(
+            include_str!("../../asset/sparkline_chart/basic.svg"),
+            chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn sparkline_chart_bar_json() {
+        let chart = SparklineChart::from_json(
+            r###"{
+                "category": "bar",
+                "width": 40,
+                "height": 12,
+                "series_list": [{"name": "", "data": [3, -2, null, 4]}]
+            }"###,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.starts_with(r#"<svg width="40" height="12" viewBox="0 0 40 12""#));
+        // three bars and the background, no axis or title
+        assert_eq!(4, svg.matches("<rect").count());
+        assert!(!svg.contains("<text"));
+        // the bars grow from zero in both directions
+        assert!(svg.contains(r#"<rect x="0.5" y="2.5" width="9" height="5.2""#));
+        assert!(svg.contains(r#"<rect x="10.5" y="7.7" width="9" height="3.5""#));
+    }
+}
@@ -0,0 +1,360 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::base::{ChartBase, render_color_scheme};
+use super::canvas;
+use super::color::*;
+use super::common::*;
+use super::component::*;
+use super::params::*;
+use super::theme::{Theme, get_default_theme_name, get_theme};
+use super::util::*;
+use super::{Canvas, SparklineChart};
+
+static DEFAULT_STAT_WIDTH: f32 = 260.0;
+static DEFAULT_STAT_HEIGHT: f32 = 140.0;
+
+/// A KPI card: the latest value of the first series in a big font, its
+/// delta against the previous period with an up/down arrow and an inline
+/// sparkline of the whole series.
+#[derive(Clone, Debug, Default)]
+pub struct StatChart {
+    /// The shared chart options (size, series, title/legend, axes); exposed
+    /// directly on the chart through `Deref`, e.g. `chart.title_text`.
+    pub base: ChartBase,
+    y_axis_configs: Vec<YAxisConfig>,
+
+    // ── Stat-specific fields ──────────────────────────────────────────────────
+    /// Value the delta is computed against.  Defaults to the second to last
+    /// data point of the series.
+    pub previous_value: Option<f32>,
+
+    /// Formatter of the big value, supporting `{c}` and `{t}`.
+    pub value_formatter: String,
+
+    /// Formatter of the delta: `{c}` / `{t}` is the absolute change and `{d}`
+    /// the change in percent (default: "{d}").
+    pub delta_formatter: String,
+
+    /// Color of an increase (default: green).
+    pub increase_color: Color,
+
+    /// Color of a decrease (default: red).
+    pub decrease_color: Color,
+
+    /// Swaps the increase and decrease colors, for metrics where lower is
+    /// better (e.g. costs or latency).
+    pub lower_is_better: bool,
+
+    /// Whether to draw the sparkline (default: true stored as None → true).
+    pub sparkline_show: Option<bool>,
+
+    /// Color of the sparkline.  Defaults to the first `series_colors` entry.
+    pub sparkline_color: Color,
+}
+
+impl std::ops::Deref for StatChart {
+    type Target = ChartBase;
+    fn deref(&self) -> &ChartBase {
+        &self.base
+    }
+}
+impl std::ops::DerefMut for StatChart {
+    fn deref_mut(&mut self) -> &mut ChartBase {
+        &mut self.base
+    }
+}
+
+impl StatChart {
+    fn fill_default(&mut self, t: &Theme) {
+        if self.legend_show.is_none() {
+            self.legend_show = Some(false);
+        }
+        if self.delta_formatter.is_empty() {
+            self.delta_formatter = PERCENTAGE_FORMAT_LABEL.to_string();
+        }
+        if self.increase_color.is_zero() {
+            self.increase_color = t.stat_increase_color;
+        }
+        if self.decrease_color.is_zero() {
+            self.decrease_color = t.stat_decrease_color;
+        }
+        if self.sparkline_color.is_zero() {
+            self.sparkline_color = get_color(&self.series_colors, 0);
+        }
+    }
+
+    /// Creates a stat chart with the default theme.
+    pub fn new(series_list: Vec<Series>) -> StatChart {
+        StatChart::new_with_theme(series_list, &get_default_theme_name())
+    }
+
+    /// Creates a stat chart with the given theme.
+    pub fn new_with_theme(series_list: Vec<Series>, theme: &str) -> StatChart {
+        let mut c = StatChart {
+            ..Default::default()
+        };
+        c.series_list = series_list;
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.width = DEFAULT_STAT_WIDTH;
+        c.height = DEFAULT_STAT_HEIGHT;
+        c.fill_default(&t);
+        c
+    }
+
+    /// Creates a stat chart from JSON options.
+    pub fn from_json(json: &str) -> canvas::Result<StatChart> {
+        let mut c = StatChart {
+            ..Default::default()
+        };
+        let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
+        // a card is much smaller than the theme's default chart size
+        if value.get("width").is_none() {
+            c.width = DEFAULT_STAT_WIDTH;
+        }
+        if value.get("height").is_none() {
+            c.height = DEFAULT_STAT_HEIGHT;
+        }
+        if let Some(v) = get_f32_from_value(&value, "previous_value") {
+            c.previous_value = Some(v);
+        }
+        if let Some(v) = get_string_from_value(&value, "value_formatter") {
+            c.value_formatter = v;
+        }
+        if let Some(v) = get_string_from_value(&value, "delta_formatter") {
+            c.delta_formatter = v;
+        }
+        if let Some(v) = get_color_from_value(&value, "increase_color")? {
+            c.increase_color = v;
+        }
+        if let Some(v) = get_color_from_value(&value, "decrease_color")? {
+            c.decrease_color = v;
+        }
+        if let Some(v) = get_bool_from_value(&value, "lower_is_better") {
+            c.lower_is_better = v;
+        }
+        if let Some(v) = get_bool_from_value(&value, "sparkline_show") {
+            c.sparkline_show = Some(v);
+        }
+        if let Some(v) = get_color_from_value(&value, "sparkline_color")? {
+            c.sparkline_color = v;
+        }
+        c.fill_default(&get_theme_from_value(&value));
+        Ok(c)
+    }
+
+    /// Returns the current value and the one it is compared against.
+    fn get_values(&self) -> (Option<f32>, Option<f32>) {
+        let values: Vec<f32> = self
+            .series_list
+            .first()
+            .map(|s| s.data.iter().flatten().copied().collect())
+            .unwrap_or_default();
+        let current = values.last().copied();
+        let previous = self.previous_value.or_else(|| {
+            values
+                .len()
+                .checked_sub(2)
+                .and_then(|index| values.get(index).copied())
+        });
+        (current, previous)
+    }
+
+    /// Formats the change from `previous` to `current`.
+    fn format_delta(&self, current: f32, previous: f32) -> String {
+        let delta = current - previous;
+        let percent = if previous != 0.0 {
+            format!("{}%", format_float(delta.abs() / previous.abs() * 100.0))
+        } else {
+            "-".to_string()
+        };
+        format_value(delta.abs(), &self.delta_formatter).replace(PERCENTAGE_FORMAT_LABEL, &percent)
+    }
+
+    /// Renders the chart to an SVG string.
+    pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        self.render_background(c.child(Box::default()));
+        c.margin = self.margin.clone();
+
+        let title_height = self.render_title(c.child(Box::default()));
+
+        let mut body = c.child(Box {
+            top: title_height,
+            ..Default::default()
+        });
+        let width = body.width();
+        let height = body.height();
+        let (current, previous) = self.get_values();
+
+        // ── Name ──────────────────────────────────────────────────────────────
+        let mut top = 0.0;
+        if let Some(series) = self.series_list.first()
+            && !series.name.is_empty()
+        {
+            body.text(Text {
+                text: series.name.clone(),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(self.series_label_font_size),
+                dominant_baseline: Some("hanging".to_string()),
+                x: Some(0.0),
+                y: Some(top),
+                ..Default::default()
+            });
+            top += self.series_label_font_size + 6.0;
+        }
+
+        // ── Value ─────────────────────────────────────────────────────────────
+        let value_font_size = (height * 0.3).clamp(18.0, 48.0);
+        let value_text = current
+            .map(|value| format_value(value, &self.value_formatter))
+            .unwrap_or_else(|| "-".to_string());
+        body.text(Text {
+            text: value_text,
+            font_family: Some(self.font_family.clone()),
+            font_color: Some(self.title_font_color),
+            font_size: Some(value_font_size),
+            font_weight: Some("bold".to_string()),
+            dominant_baseline: Some("hanging".to_string()),
+            x: Some(0.0),
+            y: Some(top),
+            ..Default::default()
+        });
+        top += value_font_size + 6.0;
+
+        // ── Delta ─────────────────────────────────────────────────────────────
+        if let (Some(current), Some(previous)) = (current, previous) {
+            let font_size = self.series_label_font_size;
+            let delta = current - previous;
+            let color = if delta == 0.0 {
+                self.series_label_font_color
+            } else if (delta > 0.0) != self.lower_is_better {
+                self.increase_color
+            } else {
+                self.decrease_color
+            };
+            let arrow_size = font_size * 0.7;
+            let mut text_x = 0.0;
+            if delta != 0.0 {
+                let arrow_top = top + (font_size - arrow_size) / 2.0;
+                let points = if delta > 0.0 {
+                    vec![
+                        (0.0, arrow_top + arrow_size).into(),
+                        (arrow_size / 2.0, arrow_top).into(),
+                        (arrow_size, arrow_top + arrow_size).into(),
+                    ]
+                } else {
+                    vec![
+                        (0.0, arrow_top).into(),
+                        (arrow_size, arrow_top).into(),
+                        (arrow_size / 2.0, arrow_top + arrow_size).into(),
+                    ]
+                };
+                body.polygon(Polygon {
+                    color: Some(color),
+                    fill: Some(color),
+                    points,
+                    ..Default::default()
+                });
+                text_x = arrow_size + 4.0;
+            }
+            body.text(Text {
+                text: self.format_delta(current, previous),
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(color),
+                font_size: Some(font_size),
+                dominant_baseline: Some("middle".to_string()),
+                x: Some(text_x),
+                y: Some(top + font_size / 2.0),
+                ..Default::default()
+            });
+            top += font_size + 8.0;
+        }
+
+        // ── Sparkline ─────────────────────────────────────────────────────────
+        let values = self
+            .series_list
+            .first()
+            .map(|s| s.data.clone())
+            .unwrap_or_default();
+        let sparkline_height = height - top;
+        if self.sparkline_show.unwrap_or(true)
+            && values.iter().flatten().count() > 1
+            && sparkline_height >= 10.0
+        {
+            let mut sparkline = SparklineChart::default();
+            sparkline.base = self.base.clone();
+            sparkline.last_point_show = true;
+            sparkline.series_colors = vec![self.sparkline_color];
+            sparkline.series_stroke_width = 1.5;
+            sparkline.series_fill = true;
+            sparkline.render_values(
+                &mut body.child(Box {
+                    top,
+                    ..Default::default()
+                }),
+                &values,
+                width,
+                sparkline_height,
+            );
+        }
+
+        c.svg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatChart;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn stat_chart_basic() {
+        let mut chart = StatChart::new(vec![
+            (
+                "Revenue",
+                vec![120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0, 250.0],
+            )
+                .into(),
+        ]);
+        chart.value_formatter = "${t}".to_string();
+        assert_eq!(
+            include_str!("../../asset/stat_chart/basic.svg"),
+            chart.svg().unwrap()
+        );
+    }
+
+    #[test]
+    fn stat_chart_json() {
+        let chart = StatChart::from_json(
+            r##"{
+                "lower_is_better": true,
+                "previous_value": 300,
+                "delta_formatter": "{c} ms ({d})",
+                "sparkline_show": false,
+                "series_list": [{"name": "Latency", "data": [320, 280, 240]}]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(260.0, chart.width);
+        let svg = chart.svg().unwrap();
+        // a decrease is good, so it takes the increase color
+        assert!(svg.contains(r##"fill="#91CC75""##));
+        assert!(svg.contains("60 ms (20%)"));
+        assert!(!svg.contains("<polyline"));
+    }
+}
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -78,29 +78,56 @@ fn lighten(c: Color, factor: f32) -> Color {
     }
 }
 
-pub(crate) fn parse_node(value: &serde_json::Value) -> Option<SunburstData> {
+pub(crate) fn parse_node(value: &serde_json::Value) -> canvas::Result<Option<SunburstData>> {
     let name = get_string_from_value(value, "name").unwrap_or_default();
     let val = get_f32_from_value(value, "value").unwrap_or_default();
-    let color = get_color_from_value(value, "color");
+    let color = get_color_from_value(value, "color")?;
     let mut children = vec![];
     if let Some(arr) = value.get("children").and_then(|v| v.as_array()) {
         for item in arr.iter() {
-            if let Some(node) = parse_node(item) {
+            if let Some(node) = parse_node(item)? {
                 children.push(node);
             }
         }
     }
     if name.is_empty() && children.is_empty() && val == 0.0 {
-        return None;
+        return Ok(None);
     }
-    Some(SunburstData {
+    Ok(Some(SunburstData {
         name,
         value: val,
         children,
         color,
+    }))
+}
+
+pub(crate) fn parse_level(value: &serde_json::Value) -> canvas::Result<SunburstLevel> {
+    Ok(SunburstLevel {
+        color_fade: get_f32_from_value(value, "color_fade"),
+        border_color: get_color_from_value(value, "border_color")?,
+        border_width: get_f32_from_value(value, "border_width"),
+        label_show: get_bool_from_value(value, "label_show"),
     })
 }
 
+/// Style of one level of the hierarchy, indexed by the depth of its nodes
+/// (the top-level nodes are level 0, also in a drill-down view).
+#[derive(Clone, Debug, Default)]
+pub struct SunburstLevel {
+    /// How far derived colors of the level are faded toward white, from 0.0
+    /// to 0.85. Defaults to 0.16 per level.
+    pub color_fade: Option<f32>,
+    /// Border color of the arcs. Defaults to the background color.
+    pub border_color: Option<Color>,
+    /// Border width of the arcs in pixels.
+    pub border_width: Option<f32>,
+    /// Whether the arcs of the level are labelled (default: true).
+    pub label_show: Option<bool>,
+}
+
+/// Alpha of the arcs off the highlighted path.
+const DIMMED_ALPHA: u8 = 60;
+
 /// Geometry and totals shared by every ring while drawing the hierarchy.
 struct RingLayout<'a> {
     cx: f32,
@@ -108,6 +135,10 @@ struct RingLayout<'a> {
     grand_total: f32,
     /// Pixel thickness of each ring level, indexed by depth.
     thicknesses: &'a [f32],
+    /// Level of the innermost ring in the full hierarchy.
+    level_offset: usize,
+    /// Palette index of the first top-level node drawn.
+    color_offset: usize,
 }
 
 // ── SunburstChart ──────────────────────────────────────────────────────────────
@@ -133,6 +164,18 @@ pub struct SunburstChart {
     /// default to `1.0`. E.g. `[2.0]` makes the innermost ring twice as thick
     /// as the others. Empty (default) splits all rings equally.
     pub level_thickness: Vec<f32>,
+    /// Names from a top-level node down to the node whose sub-tree is drawn
+    /// (drill-down), with a breadcrumb of the path above it. Empty (default)
+    /// draws the whole hierarchy.
+    pub root_path: Vec<String>,
+    /// Label rotation (default: along the arc). Labels that do not fit
+    /// their arc are hidden.
+    pub label_rotate: LabelRotate,
+    /// Per-level styles, indexed by depth.
+    pub levels: Vec<SunburstLevel>,
+    /// Names of a path from a top-level node; arcs that are neither on the
+    /// path nor below its last node are dimmed.
+    pub highlight_path: Vec<String>,
 }
 
 impl std::ops::Deref for SunburstChart {
@@ -170,7 +213,11 @@ impl SunburstChart {
         };
         let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
         if let Some(arr) = value.get("series_data").and_then(|v| v.as_array()) {
-            c.series_data = arr.iter().filter_map(parse_node).collect();
+            c.series_data = arr
+                .iter()
+                .map(parse_node)
+                .filter_map(Result::transpose)
+                .collect::<canvas::Result<_>>()?;
         }
         if let Some(v) = get_f32_from_value(&value, "radius") {
             c.radius = v;
@@ -184,6 +231,18 @@ impl SunburstChart {
         if let Some(v) = get_f32_slice_from_value(&value, "level_thickness") {
             c.level_thickness = v;
         }
+        if let Some(v) = get_string_slice_from_value(&value, "root_path") {
+            c.root_path = v;
+        }
+        if let Some(v) = get_label_rotate_from_value(&value, "label_rotate")? {
+            c.label_rotate = v;
+        }
+        if let Some(arr) = value.get("levels").and_then(|v| v.as_array()) {
+            c.levels = arr.iter().map(parse_level).collect::<canvas::Result<_>>()?;
+        }
+        if let Some(v) = get_string_slice_from_value(&value, "highlight_path") {
+            c.highlight_path = v;
+        }
         Ok(c)
     }
 
@@ -200,6 +259,8 @@ impl SunburstChart {
         depth: usize,
         inner_r: f32,
         base_color: Color,
+        // Names of the ancestors of `nodes`.
+        path: &[String],
         l: &RingLayout,
     ) {
         if total <= 0.0 {
@@ -215,14 +276,27 @@ impl SunburstChart {
                 continue;
             }
             let delta = node_total / total * span;
+            let level = depth + l.level_offset;
+            let level_style = self.levels.get(level);
             let color = node.color.unwrap_or_else(|| {
-                if depth == 0 {
-                    get_color(&self.series_colors, i)
+                if level == 0 {
+                    get_color(&self.series_colors, l.color_offset + i)
                 } else {
-                    lighten(base_color, depth as f32 * 0.16)
+                    let fade = level_style
+                        .and_then(|s| s.color_fade)
+                        .unwrap_or(level as f32 * 0.16);
+                    lighten(base_color, fade)
                 }
             });
             let outer_r = inner_r + thickness;
+            let mut node_path = path.to_vec();
+            node_path.push(node.name.clone());
+            // on the path or below its last node
+            let dimmed = !self
+                .highlight_path
+                .iter()
+                .zip(node_path.iter())
+                .all(|(a, b)| a == b);
 
             // Tiny slices would render as degenerate arcs; skip drawing but
             // still consume their angle so the layout stays consistent.
@@ -236,8 +310,18 @@ impl SunburstChart {
                     (None, None)
                 };
                 c.pie(Pie {
-                    fill: color.into(),
-                    stroke_color: Some(self.background_color),
+                    fill: if dimmed {
+                        color.with_alpha(DIMMED_ALPHA).into()
+                    } else {
+                        color.into()
+                    },
+                    // a full circle would show its seam at the start angle
+                    stroke_color: (delta < 360.0).then(|| {
+                        level_style
+                            .and_then(|s| s.border_color)
+                            .unwrap_or(self.background_color)
+                    }),
+                    stroke_width: level_style.and_then(|s| s.border_width),
                     cx: l.cx,
                     cy: l.cy,
                     r: outer_r,
@@ -249,13 +333,15 @@ impl SunburstChart {
                     style: anim_style,
                     ..Default::default()
                 });
-                self.draw_label(
-                    c, node, angle, delta, inner_r, thickness, color, node_total, l,
-                );
+                if level_style.and_then(|s| s.label_show).unwrap_or(true) {
+                    self.draw_label(
+                        c, node, angle, delta, inner_r, thickness, color, node_total, dimmed, l,
+                    );
+                }
             }
 
             if !node.children.is_empty() {
-                let child_base = if depth == 0 { color } else { base_color };
+                let child_base = if level == 0 { color } else { base_color };
                 self.draw_nodes(
                     c,
                     &node.children,
@@ -265,6 +351,7 @@ impl SunburstChart {
                     depth + 1,
                     outer_r,
                     child_base,
+                    &node_path,
                     l,
                 );
             }
@@ -283,6 +370,7 @@ impl SunburstChart {
         thickness: f32,
         fill: Color,
         value: f32,
+        dimmed: bool,
         l: &RingLayout,
     ) {
         // {a}/{b}: node name, {c}: value, {d}: percentage of the grand total.
@@ -307,31 +395,55 @@ impl SunburstChart {
         }
         let font_size = self.series_label_font_size.max(10.0);
         let mid_angle = start_angle + delta / 2.0;
-        let mid_r = inner_r + thickness / 2.0;
-        let point = get_pie_point(l.cx, l.cy, mid_r, mid_angle);
+        let mut mid_r = inner_r + thickness / 2.0;
 
         let name_w = measure_text_width_family(&self.font_family, font_size, &text)
             .map(|b| b.width())
             .unwrap_or(text.len() as f32 * font_size * 0.6);
-        // Labels are drawn tangentially (along the arc). Skip the label when
-        // the slice's arc length cannot hold the text — this keeps the text
-        // inside its own ring band instead of overflowing onto neighbours.
-        let arc_len = delta.to_radians() * mid_r;
-        if name_w + 6.0 > arc_len {
-            return;
-        }
-
-        // Tangential rotation: chart angle 0 points up and increases clockwise,
-        // which matches the SVG `rotate` convention, so the tangent at
-        // `mid_angle` is `rotate(mid_angle)`. Flip text on the bottom half so it
-        // stays upright rather than upside-down.
         let mut deg = mid_angle % 360.0;
         if deg < 0.0 {
             deg += 360.0;
         }
-        if deg > 90.0 && deg < 270.0 {
-            deg -= 180.0;
+        if inner_r <= 0.0 && delta >= 360.0 {
+            // A full disc (the root of a drill-down) is labelled in its center.
+            if name_w + 6.0 > thickness * 2.0 {
+                return;
+            }
+            mid_r = 0.0;
+            deg = 0.0;
+        } else {
+            // Skip the label when the slice cannot hold the text along the
+            // chosen direction — this keeps the text inside its own ring band
+            // instead of overflowing onto neighbours.
+            let arc_len = delta.to_radians() * mid_r;
+            let tangential_fit = name_w + 6.0 <= arc_len;
+            let radial_fit = name_w + 6.0 <= thickness && font_size <= arc_len;
+            let radial = match self.label_rotate {
+                LabelRotate::Radial => radial_fit.then_some(true),
+                LabelRotate::Auto => {
+                    if tangential_fit {
+                        Some(false)
+                    } else {
+                        radial_fit.then_some(true)
+                    }
+                }
+                LabelRotate::Tangential => tangential_fit.then_some(false),
+            };
+            let Some(radial) = radial else {
+                return;
+            };
+            // Chart angle 0 points up and increases clockwise, which matches
+            // the SVG `rotate` convention, so the tangent at `mid_angle` is
+            // `rotate(mid_angle)` and the radius `rotate(mid_angle - 90)`.
+            // Flip text on the bottom (tangential) or left (radial) half so
+            // it stays upright rather than upside-down.
+            if radial {
+                deg = if deg > 180.0 { deg + 90.0 } else { deg - 90.0 };
+            } else if deg > 90.0 && deg < 270.0 {
+                deg -= 180.0;
+            }
         }
+        let point = get_pie_point(l.cx, l.cy, mid_r, mid_angle);
         // The transform is emitted verbatim, so bake in the canvas offset that
         // `Canvas::text` adds to x / y.
         let abs_x = point.x + c.margin.left;
@@ -343,7 +455,7 @@ impl SunburstChart {
             format_float(abs_y)
         );
 
-        let text_color = if fill.is_light() {
+        let mut text_color = if fill.is_light() {
             Color {
                 r: 30,
                 g: 30,
@@ -358,6 +470,9 @@ impl SunburstChart {
                 a: 230,
             }
         };
+        if dimmed {
+            text_color = text_color.with_alpha(DIMMED_ALPHA);
+        }
         c.text(Text {
             text,
             font_family: Some(self.font_family.clone()),
@@ -373,8 +488,45 @@ impl SunburstChart {
         });
     }
 
+    /// Renders the breadcrumb of the drill-down path and returns its height.
+    fn render_breadcrumb(&self, c: &mut Canvas) -> f32 {
+        if self.root_path.is_empty() {
+            return 0.0;
+        }
+        let font_size = self.series_label_font_size.max(10.0);
+        let separator = " > ";
+        let mut x = 0.0;
+        for (i, name) in self.root_path.iter().enumerate() {
+            let last = i + 1 == self.root_path.len();
+            let text = if last {
+                name.clone()
+            } else {
+                format!("{name}{separator}")
+            };
+            let width = measure_text_width_family(&self.font_family, font_size, &text)
+                .map(|b| b.width())
+                .unwrap_or_default();
+            c.text(Text {
+                text,
+                font_family: Some(self.font_family.clone()),
+                font_color: Some(self.series_label_font_color),
+                font_size: Some(font_size),
+                font_weight: last.then(|| "bold".to_string()),
+                dominant_baseline: Some("hanging".to_string()),
+                x: Some(x),
+                y: Some(0.0),
+                ..Default::default()
+            });
+            x += width;
+        }
+        font_size + 10.0
+    }
+
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -384,18 +536,47 @@ impl SunburstChart {
             ..Default::default()
         });
 
+        // Drill-down: the node at `root_path` becomes the innermost ring,
+        // keeping the colors it has in the full hierarchy.
+        let mut roots = self.series_data.as_slice();
+        let mut color_offset = 0;
+        let mut base_color = Color::black();
+        for (level, name) in self.root_path.iter().enumerate() {
+            let Some(index) = roots.iter().position(|n| &n.name == name) else {
+                return Err(canvas::Error::Params {
+                    message: format!("sunburst root path {:?} is not found", self.root_path),
+                });
+            };
+            if level == 0 {
+                color_offset = index;
+                base_color = roots[index]
+                    .color
+                    .unwrap_or_else(|| get_color(&self.series_colors, index));
+            }
+            roots = std::slice::from_ref(&roots[index]);
+            if level + 1 < self.root_path.len() {
+                roots = &roots[0].children;
+            }
+        }
+
+        let breadcrumb_height = self.render_breadcrumb(&mut content);
+        let mut content = content.child(Box {
+            top: breadcrumb_height,
+            ..Default::default()
+        });
+
         let cw = content.width();
         let ch = content.height();
-        if cw <= 0.0 || ch <= 0.0 || self.series_data.is_empty() {
+        if cw <= 0.0 || ch <= 0.0 || roots.is_empty() {
             return c.svg();
         }
 
-        let total: f32 = self.series_data.iter().map(|n| n.total()).sum();
+        let total: f32 = roots.iter().map(|n| n.total()).sum();
         if total <= 0.0 {
             return c.svg();
         }
 
-        let depth = max_depth(&self.series_data).max(1);
+        let depth = max_depth(roots).max(1);
         let cx = cw / 2.0;
         let cy = ch / 2.0;
         let mut max_r = cw.min(ch) / 2.0 * 0.95;
@@ -421,20 +602,24 @@ impl SunburstChart {
         // to the previous `available / depth` computation.
         let thicknesses: Vec<f32> = weights.iter().map(|w| available * w / weight_sum).collect();
 
+        let level_offset = self.root_path.len().saturating_sub(1);
         self.draw_nodes(
             &mut content,
-            &self.series_data,
+            roots,
             self.start_angle,
             total,
             360.0,
             0,
             inner,
-            Color::black(),
+            base_color,
+            &self.root_path[..level_offset],
             &RingLayout {
                 cx,
                 cy,
                 grand_total: total,
                 thicknesses: &thicknesses,
+                level_offset,
+                color_offset,
             },
         );
 
@@ -460,7 +645,8 @@ impl SunburstChart {
 
 #[cfg(test)]
 mod tests {
-    use super::{SunburstChart, SunburstData};
+    use super::{SunburstChart, SunburstData, SunburstLevel};
+    use crate::LabelRotate;
     use pretty_assertions::assert_eq;
 
     fn leaf(name: &str, value: f32) -> SunburstData {
@@ -614,4 +800,70 @@ mod tests {
             chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn sunburst_chart_drill_down() {
+        let mut chart = make_sunburst();
+        chart.root_path = vec!["Grandpa".to_string()];
+        chart.inner_radius = 40.0;
+        chart.label_rotate = LabelRotate::Auto;
+        chart.levels = vec![
+            SunburstLevel::default(),
+            SunburstLevel {
+                color_fade: Some(0.3),
+                border_width: Some(2.0),
+                ..Default::default()
+            },
+        ];
+        let svg = chart.svg().unwrap();
+        assert_eq!(
+            include_str!("../../asset/sunburst_chart/drill_down.svg"),
+            svg
+        );
+    }
+
+    #[test]
+    fn sunburst_chart_invalid_label_rotate() {
+        let err = SunburstChart::from_json(r#"{"label_rotate": "vertical"}"#)
+            .err()
+            .unwrap();
+        assert_eq!(
+            r#"Params is invalid: label rotate "vertical" of "label_rotate" is invalid"#,
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn sunburst_chart_highlight_json() {
+        let chart = SunburstChart::from_json(
+            r##"{
+                "label_rotate": "radial",
+                "highlight_path": ["Grandpa", "Father"],
+                "levels": [{}, {"border_color": "#333", "border_width": 3}, {"label_show": false}],
+                "series_data": [
+                    {"name": "Grandpa", "children": [
+                        {"name": "Uncle Leo", "children": [{"name": "Cousin Jack", "value": 18}]},
+                        {"name": "Father", "children": [{"name": "Me", "value": 40}]}
+                    ]},
+                    {"name": "Nancy", "children": [
+                        {"name": "Uncle Nike", "children": [{"name": "Cousin Betty", "value": 10}]}
+                    ]}
+                ]
+            }"##,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // Uncle Leo, Cousin Jack, Nancy, Uncle Nike and Cousin Betty are dimmed
+        assert_eq!(5, svg.matches(r#"fill-opacity="0.2" stroke="#).count());
+        assert_eq!(3, svg.matches(r#"stroke-width="3""#).count());
+        assert!(svg.contains("\nFather\n"));
+        assert!(!svg.contains("\nMe\n"));
+
+        let mut chart = chart;
+        chart.root_path = vec!["Grandpa".to_string(), "Mother".to_string()];
+        assert_eq!(
+            "Params is invalid: sunburst root path [\"Grandpa\", \"Mother\"] is not found",
+            chart.svg().unwrap_err().to_string()
+        );
+    }
 }
@@ -10,8 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, get_color_scheme_chart, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -20,7 +19,9 @@ use super::font::text_wrap_fit;
 use super::params::*;
 use super::theme::{Theme, get_default_theme_name, get_theme};
 use super::util::*;
+use super::{Canvas, SparklineChart};
 use crate::charts::measure_text_width_family;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// A style override for a single table cell, addressed by `indexes`.
@@ -36,6 +37,384 @@ pub struct TableCellStyle {
     pub indexes: Vec<usize>,
 }
 
+/// Draws the body cells of a column as something else than plain text.
+///
+/// The numeric renderers read the number of the cell text, ignoring
+/// thousands separators, currency signs and a trailing `%`.
+#[derive(Clone, Debug)]
+pub enum TableCellRenderer {
+    /// Draws the comma or space separated numbers of the cell as a
+    /// sparkline; anything that is not a number is a gap.
+    Sparkline(std::boxed::Box<SparklineChart>),
+    /// Draws a bar behind the text, proportional to the value between `min`
+    /// and `max` (default: zero and the largest value of the column).
+    DataBar {
+        /// Color of the bar.
+        color: Color,
+        /// Value of an empty bar.
+        min: Option<f32>,
+        /// Value of a full bar.
+        max: Option<f32>,
+    },
+    /// Colors the cell background by its value, from `min_color` for the
+    /// smallest value of the column to `max_color` for the largest one,
+    /// passing `mid_color` half way when set.
+    ColorScale {
+        /// Color of the smallest value.
+        min_color: Color,
+        /// Optional color of the middle of the range.
+        mid_color: Option<Color>,
+        /// Color of the largest value.
+        max_color: Color,
+    },
+    /// Prefixes the text with an up or down arrow by the sign of the value
+    /// and colors both accordingly.
+    Delta {
+        /// Color of positive values.
+        increase_color: Color,
+        /// Color of negative values.
+        decrease_color: Color,
+    },
+}
+
+impl TableCellRenderer {
+    /// Whether the cell text is replaced by the rendered content.
+    fn replaces_text(&self) -> bool {
+        matches!(self, TableCellRenderer::Sparkline(_))
+    }
+}
+
+fn get_table_cell_renderer_list_from_value(
+    value: &serde_json::Value,
+    theme: &str,
+) -> canvas::Result<Option<Vec<Option<TableCellRenderer>>>> {
+    let Some(arr) = value.get("cell_renderers").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+    let t = get_theme(theme);
+    let mut data_bar_color = t.table_data_bar_color;
+    if data_bar_color.is_zero() {
+        data_bar_color = get_color(&t.series_colors, 0).with_alpha(90);
+    }
+    let mut renderers = vec![];
+    for item in arr.iter() {
+        let renderer = match get_string_from_value(item, "type").as_deref() {
+            Some("data_bar") => Some(TableCellRenderer::DataBar {
+                color: get_color_from_value(item, "color")?.unwrap_or(data_bar_color),
+                min: get_f32_from_value(item, "min"),
+                max: get_f32_from_value(item, "max"),
+            }),
+            Some("color_scale") => Some(TableCellRenderer::ColorScale {
+                min_color: get_color_from_value(item, "min_color")?
+                    .unwrap_or(t.table_color_scale_min_color),
+                mid_color: get_color_from_value(item, "mid_color")?,
+                max_color: get_color_from_value(item, "max_color")?
+                    .unwrap_or(t.table_color_scale_max_color),
+            }),
+            Some("delta") => Some(TableCellRenderer::Delta {
+                increase_color: get_color_from_value(item, "increase_color")?
+                    .unwrap_or(t.table_delta_increase_color),
+                decrease_color: get_color_from_value(item, "decrease_color")?
+                    .unwrap_or(t.table_delta_decrease_color),
+            }),
+            Some("sparkline") => {
+                let mut option = item.clone();
+                if let Some(obj) = option.as_object_mut()
+                    && !obj.contains_key("theme")
+                {
+                    obj.insert("theme".to_string(), theme.into());
+                }
+                let chart = SparklineChart::from_json(&option.to_string())?;
+                Some(TableCellRenderer::Sparkline(std::boxed::Box::new(chart)))
+            }
+            _ => None,
+        };
+        renderers.push(renderer);
+    }
+    Ok(Some(renderers))
+}
+
+/// Parses the number a cell starts with, e.g. `"$1,234.5"`, `"-7.49%"` or
+/// `"12 items"`. A sign and currency symbols may precede the number, thousands
+/// separators are skipped and anything after the number is ignored.
+fn parse_cell_value(text: &str) -> Option<f32> {
+    let mut chars = text.trim().chars().peekable();
+    let mut value = String::new();
+    while let Some(&c) = chars.peek() {
+        if matches!(c, '-' | '+') && value.is_empty() {
+            value.push(c);
+        } else if !matches!(c, '$' | '€' | '£' | '¥' | '₹' | '₩' | '₽' | '¢') && !c.is_whitespace()
+        {
+            break;
+        }
+        chars.next();
+    }
+    for c in chars {
+        if c.is_ascii_digit() || (c == '.' && !value.contains('.')) {
+            value.push(c);
+        } else if c != ',' {
+            break;
+        }
+    }
+    value.parse::<f32>().ok()
+}
+
+/// Interpolates the color scale at `ratio` (0.0 to 1.0).
+fn get_scale_color(
+    min_color: Color,
+    mid_color: Option<Color>,
+    max_color: Color,
+    ratio: f32,
+) -> Color {
+    let (from, to, ratio) = match mid_color {
+        Some(mid_color) if ratio < 0.5 => (min_color, mid_color, ratio * 2.0),
+        Some(mid_color) => (mid_color, max_color, ratio * 2.0 - 1.0),
+        None => (min_color, max_color, ratio),
+    };
+    from.mix(to, ratio)
+}
+
+/// Parses the comma or space separated numbers of a sparkline cell.
+fn parse_cell_values(text: &str) -> Vec<Option<f32>> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<f32>().ok())
+        .collect()
+}
+
+/// Cells merged into one, addressed by the top left cell.  The text of the
+/// other cells of the area is ignored.
+#[derive(Clone, Debug, Default)]
+pub struct TableMergedCell {
+    /// The top left cell as `[row, column]`.
+    pub indexes: Vec<usize>,
+    /// Number of rows taken by the cell.
+    pub row_span: usize,
+    /// Number of columns taken by the cell.
+    pub column_span: usize,
+}
+
+fn get_table_merged_cell_list_from_value(
+    value: &serde_json::Value,
+) -> Option<Vec<TableMergedCell>> {
+    let arr = value.get("merged_cells")?.as_array()?;
+    let merged_cells = arr
+        .iter()
+        .map(|item| TableMergedCell {
+            indexes: get_usize_slice_from_value(item, "indexes").unwrap_or_default(),
+            row_span: get_usize_from_value(item, "row_span").unwrap_or(1),
+            column_span: get_usize_from_value(item, "column_span").unwrap_or(1),
+        })
+        .collect();
+    Some(merged_cells)
+}
+
+/// The aggregation of a column in the summary row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableAggregation {
+    /// Sum of the numbers.
+    Sum,
+    /// Average of the numbers.
+    Avg,
+    /// Smallest number.
+    Min,
+    /// Largest number.
+    Max,
+    /// Number of non-empty cells.
+    Count,
+}
+
+impl TableAggregation {
+    fn aggregate(&self, texts: &[&str]) -> Option<f32> {
+        if *self == TableAggregation::Count {
+            return Some(texts.iter().filter(|text| !text.trim().is_empty()).count() as f32);
+        }
+        let values: Vec<f32> = texts
+            .iter()
+            .filter_map(|text| parse_cell_value(text))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        let value = match self {
+            TableAggregation::Sum => values.iter().sum(),
+            TableAggregation::Avg => values.iter().sum::<f32>() / values.len() as f32,
+            TableAggregation::Min => values.iter().copied().fold(f32::INFINITY, f32::min),
+            TableAggregation::Max => values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            TableAggregation::Count => values.len() as f32,
+        };
+        Some(value)
+    }
+}
+
+fn get_table_aggregation_list_from_value(
+    value: &serde_json::Value,
+) -> Option<Vec<Option<TableAggregation>>> {
+    let arr = value.get("summary_aggregations")?.as_array()?;
+    let aggregations = arr
+        .iter()
+        .map(|item| match item.as_str()?.to_lowercase().as_str() {
+            "sum" => Some(TableAggregation::Sum),
+            "avg" | "average" => Some(TableAggregation::Avg),
+            "min" => Some(TableAggregation::Min),
+            "max" => Some(TableAggregation::Max),
+            "count" => Some(TableAggregation::Count),
+            _ => None,
+        })
+        .collect();
+    Some(aggregations)
+}
+
+/// The predicate of a conditional formatting rule, tested on the body cells
+/// of one column.  The numeric conditions read the number of the cell text
+/// like the cell renderers do.
+#[derive(Clone, Debug)]
+pub enum TableCellCondition {
+    /// The value is greater than the number.
+    GreaterThan(f32),
+    /// The value is less than the number.
+    LessThan(f32),
+    /// The value is within the inclusive range.
+    Between(f32, f32),
+    /// The text equals the string, or the value equals its number.
+    Equal(String),
+    /// The text matches the regular expression.
+    Regex(regex_lite::Regex),
+    /// The value is one of the n largest of the column.
+    Top(usize),
+    /// The value is one of the n smallest of the column.
+    Bottom(usize),
+}
+
+/// Styles the body cells of a column matching a condition, so the styling
+/// follows the data instead of fixed `TableCellStyle::indexes`.  Later rules
+/// override earlier ones and `cell_styles` override both.
+#[derive(Clone, Debug)]
+pub struct TableCellRule {
+    /// The column the condition is tested on.
+    pub column: usize,
+    /// The condition.
+    pub condition: TableCellCondition,
+    /// Font color of the matching cells.
+    pub font_color: Option<Color>,
+    /// Font weight of the matching cells.
+    pub font_weight: Option<String>,
+    /// Background color of the matching cells.
+    pub background_color: Option<Color>,
+}
+
+impl TableCellRule {
+    /// Returns the body rows (relative to the first one) matching the rule.
+    fn get_matched_rows(&self, texts: &[&str]) -> Vec<usize> {
+        let values: Vec<Option<f32>> = texts.iter().map(|text| parse_cell_value(text)).collect();
+        // the threshold of the top and bottom conditions
+        let mut sorted_values: Vec<f32> = values.iter().flatten().copied().collect();
+        sorted_values.sort_by(|a, b| a.total_cmp(b));
+        (0..texts.len())
+            .filter(|&index| {
+                let text = texts[index].trim();
+                let value = values[index];
+                match &self.condition {
+                    TableCellCondition::GreaterThan(v) => value.is_some_and(|value| value > *v),
+                    TableCellCondition::LessThan(v) => value.is_some_and(|value| value < *v),
+                    TableCellCondition::Between(min, max) => {
+                        value.is_some_and(|value| value >= *min && value <= *max)
+                    }
+                    TableCellCondition::Equal(v) => {
+                        text == v || value.is_some_and(|value| v.parse::<f32>().ok() == Some(value))
+                    }
+                    TableCellCondition::Regex(regex) => regex.is_match(text),
+                    TableCellCondition::Top(n) => {
+                        *n > 0
+                            && value.is_some_and(|value| {
+                                let index = sorted_values.len().saturating_sub(*n);
+                                value >= sorted_values[index]
+                            })
+                    }
+                    TableCellCondition::Bottom(n) => {
+                        *n > 0
+                            && value.is_some_and(|value| {
+                                let index = (*n).min(sorted_values.len()) - 1;
+                                value <= sorted_values[index]
+                            })
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn get_table_cell_rule_list_from_value(
+    value: &serde_json::Value,
+) -> canvas::Result<Option<Vec<TableCellRule>>> {
+    let Some(arr) = value.get("cell_rules").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+    let mut rules = vec![];
+    for item in arr.iter() {
+        let condition = get_string_from_value(item, "condition").unwrap_or_default();
+        let invalid = || canvas::Error::Params {
+            message: format!("cell rule condition {condition:?} or its value is invalid"),
+        };
+        let number = get_f32_from_value(item, "value");
+        let count = get_usize_from_value(item, "value");
+        let condition = match condition.as_str() {
+            "gt" => TableCellCondition::GreaterThan(number.ok_or_else(invalid)?),
+            "lt" => TableCellCondition::LessThan(number.ok_or_else(invalid)?),
+            "between" => match get_f32_slice_from_value(item, "value").as_deref() {
+                Some([min, max]) => TableCellCondition::Between(*min, *max),
+                _ => return Err(invalid()),
+            },
+            "eq" => match item.get("value") {
+                Some(serde_json::Value::String(v)) => TableCellCondition::Equal(v.clone()),
+                Some(v) if v.is_number() => TableCellCondition::Equal(v.to_string()),
+                _ => return Err(invalid()),
+            },
+            "regex" => {
+                let pattern = get_string_from_value(item, "value").ok_or_else(invalid)?;
+                let regex =
+                    regex_lite::Regex::new(&pattern).map_err(|e| canvas::Error::Params {
+                        message: format!("cell rule regex {pattern:?} is invalid: {e}"),
+                    })?;
+                TableCellCondition::Regex(regex)
+            }
+            "top" => TableCellCondition::Top(count.ok_or_else(invalid)?),
+            "bottom" => TableCellCondition::Bottom(count.ok_or_else(invalid)?),
+            _ => return Err(invalid()),
+        };
+        rules.push(TableCellRule {
+            column: get_usize_from_value(item, "column").unwrap_or_default(),
+            condition,
+            font_color: get_color_from_value(item, "font_color")?,
+            font_weight: get_string_from_value(item, "font_weight"),
+            background_color: get_color_from_value(item, "background_color")?,
+        });
+    }
+    Ok(Some(rules))
+}
+
+/// The computed geometry of a table, shared by all its pages.
+struct TableLayout {
+    /// The data rows followed by the summary row.
+    rows: Vec<Vec<String>>,
+    header_count: usize,
+    summary_index: Option<usize>,
+    /// Column widths.
+    spans: Vec<f32>,
+    /// The top left cell of the merged cell taking each cell.
+    origins: Vec<Vec<(usize, usize)>>,
+    /// `(row_span, column_span)` of each cell, zero for merged away cells.
+    cell_spans: Vec<Vec<(usize, usize)>>,
+    /// The wrapped text lines of each cell.
+    contents: Vec<Vec<Vec<String>>>,
+    /// Row heights.
+    heights: Vec<f32>,
+    /// The value ranges of the columns with a renderer.
+    column_ranges: Vec<Option<(f32, f32)>>,
+    /// The styles of the cell rules by `(row, column)`.
+    rule_styles: HashMap<(usize, usize), TableCellStyle>,
+}
+
 /// A table rendered as SVG, with optional per-cell style overrides.
 #[derive(Clone, Debug, Default)]
 pub struct TableChart {
@@ -43,8 +422,14 @@ pub struct TableChart {
     /// directly on the chart through `Deref`, e.g. `chart.title_text`.
     pub base: ChartBase,
 
-    /// The table content as rows of cells; the first row is the header.
+    /// The table content as rows of cells; the first `header_row_count` rows
+    /// are the header.
     pub data: Vec<Vec<String>>,
+    /// Number of header rows, for grouped headers (default: 1).
+    pub header_row_count: usize,
+    /// Cells spanning several rows and/or columns.  Cells merged over
+    /// several columns are centered.
+    pub merged_cells: Vec<TableMergedCell>,
     /// Column widths: values below 1.0 are fractions of the table width,
     /// larger values are absolute; unspecified columns split the rest equally.
     pub spans: Vec<f32>,
@@ -81,6 +466,23 @@ pub struct TableChart {
 
     /// Per-cell style overrides.
     pub cell_styles: Vec<TableCellStyle>,
+
+    /// Conditional formatting of the body cells.
+    pub cell_rules: Vec<TableCellRule>,
+
+    /// Renderers of the body cells per column; `None` draws the text.
+    pub cell_renderers: Vec<Option<TableCellRenderer>>,
+
+    /// Aggregations of the summary row per column, drawn in the header
+    /// style below the body; no aggregation leaves the row out.
+    pub summary_aggregations: Vec<Option<TableAggregation>>,
+    /// Text of the first summary cell when that column is not aggregated.
+    pub summary_label: String,
+    /// Formatter of the aggregated values, supporting `{c}` and `{t}`.
+    pub summary_formatter: String,
+
+    /// Maximum height of a page of `svg_pages`; zero keeps one page.
+    pub max_height: f32,
 }
 
 impl std::ops::Deref for TableChart {
@@ -122,7 +524,7 @@ impl TableChart {
         if let Some(title_font_size) = get_f32_from_value(&data, "title_font_size") {
             self.title_font_size = title_font_size;
         }
-        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color") {
+        if let Some(title_font_color) = get_color_from_value(&data, "title_font_color")? {
             self.title_font_color = title_font_color;
         }
         if let Some(title_font_weight) = get_string_from_value(&data, "title_font_weight") {
@@ -144,7 +546,7 @@ impl TableChart {
         if let Some(sub_title_font_size) = get_f32_from_value(&data, "sub_title_font_size") {
             self.sub_title_font_size = sub_title_font_size;
         }
-        if let Some(sub_title_font_color) = get_color_from_value(&data, "sub_title_font_color") {
+        if let Some(sub_title_font_color) = get_color_from_value(&data, "sub_title_font_color")? {
             self.sub_title_font_color = sub_title_font_color;
         }
         if let Some(sub_title_font_weight) = get_string_from_value(&data, "sub_title_font_weight") {
@@ -167,10 +569,10 @@ impl TableChart {
                 let mut style = TableCellStyle {
                     ..Default::default()
                 };
-                if let Some(font_color) = get_color_from_value(item, "font_color") {
+                if let Some(font_color) = get_color_from_value(item, "font_color")? {
                     style.font_color = Some(font_color);
                 }
-                if let Some(background_color) = get_color_from_value(item, "background_color") {
+                if let Some(background_color) = get_color_from_value(item, "background_color")? {
                     style.background_color = Some(background_color);
                 }
                 if let Some(font_weight) = get_string_from_value(item, "font_weight") {
@@ -194,6 +596,17 @@ impl TableChart {
                     for item in sub_arr.iter() {
                         if let Some(str) = item.as_str() {
                             list.push(str.to_string());
+                        } else if item.is_number() {
+                            list.push(item.to_string());
+                        } else if let Some(values) = item.as_array() {
+                            // the numbers of a sparkline cell
+                            let values: Vec<String> = values
+                                .iter()
+                                .map(|v| {
+                                    v.as_f64().map(|v| v.to_string()).unwrap_or("-".to_string())
+                                })
+                                .collect();
+                            list.push(values.join(","));
                         }
                     }
                 }
@@ -219,11 +632,11 @@ impl TableChart {
         if let Some(header_font_weight) = get_string_from_value(&data, "header_font_weight") {
             self.header_font_weight = Some(header_font_weight);
         }
-        if let Some(header_font_color) = get_color_from_value(&data, "header_font_color") {
+        if let Some(header_font_color) = get_color_from_value(&data, "header_font_color")? {
             self.header_font_color = header_font_color;
         }
         if let Some(header_background_color) =
-            get_color_from_value(&data, "header_background_color")
+            get_color_from_value(&data, "header_background_color")?
         {
             self.header_background_color = header_background_color;
         }
@@ -236,20 +649,44 @@ impl TableChart {
         if let Some(body_font_size) = get_f32_from_value(&data, "body_font_size") {
             self.body_font_size = body_font_size;
         }
-        if let Some(body_font_color) = get_color_from_value(&data, "body_font_color") {
+        if let Some(body_font_color) = get_color_from_value(&data, "body_font_color")? {
             self.body_font_color = body_font_color;
         }
         if let Some(body_background_colors) =
-            get_color_slice_from_value(&data, "body_background_colors")
+            get_color_slice_from_value(&data, "body_background_colors")?
         {
             self.body_background_colors = body_background_colors;
         }
-        if let Some(border_color) = get_color_from_value(&data, "border_color") {
+        if let Some(border_color) = get_color_from_value(&data, "border_color")? {
             self.border_color = border_color;
         }
         if let Some(outlined) = get_bool_from_value(&data, "outlined") {
             self.outlined = outlined;
         }
+        if let Some(cell_renderers) = get_table_cell_renderer_list_from_value(&data, &theme)? {
+            self.cell_renderers = cell_renderers;
+        }
+        if let Some(cell_rules) = get_table_cell_rule_list_from_value(&data)? {
+            self.cell_rules = cell_rules;
+        }
+        if let Some(header_row_count) = get_usize_from_value(&data, "header_row_count") {
+            self.header_row_count = header_row_count;
+        }
+        if let Some(merged_cells) = get_table_merged_cell_list_from_value(&data) {
+            self.merged_cells = merged_cells;
+        }
+        if let Some(summary_aggregations) = get_table_aggregation_list_from_value(&data) {
+            self.summary_aggregations = summary_aggregations;
+        }
+        if let Some(summary_label) = get_string_from_value(&data, "summary_label") {
+            self.summary_label = summary_label;
+        }
+        if let Some(summary_formatter) = get_string_from_value(&data, "summary_formatter") {
+            self.summary_formatter = summary_formatter;
+        }
+        if let Some(max_height) = get_f32_from_value(&data, "max_height") {
+            self.max_height = max_height;
+        }
         Ok(data)
     }
     /// Creates a table chart from json.
@@ -262,6 +699,7 @@ impl TableChart {
     pub fn new_with_theme(data: Vec<Vec<String>>, theme: &str) -> TableChart {
         let mut table = TableChart {
             data,
+            header_row_count: 1,
             header_row_padding: (10.0, 8.0).into(),
             header_row_height: 30.0,
             body_row_padding: (10.0, 5.0).into(),
@@ -363,57 +801,119 @@ impl TableChart {
     /// Converts table chart to svg. The table sizes itself to its content;
     /// the rendered height is the `height` attribute of the returned svg.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |_| &mut [], Self::svg) {
+            return svg;
+        }
         Ok(self.render()?.0)
     }
 
-    /// Renders the svg and returns it with the computed (auto-sized) height.
-    pub(crate) fn render(&self) -> canvas::Result<(String, f32)> {
-        if self.data.is_empty() {
-            return Err(canvas::Error::Params {
-                message: "data is empty".to_string(),
-            });
+    /// Converts table chart to one svg per page, each at most `max_height`
+    /// high.  Every page repeats the title and the header rows; the summary
+    /// row closes the last page.  Rows of a cell merged over several rows are
+    /// kept on one page, and a row higher than a page gets a page of its own.
+    pub fn svg_pages(&self) -> canvas::Result<Vec<String>> {
+        if let Some(result) = get_color_scheme_chart(self, |_| &mut [], Self::svg) {
+            let (chart, variables) = result?;
+            let _guard = variables.activate();
+            return chart.svg_pages();
         }
-        let column_count = self.data[0].len();
-        if column_count == 0 {
-            return Err(canvas::Error::Params {
-                message: "table header column is empty".to_string(),
-            });
-        }
-        for item in self.data.iter() {
-            if item.len() != column_count {
-                return Err(canvas::Error::Params {
-                    message: "data len is invalid".to_string(),
-                });
+        let layout = self.get_layout()?;
+        let header_rows: Vec<usize> = (0..layout.header_count).collect();
+        let header_height: f32 = header_rows.iter().map(|i| layout.heights[*i]).sum();
+        let summary_height = layout.summary_index.map_or(0.0, |i| layout.heights[i]);
+
+        // the rows of a cell merged over several rows can not be split
+        let mut blocks = vec![];
+        let body_end = layout.summary_index.unwrap_or(layout.rows.len());
+        let mut start = layout.header_count;
+        while start < body_end {
+            let mut end = start + 1;
+            let mut i = start;
+            while i < end {
+                for (row_span, _) in layout.cell_spans[i].iter() {
+                    end = end.max(i + row_span);
+                }
+                i += 1;
             }
+            blocks.push(start..end);
+            start = end;
         }
 
-        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
-
-        if !self.title_text.is_empty() {
-            let mut title_height = self.title_height;
-            if let Some(value) = self.title_margin.clone() {
-                title_height += value.top + value.bottom;
+        let mut pages: Vec<Vec<usize>> = vec![];
+        if self.max_height > 0.0 {
+            let available = self.max_height - self.get_title_height() - header_height;
+            let mut page = vec![];
+            let mut page_height = 0.0;
+            for block in blocks {
+                let height: f32 = block.clone().map(|i| layout.heights[i]).sum();
+                if !page.is_empty() && page_height + height > available {
+                    pages.push(std::mem::take(&mut page));
+                    page_height = 0.0;
+                }
+                page.extend(block);
+                page_height += height;
             }
-            if !self.sub_title_text.is_empty() {
-                title_height += self.sub_title_height;
+            if let Some(index) = layout.summary_index {
+                if !page.is_empty() && page_height + summary_height > available {
+                    pages.push(std::mem::take(&mut page));
+                }
+                page.push(index);
             }
-            c.rect(Rect {
-                fill: Some(self.background_color.into()),
-                left: 0.0,
-                top: 0.0,
-                width: self.width,
-                height: title_height,
-                ..Default::default()
-            });
+            if !page.is_empty() {
+                pages.push(page);
+            }
+        } else {
+            pages.push((layout.header_count..layout.rows.len()).collect());
         }
 
-        let title_height = self.render_title(c.child(Box::default()));
+        let mut svg_list = vec![];
+        for page in pages {
+            let mut rows = header_rows.clone();
+            rows.extend(page);
+            svg_list.push(self.render_rows(&layout, &rows)?.0);
+        }
+        Ok(svg_list)
+    }
 
-        c = c.child(Box {
-            top: title_height,
-            ..Default::default()
-        });
-        let width = c.width();
+    /// Renders the svg and returns it with the computed (auto-sized) height.
+    pub(crate) fn render(&self) -> canvas::Result<(String, f32)> {
+        let layout = self.get_layout()?;
+        let rows: Vec<usize> = (0..layout.rows.len()).collect();
+        self.render_rows(&layout, &rows)
+    }
+
+    /// Returns the height taken by the title and the sub title.
+    fn get_title_height(&self) -> f32 {
+        let c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+        self.render_title(c.child(Box::default()))
+    }
+
+    /// Returns the summary row, or `None` without any aggregation.
+    fn get_summary_row(&self, header_count: usize, column_count: usize) -> Option<Vec<String>> {
+        if self.summary_aggregations.iter().all(|v| v.is_none()) {
+            return None;
+        }
+        let body = &self.data[header_count..];
+        let row = (0..column_count)
+            .map(
+                |j| match self.summary_aggregations.get(j).copied().flatten() {
+                    Some(aggregation) => {
+                        let texts: Vec<&str> = body.iter().map(|row| row[j].as_str()).collect();
+                        aggregation
+                            .aggregate(&texts)
+                            .map(|value| format_value(value, &self.summary_formatter))
+                            .unwrap_or_else(|| "-".to_string())
+                    }
+                    None if j == 0 => self.summary_label.clone(),
+                    None => "".to_string(),
+                },
+            )
+            .collect();
+        Some(row)
+    }
+
+    /// Splits the width between the columns.
+    fn get_spans(&self, width: f32, column_count: usize) -> Vec<f32> {
         let mut spans = vec![];
         let mut rest_width = width;
         let mut rest_count = 0.0_f32;
@@ -442,55 +942,312 @@ impl TableChart {
                 }
             }
         }
+        spans
+    }
 
-        let find_cell_style = |row: usize, column: usize| -> Option<&TableCellStyle> {
-            for cell_style in self.cell_styles.iter() {
-                if cell_style.indexes.len() != 2 {
-                    continue;
+    fn is_header_row(&self, layout: &TableLayout, row: usize) -> bool {
+        row < layout.header_count || layout.summary_index == Some(row)
+    }
+
+    /// Returns the style of the cell rules overridden by the cell style.
+    fn get_cell_style(
+        &self,
+        layout: &TableLayout,
+        row: usize,
+        column: usize,
+    ) -> Option<TableCellStyle> {
+        let rule_style = layout.rule_styles.get(&(row, column));
+        let cell_style = self
+            .cell_styles
+            .iter()
+            .find(|style| style.indexes.len() == 2 && style.indexes == [row, column]);
+        match (rule_style, cell_style) {
+            (Some(rule_style), Some(cell_style)) => Some(TableCellStyle {
+                font_color: cell_style.font_color.or(rule_style.font_color),
+                font_weight: cell_style
+                    .font_weight
+                    .clone()
+                    .or(rule_style.font_weight.clone()),
+                background_color: cell_style.background_color.or(rule_style.background_color),
+                indexes: cell_style.indexes.clone(),
+            }),
+            (rule_style, cell_style) => cell_style.or(rule_style).cloned(),
+        }
+    }
+
+    fn find_cell_renderer(
+        &self,
+        layout: &TableLayout,
+        row: usize,
+        column: usize,
+    ) -> Option<&TableCellRenderer> {
+        if self.is_header_row(layout, row) {
+            return None;
+        }
+        self.cell_renderers.get(column).and_then(|v| v.as_ref())
+    }
+
+    /// Computes the column widths, the merged cells, the wrapped text and the
+    /// height of every row.
+    fn get_layout(&self) -> canvas::Result<TableLayout> {
+        if self.data.is_empty() {
+            return Err(canvas::Error::Params {
+                message: "data is empty".to_string(),
+            });
+        }
+        let column_count = self.data[0].len();
+        if column_count == 0 {
+            return Err(canvas::Error::Params {
+                message: "table header column is empty".to_string(),
+            });
+        }
+        for item in self.data.iter() {
+            if item.len() != column_count {
+                return Err(canvas::Error::Params {
+                    message: "data len is invalid".to_string(),
+                });
+            }
+        }
+        let data_count = self.data.len();
+        let header_count = self.header_row_count.clamp(1, data_count);
+        let mut rows = self.data.clone();
+        let mut summary_index = None;
+        if let Some(row) = self.get_summary_row(header_count, column_count) {
+            summary_index = Some(rows.len());
+            rows.push(row);
+        }
+        let width = Canvas::new_width_xy(self.width, self.height, self.x, self.y).width();
+        let spans = self.get_spans(width, column_count);
+
+        // every cell starts as its own origin, merged cells point to the top
+        // left cell and only the origin keeps the spans
+        let mut origins: Vec<Vec<(usize, usize)>> = (0..rows.len())
+            .map(|i| (0..column_count).map(|j| (i, j)).collect())
+            .collect();
+        let mut cell_spans = vec![vec![(1, 1); column_count]; rows.len()];
+        for merged_cell in self.merged_cells.iter() {
+            let [row, column] = merged_cell.indexes[..] else {
+                continue;
+            };
+            if row >= data_count || column >= column_count {
+                continue;
+            }
+            // header cells do not span into the body and nothing into the summary
+            let row_end = if row < header_count {
+                header_count
+            } else {
+                data_count
+            };
+            let row_span = merged_cell.row_span.clamp(1, row_end - row);
+            let column_span = merged_cell.column_span.clamp(1, column_count - column);
+            let area = (row..row + row_span)
+                .flat_map(|i| (column..column + column_span).map(move |j| (i, j)));
+            // the first merged cell wins on overlaps
+            if area
+                .clone()
+                .any(|(i, j)| origins[i][j] != (i, j) || cell_spans[i][j] != (1, 1))
+            {
+                continue;
+            }
+            for (i, j) in area {
+                origins[i][j] = (row, column);
+                cell_spans[i][j] = (0, 0);
+            }
+            cell_spans[row][column] = (row_span, column_span);
+        }
+
+        // the value ranges of the numeric renderers
+        let column_ranges: Vec<Option<(f32, f32)>> = (0..column_count)
+            .map(|j| {
+                if self.cell_renderers.get(j).is_none_or(|v| v.is_none()) {
+                    return None;
                 }
-                if cell_style.indexes[0] != row || cell_style.indexes[1] != column {
-                    continue;
+                let values: Vec<f32> = self
+                    .data
+                    .iter()
+                    .skip(header_count)
+                    .filter_map(|row| parse_cell_value(&row[j]))
+                    .collect();
+                if values.is_empty() {
+                    return None;
+                }
+                let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                Some((min, max))
+            })
+            .collect();
+
+        let mut rule_styles: HashMap<(usize, usize), TableCellStyle> = HashMap::new();
+        for rule in self.cell_rules.iter() {
+            if rule.column >= column_count {
+                continue;
+            }
+            let texts: Vec<&str> = self.data[header_count..]
+                .iter()
+                .map(|row| row[rule.column].as_str())
+                .collect();
+            for index in rule.get_matched_rows(&texts) {
+                let row = header_count + index;
+                let style = rule_styles.entry((row, rule.column)).or_default();
+                if rule.font_color.is_some() {
+                    style.font_color = rule.font_color;
+                }
+                if rule.font_weight.is_some() {
+                    style.font_weight.clone_from(&rule.font_weight);
+                }
+                if rule.background_color.is_some() {
+                    style.background_color = rule.background_color;
                 }
-                return Some(cell_style);
             }
-            None
+        }
+
+        let mut layout = TableLayout {
+            rows,
+            header_count,
+            summary_index,
+            spans,
+            origins,
+            cell_spans,
+            contents: vec![],
+            heights: vec![],
+            column_ranges,
+            rule_styles,
         };
 
-        let mut table_content_list = vec![];
-        for (i, items) in self.data.iter().enumerate() {
+        for (i, items) in layout.rows.iter().enumerate() {
             let mut font_size = self.body_font_size;
             let mut padding = self.body_row_padding.left + self.body_row_padding.right;
-            let is_header = i == 0;
-            if is_header {
+            if self.is_header_row(&layout, i) {
                 font_size = self.header_font_size;
                 padding = self.header_row_padding.left + self.header_row_padding.right;
             }
 
             let mut row_content_list = vec![];
             for (j, item) in items.iter().enumerate() {
-                // spans[j] can not be nil
+                let (_, column_span) = layout.cell_spans[i][j];
+                // the renderer draws the cell, its text is not wrapped
+                if column_span == 0
+                    || self
+                        .find_cell_renderer(&layout, i, j)
+                        .is_some_and(|r| r.replaces_text())
+                {
+                    row_content_list.push(vec![]);
+                    continue;
+                }
                 // minus the padding value
-                let span_width = spans[j] - padding;
+                let span_width = layout.spans[j..j + column_span].iter().sum::<f32>() - padding;
                 if let Ok(result) = text_wrap_fit(&self.font_family, font_size, item, span_width) {
                     row_content_list.push(result);
                 } else {
                     row_content_list.push(vec![item.clone()]);
                 }
             }
-            table_content_list.push(row_content_list);
+            layout.contents.push(row_content_list);
+        }
+
+        // (line height, vertical padding) of a row
+        let row_metrics = |i: usize| {
+            if self.is_header_row(&layout, i) {
+                (
+                    self.header_row_height,
+                    self.header_row_padding.top + self.header_row_padding.bottom,
+                )
+            } else {
+                (
+                    self.body_row_height,
+                    self.body_row_padding.top + self.body_row_padding.bottom,
+                )
+            }
+        };
+        let mut heights = vec![];
+        for i in 0..layout.rows.len() {
+            let (line_height, padding) = row_metrics(i);
+            let mut count = 0;
+            for (j, content_list) in layout.contents[i].iter().enumerate() {
+                // cells merged over several rows are sized below
+                if layout.cell_spans[i][j].0 != 1 {
+                    continue;
+                }
+                // a rendered cell takes one line
+                let lines = if self
+                    .find_cell_renderer(&layout, i, j)
+                    .is_some_and(|r| r.replaces_text())
+                {
+                    1
+                } else {
+                    content_list.len()
+                };
+                if count < lines {
+                    count = lines;
+                }
+            }
+            heights.push(line_height * count.max(1) as f32 + padding);
+        }
+        // the last row of a merged cell grows until the content fits
+        for i in 0..layout.rows.len() {
+            let (line_height, padding) = row_metrics(i);
+            for (j, (row_span, _)) in layout.cell_spans[i].iter().enumerate() {
+                if *row_span < 2 {
+                    continue;
+                }
+                let lines = layout.contents[i][j].len().max(1);
+                let needed = line_height * lines as f32 + padding;
+                let total: f32 = heights[i..i + row_span].iter().sum();
+                if needed > total {
+                    heights[i + row_span - 1] += needed - total;
+                }
+            }
+        }
+        layout.heights = heights;
+        Ok(layout)
+    }
+
+    /// Renders the given rows of the layout below the title.
+    fn render_rows(&self, layout: &TableLayout, rows: &[usize]) -> canvas::Result<(String, f32)> {
+        let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
+
+        if !self.title_text.is_empty() {
+            let mut title_height = self.title_height;
+            if let Some(value) = self.title_margin.clone() {
+                title_height += value.top + value.bottom;
+            }
+            if !self.sub_title_text.is_empty() {
+                title_height += self.sub_title_height;
+            }
+            c.rect(Rect {
+                fill: Some(self.background_color.into()),
+                left: 0.0,
+                top: 0.0,
+                width: self.width,
+                height: title_height,
+                ..Default::default()
+            });
+        }
+
+        let title_height = self.render_title(c.child(Box::default()));
+
+        c = c.child(Box {
+            top: title_height,
+            ..Default::default()
+        });
+        let spans = &layout.spans;
+        let mut lefts = vec![];
+        let mut right = 0.0;
+        for span in spans.iter() {
+            lefts.push(right);
+            right += span;
         }
+
         let mut top = 0.0;
         let body_background_color_count = self.body_background_colors.len();
 
-        for (i, items) in table_content_list.iter().enumerate() {
-            let mut left = 0.0;
-            let mut right = 0.0;
+        for &i in rows.iter() {
             let mut line_height = self.body_row_height;
             let mut padding = self.body_row_padding.top + self.body_row_padding.bottom;
             let mut font_size = self.body_font_size;
             let mut font_color = self.body_font_color;
 
-            let is_header = i == 0;
+            let is_header = self.is_header_row(layout, i);
             let mut font_weight = None;
             let bg_color = if is_header {
                 line_height = self.header_row_height;
@@ -500,7 +1257,7 @@ impl TableChart {
                 font_weight.clone_from(&self.header_font_weight);
                 self.header_background_color
             } else {
-                self.body_background_colors[(i - 1) % body_background_color_count]
+                self.body_background_colors[(i - layout.header_count) % body_background_color_count]
             };
 
             let row_padding = if is_header {
@@ -508,45 +1265,81 @@ impl TableChart {
             } else {
                 self.body_row_padding.clone()
             };
-            let mut count = 0;
-            for content_list in items.iter() {
-                if count < content_list.len() {
-                    count = content_list.len();
+            let row_height = layout.heights[i];
+
+            // the columns of cells merged from the rows above are left out
+            let mut segments: Vec<(f32, f32)> = vec![];
+            let mut previous_included = false;
+            for (j, (origin_row, _)) in layout.origins[i].iter().enumerate() {
+                let included = *origin_row >= i;
+                if included {
+                    match segments.last_mut() {
+                        Some((_, width)) if previous_included => *width += spans[j],
+                        _ => segments.push((lefts[j], spans[j])),
+                    }
                 }
+                previous_included = included;
             }
-            let row_height = line_height * count as f32 + padding;
-
-            c.rect(Rect {
-                fill: Some(bg_color.into()),
-                top,
-                width: c.width(),
-                height: row_height,
-                ..Default::default()
-            });
-            if !self.border_color.is_transparent() {
-                c.line(Line {
-                    color: Some(self.border_color),
-                    stroke_width: 1.0,
+            if segments == [(0.0, right)] {
+                segments[0].1 = c.width();
+            }
+            for (left, width) in segments.iter() {
+                c.rect(Rect {
+                    fill: Some(bg_color.into()),
+                    left: *left,
                     top,
-                    right: c.width(),
-                    bottom: top,
+                    width: *width,
+                    height: row_height,
                     ..Default::default()
                 });
             }
-            for (j, content_list) in items.iter().enumerate() {
-                // spans[j] can not be nil
-                let span_width = spans[j];
+            if !self.border_color.is_transparent() {
+                for (left, width) in segments.iter() {
+                    c.line(Line {
+                        color: Some(self.border_color),
+                        stroke_width: 1.0,
+                        left: *left,
+                        top,
+                        right: *left + *width,
+                        bottom: top,
+                        ..Default::default()
+                    });
+                }
+            }
+            for (j, content_list) in layout.contents[i].iter().enumerate() {
+                let (row_span, column_span) = layout.cell_spans[i][j];
+                if column_span == 0 {
+                    continue;
+                }
+                let left = lefts[j];
+                let span_width: f32 = spans[j..j + column_span].iter().sum();
+                let cell_height: f32 = layout.heights[i..i + row_span].iter().sum();
+
+                // cells merged over several rows are centered vertically
+                let mut text_top = top;
+                if row_span > 1 {
+                    c.rect(Rect {
+                        fill: Some(bg_color.into()),
+                        left,
+                        top,
+                        width: span_width,
+                        height: cell_height,
+                        ..Default::default()
+                    });
+                    let content_height = line_height * content_list.len().max(1) as f32 + padding;
+                    text_top += ((cell_height - content_height) / 2.0).max(0.0);
+                }
 
                 let mut cell_font_color = font_color;
                 let mut cell_font_weight = font_weight.clone();
 
                 // get the table cell's background color
-                if let Some(cell_style) = find_cell_style(i, j) {
+                if let Some(cell_style) = self.get_cell_style(layout, i, j) {
                     if let Some(value) = cell_style.font_color {
                         cell_font_color = value;
                     }
-                    if let Some(ref value) = cell_style.font_weight {
-                        cell_font_weight = Some(value.clone());
+                    if cell_style.font_weight.is_some() {
+                        cell_font_weight = cell_style.font_weight;
                     }
                     if let Some(value) = cell_style.background_color {
                         c.rect(Rect {
@@ -554,29 +1347,134 @@ impl TableChart {
                             left,
                             top: top + 1.0,
                             width: span_width,
-                            height: row_height - 1.0,
+                            height: cell_height - 1.0,
                             ..Default::default()
                         });
                     }
                 }
 
+                // space in front of the text, taken by a delta arrow
+                let mut text_offset = 0.0;
+                let mut arrow = None;
+                if let Some(renderer) = self.find_cell_renderer(layout, i, j) {
+                    let width = span_width - row_padding.left - row_padding.right;
+                    let height = cell_height - padding;
+                    let text = &layout.rows[i][j];
+                    let value = parse_cell_value(text);
+                    let range = layout.column_ranges[j];
+                    let mut cell = c.child(Box {
+                        left: left + row_padding.left,
+                        top: top + row_padding.top,
+                        ..Default::default()
+                    });
+                    match renderer {
+                        TableCellRenderer::Sparkline(chart) => {
+                            let values = parse_cell_values(text);
+                            chart.render_values(&mut cell, &values, width, height);
+                        }
+                        TableCellRenderer::DataBar { color, min, max } => {
+                            if let Some(value) = value {
+                                let min = min.unwrap_or(range.map_or(0.0, |r| r.0.min(0.0)));
+                                let max = max.unwrap_or(range.map_or(0.0, |r| r.1));
+                                if max > min {
+                                    let ratio = ((value - min) / (max - min)).clamp(0.0, 1.0);
+                                    cell.rect(Rect {
+                                        fill: Some((*color).into()),
+                                        width: width * ratio,
+                                        height,
+                                        ..Default::default()
+                                    });
+                                }
+                            }
+                        }
+                        TableCellRenderer::ColorScale {
+                            min_color,
+                            mid_color,
+                            max_color,
+                        } => {
+                            if let (Some(value), Some((min, max))) = (value, range) {
+                                let ratio = if max > min {
+                                    (value - min) / (max - min)
+                                } else {
+                                    0.5
+                                };
+                                c.rect(Rect {
+                                    fill: Some(
+                                        get_scale_color(*min_color, *mid_color, *max_color, ratio)
+                                            .into(),
+                                    ),
+                                    left,
+                                    top: top + 1.0,
+                                    width: span_width,
+                                    height: cell_height - 1.0,
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        TableCellRenderer::Delta {
+                            increase_color,
+                            decrease_color,
+                        } => {
+                            if let Some(value) = value
+                                && value != 0.0
+                            {
+                                cell_font_color = if value > 0.0 {
+                                    *increase_color
+                                } else {
+                                    *decrease_color
+                                };
+                                let arrow_size = font_size * 0.6;
+                                text_offset = arrow_size + 4.0;
+                                arrow = Some((value > 0.0, arrow_size));
+                            }
+                        }
+                    }
+                }
+
+                // cells merged over several columns are centered
+                let align = if column_span > 1 {
+                    Align::Center
+                } else {
+                    self.text_aligns.get(j).cloned().unwrap_or(Align::Left)
+                };
                 for (index, item) in content_list.iter().enumerate() {
                     let mut dx = None;
                     if let Ok(measurement) =
                         measure_text_width_family(&self.font_family, font_size, item)
                     {
-                        let mut align = Align::Left;
-                        if let Some(value) = self.text_aligns.get(j) {
-                            value.clone_into(&mut align);
-                        }
                         let text_width = measurement.width();
                         let text_max_width = span_width - row_padding.left - row_padding.right;
+                        let text_width = text_width + text_offset;
                         dx = match align {
                             Align::Center => Some((text_max_width - text_width) / 2.0),
                             Align::Right => Some(text_max_width - text_width),
                             Align::Left => None,
                         };
                     }
+                    if let Some((is_up, arrow_size)) = arrow.take() {
+                        let x = left + row_padding.left + dx.unwrap_or_default();
+                        let y = text_top + row_padding.top + (line_height - arrow_size) / 2.0;
+                        let points = if is_up {
+                            vec![
+                                (x, y + arrow_size).into(),
+                                (x + arrow_size / 2.0, y).into(),
+                                (x + arrow_size, y + arrow_size).into(),
+                            ]
+                        } else {
+                            vec![
+                                (x, y).into(),
+                                (x + arrow_size, y).into(),
+                                (x + arrow_size / 2.0, y + arrow_size).into(),
+                            ]
+                        };
+                        c.polygon(Polygon {
+                            color: Some(cell_font_color),
+                            fill: Some(cell_font_color),
+                            points,
+                            ..Default::default()
+                        });
+                        dx = Some(dx.unwrap_or_default() + text_offset);
+                    }
                     c.child(row_padding.clone()).text(Text {
                         text: item.to_string(),
                         font_weight: cell_font_weight.clone(),
@@ -586,13 +1484,10 @@ impl TableChart {
                         line_height: Some(line_height),
                         dx,
                         x: Some(left),
-                        y: Some(top + line_height * index as f32),
+                        y: Some(text_top + line_height * index as f32),
                         ..Default::default()
                     });
                 }
-
-                right += span_width;
-                left = right
             }
             top += row_height;
         }
@@ -616,8 +1511,8 @@ impl TableChart {
 
 #[cfg(test)]
 mod tests {
-    use super::{TableCellStyle, TableChart};
-    use crate::{Align, THEME_ANT, THEME_DARK, THEME_GRAFANA};
+    use super::{TableAggregation, TableCellStyle, TableChart, parse_cell_value};
+    use crate::{Align, ColorScheme, THEME_ANT, THEME_DARK, THEME_GRAFANA};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -833,4 +1728,225 @@ mod tests {
             table_chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn table_sparkline_renderer() {
+        let table_chart = TableChart::from_json(
+            r###"{
+                "cell_renderers": [
+                    null,
+                    {"type": "sparkline", "last_point_show": true},
+                    {"type": "sparkline", "category": "bar", "series_colors": ["#EE6666"]}
+                ],
+                "data": [
+                    ["Name", "Trend", "Volume"],
+                    ["Datadog", "90 92 95 94 97", "10,12,8,15"],
+                    ["Hashicorp", "35, 33, -, 30, 28.66", "5,6,null,4"]
+                ]
+            }"###,
+        )
+        .unwrap();
+        let svg = table_chart.svg().unwrap();
+        // the header is text, the cells are drawn
+        assert!(svg.contains("\nTrend\n"));
+        assert!(!svg.contains("90 92 95 94 97"));
+        // the gap splits the second line in two
+        assert_eq!(3, svg.matches("<polyline").count());
+        assert_eq!(2, svg.matches(r#"r="2""#).count());
+        assert_eq!(7, svg.matches(r##"fill="#EE6666""##).count());
+        // one line rows
+        assert!(svg.starts_with(r#"<svg width="600" height="126""#));
+    }
+
+    #[test]
+    fn table_rich_cells() {
+        let table_chart = TableChart::from_json(
+            r###"{
+                "text_aligns": ["left", "right", "left", "right"],
+                "cell_renderers": [
+                    null,
+                    {"type": "color_scale", "min_color": "#FF0000", "max_color": "#00FF00"},
+                    {"type": "data_bar", "color": "#0000FF"},
+                    {"type": "delta"}
+                ],
+                "data": [
+                    ["Name", "Price", "Volume", "Change"],
+                    ["Datadog", 90, "$1,000", "+7.49%"],
+                    ["Hashicorp", "30", "$4,000", "-9.25%"],
+                    ["Gitlab", "60", "$2,000", "0%"]
+                ]
+            }"###,
+        )
+        .unwrap();
+        let svg = table_chart.svg().unwrap();
+        // the text is kept
+        assert!(svg.contains("\n$1,000\n"));
+        assert!(svg.contains("\n90\n"));
+        // color scale from the smallest to the largest price
+        assert!(svg.contains(r##"fill="#00FF00""##));
+        assert!(svg.contains(r##"fill="#FF0000""##));
+        assert!(svg.contains(r##"fill="#808000""##));
+        // data bars proportional to the largest volume
+        assert!(svg.contains(r##"width="32.5" height="30" fill="#0000FF""##));
+        assert!(svg.contains(r##"width="130" height="30" fill="#0000FF""##));
+        assert!(svg.contains(r##"width="65" height="30" fill="#0000FF""##));
+        // one up and one down arrow, no arrow for zero
+        assert_eq!(2, svg.matches("<polygon").count());
+        assert!(svg.contains(r##"fill="#91CC75""##));
+        assert!(svg.contains(r##"fill="#EE6666""##));
+    }
+
+    #[test]
+    fn table_merged_cells() {
+        let table_chart = TableChart::from_json(
+            r###"{
+                "title_text": "Quarterly Report",
+                "width": 600,
+                "header_row_count": 2,
+                "outlined": true,
+                "data": [
+                    ["Region", "City", "Revenue", "", "Cost", ""],
+                    ["", "", "Q1", "Q2", "Q1", "Q2"],
+                    ["North", "Beijing", "1,200", "1,350", "800", "820"],
+                    ["", "Harbin", "300", "310", "220", "230"],
+                    ["South", "Guangzhou", "980", "1,020", "600", "640"],
+                    ["", "Shenzhen", "1,500", "1,720", "900", "950"],
+                    ["", "Xiamen", "420", "460", "300", "310"]
+                ],
+                "merged_cells": [
+                    {"indexes": [0, 0], "row_span": 2},
+                    {"indexes": [0, 1], "row_span": 2},
+                    {"indexes": [0, 2], "column_span": 2},
+                    {"indexes": [0, 4], "column_span": 2},
+                    {"indexes": [2, 0], "row_span": 2},
+                    {"indexes": [4, 0], "row_span": 3}
+                ],
+                "text_aligns": ["left", "left", "right", "right", "right", "right"],
+                "summary_label": "Total",
+                "summary_formatter": "{t}",
+                "summary_aggregations": [null, "count", "sum", "sum", "sum", "sum"]
+            }"###,
+        )
+        .unwrap();
+        let svg = table_chart.svg().unwrap();
+        assert!(svg.contains("\n4,400\n"));
+        assert!(svg.contains("\n2,950\n"));
+        assert_eq!(
+            include_str!("../../asset/table_chart/merged_cells.svg"),
+            svg
+        );
+    }
+
+    #[test]
+    fn table_pages() {
+        let mut data = vec![vec!["Name".to_string(), "Value".to_string()]];
+        for i in 0..20 {
+            data.push(vec![format!("Item {i}"), format!("{}", i * 10)]);
+        }
+        let mut table_chart = TableChart::new(data);
+        table_chart.title_text = "Items".to_string();
+        table_chart.summary_aggregations = vec![None, Some(TableAggregation::Avg)];
+        table_chart.summary_label = "Average".to_string();
+        // one page without a maximum height
+        assert_eq!(1, table_chart.svg_pages().unwrap().len());
+
+        table_chart.max_height = 300.0;
+        let pages = table_chart.svg_pages().unwrap();
+        assert_eq!(5, pages.len());
+        for page in pages.iter() {
+            // the header is repeated on every page
+            assert!(page.contains("\nName\n"));
+            let height: f32 = page
+                .split("height=\"")
+                .nth(1)
+                .and_then(|v| v.split('"').next())
+                .and_then(|v| v.parse().ok())
+                .unwrap();
+            assert!(height <= 300.0);
+        }
+        assert!(pages[3].contains("\nItem 19\n"));
+        // the summary row does not fit the last page and gets its own
+        assert!(!pages[4].contains("\nItem "));
+        assert!(pages[4].contains("\nAverage\n"));
+        assert!(pages[4].contains("\n95\n"));
+
+        // every page defines and uses the color scheme variables
+        table_chart.color_scheme = Some(ColorScheme {
+            light: "light".to_string(),
+            dark: "dark".to_string(),
+        });
+        let pages = table_chart.svg_pages().unwrap();
+        assert_eq!(5, pages.len());
+        for page in pages.iter() {
+            assert!(page.contains("@media (prefers-color-scheme: dark)"));
+            assert!(page.contains(r#"fill="var(--charts-background-color)""#));
+        }
+    }
+
+    #[test]
+    fn table_parse_cell_value() {
+        assert_eq!(Some(1234.5), parse_cell_value("$1,234.5"));
+        assert_eq!(Some(-7.49), parse_cell_value("-7.49%"));
+        assert_eq!(Some(-7.0), parse_cell_value("-$7"));
+        assert_eq!(Some(12.0), parse_cell_value("12 items"));
+        assert_eq!(Some(3.0), parse_cell_value("3e kg"));
+        assert_eq!(Some(1.0), parse_cell_value("1-2"));
+        assert_eq!(Some(9.5), parse_cell_value(" € 9.50 EUR"));
+        assert_eq!(None, parse_cell_value("v1.2"));
+        assert_eq!(None, parse_cell_value("N/A"));
+        assert_eq!(None, parse_cell_value("-"));
+    }
+
+    #[test]
+    fn table_cell_rules() {
+        let table_chart = TableChart::from_json(
+            r###"{
+                "cell_rules": [
+                    {"column": 1, "condition": "top", "value": 1, "background_color": "#00FF00"},
+                    {"column": 1, "condition": "bottom", "value": 2, "background_color": "#FF0000"},
+                    {"column": 1, "condition": "between", "value": [20, 40], "font_weight": "bold"},
+                    {"column": 2, "condition": "lt", "value": 0, "font_color": "#FF00FF"},
+                    {"column": 2, "condition": "gt", "value": 5, "font_color": "#00FFFF"},
+                    {"column": 0, "condition": "regex", "value": "^Git", "font_color": "#0000FF"},
+                    {"column": 0, "condition": "eq", "value": "Datadog", "font_color": "#FFFF00"}
+                ],
+                "cell_styles": [
+                    {"indexes": [4, 1], "background_color": "#123456"}
+                ],
+                "data": [
+                    ["Name", "Price", "Change"],
+                    ["Datadog", "$97", "+7.49%"],
+                    ["Hashicorp", "$28", "-9.25%"],
+                    ["Gitlab", "$51", "+2.1%"],
+                    ["Github", "$10", "0%"]
+                ]
+            }"###,
+        )
+        .unwrap();
+        let svg = table_chart.svg().unwrap();
+        // the largest price, the two smallest with the explicit cell style winning
+        assert_eq!(1, svg.matches(r##"fill="#00FF00""##).count());
+        assert_eq!(1, svg.matches(r##"fill="#FF0000""##).count());
+        assert_eq!(1, svg.matches(r##"fill="#123456""##).count());
+        // the header is never styled by the rules
+        assert_eq!(1, svg.matches(r#"font-weight="bold""#).count());
+        assert_eq!(1, svg.matches(r##"fill="#FF00FF""##).count());
+        assert_eq!(1, svg.matches(r##"fill="#00FFFF""##).count());
+        assert_eq!(2, svg.matches(r##"fill="#0000FF""##).count());
+        assert_eq!(1, svg.matches(r##"fill="#FFFF00""##).count());
+
+        let err = TableChart::from_json(
+            r###"{"cell_rules": [{"column": 0, "condition": "like", "value": 1}]}"###,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(r#""like""#));
+        let err = TableChart::from_json(
+            r###"{
+                "cell_rules": [{"column": 0, "condition": "regex", "value": "("}],
+                "data": [["Name"], ["Datadog"]]
+            }"###,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(r#"regex "(" is invalid"#));
+    }
 }
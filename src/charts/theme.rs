@@ -12,6 +12,7 @@
 
 use super::color::Color;
 use super::common::Align;
+use super::error::{Error, Result};
 use super::font::DEFAULT_FONT_FAMILY;
 use super::util::Box;
 use arc_swap::ArcSwap;
@@ -173,6 +174,83 @@ pub struct Theme {
     pub table_body_colors: Vec<Color>,
     /// Default table border color.
     pub table_border_color: Color,
+
+    // gauge
+    /// Default color of the gauge track behind the value arc.
+    pub gauge_background_arc_color: Color,
+
+    // candlestick
+    /// Default fill color of rising candlesticks.
+    pub candlestick_up_color: Color,
+    /// Default border color of rising candlesticks.
+    pub candlestick_up_border_color: Color,
+    /// Default fill color of falling candlesticks.
+    pub candlestick_down_color: Color,
+    /// Default border color of falling candlesticks.
+    pub candlestick_down_border_color: Color,
+
+    // waterfall
+    /// Default color of waterfall increases; a zero color follows the first
+    /// series color.
+    pub waterfall_increase_color: Color,
+    /// Default color of waterfall decreases.
+    pub waterfall_decrease_color: Color,
+    /// Default color of waterfall totals; a zero color follows the second
+    /// series color.
+    pub waterfall_total_color: Color,
+
+    // calendar
+    /// Default calendar color of the smallest value.
+    pub calendar_min_color: Color,
+    /// Default calendar color of the largest value.
+    pub calendar_max_color: Color,
+    /// Default calendar color of the days without data.
+    pub calendar_empty_color: Color,
+
+    // heatmap
+    /// Default heatmap color of the smallest value.
+    pub heatmap_min_color: Color,
+    /// Default heatmap color of the largest value.
+    pub heatmap_max_color: Color,
+    /// Default heatmap label color on the cells of small values.
+    pub heatmap_min_font_color: Color,
+    /// Default heatmap label color on the cells of large values.
+    pub heatmap_max_font_color: Color,
+
+    // stat
+    /// Default color of a stat card rising from its previous value.
+    pub stat_increase_color: Color,
+    /// Default color of a stat card falling from its previous value.
+    pub stat_decrease_color: Color,
+
+    // sparkline
+    /// Default color of the sparkline minimum point.
+    pub sparkline_min_point_color: Color,
+    /// Default color of the sparkline maximum point.
+    pub sparkline_max_point_color: Color,
+    /// Default color of the sparkline reference band.
+    pub sparkline_reference_band_color: Color,
+
+    // bullet
+    /// Default bullet range band colors, from the worst range to the best.
+    pub bullet_range_colors: Vec<Color>,
+
+    // table cell renderers
+    /// Default color of table data bars; a zero color follows the first
+    /// series color.
+    pub table_data_bar_color: Color,
+    /// Default table color scale color of the smallest value.
+    pub table_color_scale_min_color: Color,
+    /// Default table color scale color of the largest value.
+    pub table_color_scale_max_color: Color,
+    /// Default color of positive table deltas.
+    pub table_delta_increase_color: Color,
+    /// Default color of negative table deltas.
+    pub table_delta_decrease_color: Color,
+
+    // parallel
+    /// Default colors of the parallel chart `color_dimension` scale.
+    pub parallel_color_scale: Vec<Color>,
 }
 
 static LIGHT_THEME: LazyLock<Theme> = LazyLock::new(|| {
@@ -237,6 +315,51 @@ static LIGHT_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (242, 243, 245).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (229, 230, 235).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -304,6 +427,51 @@ static DARK_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: bg_color,
         table_body_colors: vec![bg_color.with_alpha(230)],
         table_border_color: (100, 100, 100).into(),
+
+        gauge_background_arc_color: (71, 71, 83).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (14, 68, 41).into(),
+        calendar_max_color: (57, 211, 83).into(),
+        calendar_empty_color: (40, 40, 45, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (255, 255, 255, 30).into(),
+
+        bullet_range_colors: vec![
+            (95, 95, 100).into(),
+            (75, 75, 80).into(),
+            (58, 58, 62).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -370,6 +538,51 @@ static ANT_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (250, 250, 250).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -438,6 +651,51 @@ static VINTAGE_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (250, 250, 250).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -504,6 +762,51 @@ static SHINE_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (250, 250, 250).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -568,6 +871,51 @@ static WALDEN_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (250, 250, 250).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -632,6 +980,51 @@ static WESTEROS_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: (250, 250, 250).into(),
         table_body_colors: vec![(255, 255, 255).into()],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (230, 230, 230).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (235, 237, 240).into(),
+        calendar_max_color: (33, 110, 57).into(),
+        calendar_empty_color: (235, 237, 240, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -699,6 +1092,51 @@ static CHALK_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: bg_color,
         table_body_colors: vec![bg_color.with_alpha(230)],
         table_border_color: (100, 100, 100).into(),
+
+        gauge_background_arc_color: (71, 81, 94).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (14, 68, 41).into(),
+        calendar_max_color: (57, 211, 83).into(),
+        calendar_empty_color: (40, 40, 45, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (0, 0, 0, 20).into(),
+
+        bullet_range_colors: vec![
+            (190, 190, 190).into(),
+            (215, 215, 215).into(),
+            (238, 238, 238).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -766,6 +1204,51 @@ static GRAFANA_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: bg_color,
         table_body_colors: vec![bg_color.with_alpha(230)],
         table_border_color: (239, 239, 244).into(),
+
+        gauge_background_arc_color: (68, 67, 67).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (14, 68, 41).into(),
+        calendar_max_color: (57, 211, 83).into(),
+        calendar_empty_color: (40, 40, 45, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (255, 255, 255, 30).into(),
+
+        bullet_range_colors: vec![
+            (95, 95, 100).into(),
+            (75, 75, 80).into(),
+            (58, 58, 62).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -833,6 +1316,51 @@ static SHADCN_THEME: LazyLock<Theme> = LazyLock::new(|| {
         table_header_color: bg_color.with_alpha(230),
         table_body_colors: vec![bg_color],
         table_border_color: (39, 39, 42).into(),
+
+        gauge_background_arc_color: (39, 39, 42).into(),
+
+        candlestick_up_color: (236, 0, 0).into(),
+        candlestick_up_border_color: (138, 0, 0).into(),
+        candlestick_down_color: (0, 218, 60).into(),
+        candlestick_down_border_color: (0, 143, 40).into(),
+
+        waterfall_increase_color: Color::default(),
+        waterfall_decrease_color: (238, 102, 102).into(),
+        waterfall_total_color: Color::default(),
+
+        calendar_min_color: (14, 68, 41).into(),
+        calendar_max_color: (57, 211, 83).into(),
+        calendar_empty_color: (40, 40, 45, 180).into(),
+
+        heatmap_min_color: (240, 217, 156).into(),
+        heatmap_max_color: (191, 68, 76).into(),
+        heatmap_min_font_color: (70, 70, 70).into(),
+        heatmap_max_font_color: (238, 238, 238).into(),
+
+        stat_increase_color: (145, 204, 117).into(),
+        stat_decrease_color: (238, 102, 102).into(),
+
+        sparkline_min_point_color: (238, 102, 102).into(),
+        sparkline_max_point_color: (145, 204, 117).into(),
+        sparkline_reference_band_color: (255, 255, 255, 30).into(),
+
+        bullet_range_colors: vec![
+            (95, 95, 100).into(),
+            (75, 75, 80).into(),
+            (58, 58, 62).into(),
+        ],
+
+        table_data_bar_color: Color::default(),
+        table_color_scale_min_color: (238, 102, 102, 160).into(),
+        table_color_scale_max_color: (145, 204, 117, 160).into(),
+        table_delta_increase_color: (145, 204, 117).into(),
+        table_delta_decrease_color: (238, 102, 102).into(),
+
+        parallel_color_scale: vec![
+            (246, 239, 166).into(),
+            (216, 130, 115).into(),
+            (191, 68, 76).into(),
+        ],
     }
 });
 
@@ -878,3 +1406,391 @@ pub fn list_theme_name() -> Vec<String> {
 pub fn get_default_theme_name() -> String {
     LIGHT_THEME_NAME.to_string()
 }
+
+// ── Theme files ──────────────────────────────────────────────────────────────
+
+/// A theme field as read from and written to a theme file.
+trait ThemeValue: Sized {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self>;
+    fn to_theme_value(&self) -> serde_json::Value;
+}
+
+impl ThemeValue for bool {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        value.as_bool()
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        (*self).into()
+    }
+}
+
+impl ThemeValue for f32 {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        value.as_f64().map(|v| v as f32)
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        // Widen through the shortest decimal form, keeping `0.1` from turning
+        // into `0.10000000149011612`.
+        self.to_string().parse::<f64>().unwrap_or_default().into()
+    }
+}
+
+impl ThemeValue for usize {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        value.as_u64().map(|v| v as usize)
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        (*self as u64).into()
+    }
+}
+
+impl ThemeValue for String {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        value.as_str().map(|v| v.to_string())
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        self.as_str().into()
+    }
+}
+
+impl ThemeValue for Color {
+    /// Accepts any CSS color, see [`Color::parse`].
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        Color::parse(value.as_str()?).ok()
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        if self.is_nontransparent() {
+            self.hex().into()
+        } else {
+            format!("{}{:02X}", self.hex(), self.a).into()
+        }
+    }
+}
+
+impl ThemeValue for Box {
+    /// Accepts one number for every side or an object of the sides.
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        if let Some(v) = value.as_f64() {
+            return Some((v as f32).into());
+        }
+        let obj = value.as_object()?;
+        let mut b = Box::default();
+        for (key, v) in obj {
+            let v = f32::from_theme_value(v)?;
+            match key.as_str() {
+                "left" => b.left = v,
+                "top" => b.top = v,
+                "right" => b.right = v,
+                "bottom" => b.bottom = v,
+                _ => return None,
+            }
+        }
+        Some(b)
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "left": self.left,
+            "top": self.top,
+            "right": self.right,
+            "bottom": self.bottom,
+        })
+    }
+}
+
+impl ThemeValue for Align {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        match value.as_str()? {
+            "left" => Some(Align::Left),
+            "center" => Some(Align::Center),
+            "right" => Some(Align::Right),
+            _ => None,
+        }
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        match self {
+            Align::Left => "left",
+            Align::Center => "center",
+            Align::Right => "right",
+        }
+        .into()
+    }
+}
+
+impl<T: ThemeValue> ThemeValue for Option<T> {
+    /// `null` clears the field.
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        if value.is_null() {
+            return Some(None);
+        }
+        T::from_theme_value(value).map(Some)
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        self.as_ref()
+            .map(|v| v.to_theme_value())
+            .unwrap_or_default()
+    }
+}
+
+impl<T: ThemeValue> ThemeValue for Vec<T> {
+    fn from_theme_value(value: &serde_json::Value) -> Option<Self> {
+        value.as_array()?.iter().map(T::from_theme_value).collect()
+    }
+    fn to_theme_value(&self) -> serde_json::Value {
+        self.iter().map(|v| v.to_theme_value()).collect()
+    }
+}
+
+/// Generates the field by field reading and writing of theme files.
+macro_rules! theme_fields {
+    ($($field:ident),* $(,)?) => {
+        impl Theme {
+            fn set_file_value(&mut self, key: &str, value: &serde_json::Value) -> Result<()> {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = ThemeValue::from_theme_value(value).ok_or_else(|| {
+                            Error::Params {
+                                message: format!("theme field \"{key}\" is invalid"),
+                            }
+                        })?;
+                    })*
+                    _ => {
+                        return Err(Error::Params {
+                            message: format!("theme field \"{key}\" is unknown"),
+                        });
+                    }
+                }
+                Ok(())
+            }
+
+            fn to_file_value(&self) -> serde_json::Value {
+                let mut map = serde_json::Map::new();
+                $(map.insert(stringify!($field).to_string(), self.$field.to_theme_value());)*
+                map.into()
+            }
+        }
+    };
+}
+
+theme_fields!(
+    is_light,
+    font_family,
+    margin,
+    width,
+    height,
+    background_color,
+    title_font_size,
+    title_font_color,
+    title_font_weight,
+    title_margin,
+    title_align,
+    title_height,
+    sub_title_font_size,
+    sub_title_font_color,
+    sub_title_margin,
+    sub_title_align,
+    sub_title_height,
+    legend_font_size,
+    legend_font_color,
+    legend_align,
+    legend_margin,
+    x_axis_font_size,
+    x_axis_stroke_color,
+    x_axis_font_color,
+    x_axis_name_gap,
+    x_axis_height,
+    y_axis_font_size,
+    y_axis_font_color,
+    y_axis_stroke_color,
+    y_axis_split_number,
+    y_axis_name_gap,
+    grid_stroke_color,
+    grid_stroke_width,
+    series_stroke_width,
+    series_label_font_size,
+    series_label_font_color,
+    series_colors,
+    table_header_color,
+    table_body_colors,
+    table_border_color,
+    gauge_background_arc_color,
+    candlestick_up_color,
+    candlestick_up_border_color,
+    candlestick_down_color,
+    candlestick_down_border_color,
+    waterfall_increase_color,
+    waterfall_decrease_color,
+    waterfall_total_color,
+    calendar_min_color,
+    calendar_max_color,
+    calendar_empty_color,
+    heatmap_min_color,
+    heatmap_max_color,
+    heatmap_min_font_color,
+    heatmap_max_font_color,
+    stat_increase_color,
+    stat_decrease_color,
+    sparkline_min_point_color,
+    sparkline_max_point_color,
+    sparkline_reference_band_color,
+    bullet_range_colors,
+    table_data_bar_color,
+    table_color_scale_min_color,
+    table_color_scale_max_color,
+    table_delta_increase_color,
+    table_delta_decrease_color,
+    parallel_color_scale,
+);
+
+impl Theme {
+    /// Creates a theme from a JSON theme file. Colors are CSS colors and
+    /// margins a number or an object of the sides. The theme extends the
+    /// registered theme named by `"extends"` (the light theme by default)
+    /// and overrides only the fields present, e.g.
+    /// `{"extends": "grafana", "series_colors": ["#7eb26d", "#eab839"]}`.
+    pub fn from_json(json: &str) -> Result<Theme> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Theme::from_file_value(&value)
+    }
+
+    /// Creates a theme from a TOML theme file, with the same fields as
+    /// [`Theme::from_json`].
+    #[cfg(feature = "toml")]
+    pub fn from_toml(data: &str) -> Result<Theme> {
+        let value: serde_json::Value = toml::from_str(data).map_err(|err| Error::Params {
+            message: err.to_string(),
+        })?;
+        Theme::from_file_value(&value)
+    }
+
+    /// Converts the theme to a JSON theme file holding every field, which
+    /// [`Theme::from_json`] reads back. A registered theme is exported with
+    /// `get_theme(name).to_json()`.
+    pub fn to_json(&self) -> String {
+        // Serializing a JSON value can not fail.
+        serde_json::to_string_pretty(&self.to_file_value()).unwrap_or_default()
+    }
+
+    fn from_file_value(value: &serde_json::Value) -> Result<Theme> {
+        let obj = value.as_object().ok_or_else(|| Error::Params {
+            message: "theme must be an object".to_string(),
+        })?;
+        let mut theme = match obj.get("extends") {
+            None => LIGHT_THEME.clone(),
+            Some(extends) => {
+                let name = extends.as_str().ok_or_else(|| Error::Params {
+                    message: "theme field \"extends\" is invalid".to_string(),
+                })?;
+                let theme = THEME_MAP.load().get(name).cloned();
+                let theme = theme.ok_or_else(|| Error::Params {
+                    message: format!("theme \"{name}\" to extend is not found"),
+                })?;
+                (*theme).clone()
+            }
+        };
+        for (key, value) in obj {
+            if key != "extends" {
+                theme.set_file_value(key, value)?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Theme, get_theme, list_theme_name};
+    use crate::{Align, Color};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn theme_from_json() {
+        let theme = Theme::from_json(
+            r###"{
+                "extends": "grafana",
+                "title_font_size": 20,
+                "title_align": "left",
+                "margin": {"left": 10, "top": 5},
+                "grid_stroke_color": "#ffffff20",
+                "series_colors": ["#7eb26d", "#eab839"]
+            }"###,
+        )
+        .unwrap();
+        let grafana = get_theme("grafana");
+        assert_eq!(20.0, theme.title_font_size);
+        assert_eq!(Align::Left, theme.title_align);
+        assert_eq!(10.0, theme.margin.left);
+        assert_eq!(0.0, theme.margin.right);
+        assert_eq!(Color::white().with_alpha(32), theme.grid_stroke_color);
+        assert_eq!(2, theme.series_colors.len());
+        assert_eq!(grafana.background_color, theme.background_color);
+        assert!(!theme.is_light);
+
+        // Without "extends" the light theme is the base.
+        let theme = Theme::from_json(r#"{"width": 800}"#).unwrap();
+        assert_eq!(800.0, theme.width);
+        assert!(theme.is_light);
+    }
+
+    #[test]
+    fn theme_from_json_error() {
+        for (json, message) in [
+            (
+                r#"{"title_font_color": "reddish"}"#,
+                r#"theme field "title_font_color" is invalid"#,
+            ),
+            (
+                r##"{"series_colors": ["#5470c6", 3]}"##,
+                r#"theme field "series_colors" is invalid"#,
+            ),
+            (
+                r#"{"legend_align": "middle"}"#,
+                r#"theme field "legend_align" is invalid"#,
+            ),
+            (
+                r#"{"title_size": 12}"#,
+                r#"theme field "title_size" is unknown"#,
+            ),
+            (
+                r#"{"extends": "unknown"}"#,
+                r#"theme "unknown" to extend is not found"#,
+            ),
+        ] {
+            assert_eq!(
+                format!("Params is invalid: {message}"),
+                Theme::from_json(json).unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn theme_to_json() {
+        for name in list_theme_name() {
+            let json = get_theme(&name).to_json();
+            assert_eq!(json, Theme::from_json(&json).unwrap().to_json());
+        }
+        let json = get_theme("light").to_json();
+        assert!(json.contains(r##""y_axis_stroke_color": "#00000000""##));
+        assert!(json.contains(r#""grid_stroke_width": 1.0"#));
+        assert!(json.contains(r#""title_margin": null"#));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn theme_from_toml() {
+        let theme = Theme::from_toml(
+            r##"
+extends = "dark"
+title_font_size = 20
+series_colors = ["#7eb26d", "#eab839"]
+
+[margin]
+left = 10
+"##,
+        )
+        .unwrap();
+        assert_eq!(20.0, theme.title_font_size);
+        assert_eq!(10.0, theme.margin.left);
+        assert_eq!(2, theme.series_colors.len());
+        assert!(!theme.is_light);
+    }
+}
@@ -11,14 +11,16 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, get_y_axis_config, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
+use super::date::{format_timestamp, get_time_ticks, parse_timestamp};
 use super::params::*;
 use super::theme::{get_default_theme_name, get_theme};
 use super::util::*;
+use crate::charts::measure_text_width_family;
 
 // ── ThemeRiverChart ──────────────────────────────────────────────────────────
 
@@ -27,17 +29,31 @@ use super::util::*;
 /// composition and its evolution over time are both easy to read.
 ///
 /// Data reuses the shared model: `series_list` holds one [`Series`] per stream
-/// and `x_axis_data` holds the time-axis labels.
+/// and `x_axis_data` holds the time-axis labels, or `time_data` the
+/// timestamps of a continuous time axis.
 #[derive(Clone, Debug, Default)]
 pub struct ThemeRiverChart {
     /// The shared chart options (size, series, title/legend, axes); exposed
     /// directly on the chart through `Deref`, e.g. `chart.title_text`.
     pub base: ChartBase,
-    y_axis_configs: Vec<YAxisConfig>,
+    /// The first config's `axis_min`/`axis_max` widen the value range the
+    /// streams are scaled to; the axis itself is not drawn.
+    pub y_axis_configs: Vec<YAxisConfig>,
 
     // theme-river-specific
     /// Opacity of the stream bands in `0.0..=1.0`. Default: 0.85.
     pub stream_opacity: f32,
+    /// Timestamps (seconds since the Unix epoch, UTC) of the data points,
+    /// strictly increasing. When set, the steps are placed by time on a
+    /// continuous axis with calendar-aligned ticks instead of evenly by
+    /// `x_axis_data`.
+    pub time_data: Vec<i64>,
+    /// Formatter of the time ticks with `%Y`, `%m`, `%d`, `%H`, `%M` and
+    /// `%S`. Defaults to a format matching the tick interval.
+    pub time_formatter: String,
+    /// Baseline the streams are stacked from. Default (`None`): silhouette,
+    /// centered around the middle of the chart.
+    pub stack_baseline: Option<StackBaseline>,
 }
 
 impl std::ops::Deref for ThemeRiverChart {
@@ -91,12 +107,35 @@ impl ThemeRiverChart {
         if let Some(v) = get_f32_from_value(&value, "stream_opacity") {
             c.stream_opacity = v;
         }
+        if let Some(arr) = value.get("time_data").and_then(|v| v.as_array()) {
+            for item in arr {
+                let timestamp = match item {
+                    serde_json::Value::String(v) => parse_timestamp(v),
+                    _ => item.as_f64().map(|v| v as i64),
+                };
+                let Some(timestamp) = timestamp else {
+                    return Err(canvas::Error::Params {
+                        message: format!("time_data value {item} is invalid"),
+                    });
+                };
+                c.time_data.push(timestamp);
+            }
+        }
+        if let Some(v) = get_string_from_value(&value, "time_formatter") {
+            c.time_formatter = v;
+        }
+        if let Some(v) = get_stack_baseline_from_value(&value, "stack_baseline")? {
+            c.stack_baseline = Some(v);
+        }
         c.fill_default();
         Ok(c)
     }
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
@@ -121,10 +160,33 @@ impl ThemeRiverChart {
         if t_count == 0 {
             return c.svg();
         }
+        let times = if self.time_data.is_empty() {
+            None
+        } else if self.time_data.len() < t_count {
+            return Err(canvas::Error::Params {
+                message: format!(
+                    "time_data has {} values for {t_count} data points",
+                    self.time_data.len()
+                ),
+            });
+        } else {
+            Some(&self.time_data[..t_count])
+        };
+        if let Some(times) = times
+            && let Some(index) = times.windows(2).position(|pair| pair[1] <= pair[0])
+        {
+            return Err(canvas::Error::Params {
+                message: format!(
+                    "time_data must be increasing, {} follows {}",
+                    times[index + 1],
+                    times[index]
+                ),
+            });
+        }
 
         let font_size = self.series_label_font_size.max(10.0);
         // Reserve space at the bottom for the time-axis labels.
-        let x_label_h = if self.x_axis_data.is_empty() {
+        let x_label_h = if self.x_axis_data.is_empty() && times.is_none() {
             0.0
         } else {
             font_size + 6.0
@@ -152,20 +214,54 @@ impl ThemeRiverChart {
             }
         }
 
-        // Peak total across time drives the vertical scale.
-        let mut max_total = 0.0_f32;
-        for &total in totals.iter() {
-            max_total = max_total.max(total);
+        // The first series is drawn on top, so the stack is the last layer
+        // of the baselines; `tops[t]` is where the stack starts downward.
+        let layers: Vec<Vec<f32>> = self
+            .series_list
+            .iter()
+            .rev()
+            .map(|s| (0..t_count).map(|t| val(s, t)).collect())
+            .collect();
+        let baseline = self
+            .stack_baseline
+            .clone()
+            .unwrap_or(StackBaseline::Silhouette);
+        let offsets = get_stack_baselines(&layers, &baseline);
+        let tops: Vec<f32> = offsets
+            .iter()
+            .zip(totals.iter())
+            .map(|(offset, total)| -(offset + total))
+            .collect();
+
+        // The extent of the stack across time drives the vertical scale,
+        // widened to the configured axis range like any value axis.
+        let y_axis_config = get_y_axis_config(&self.y_axis_configs, 0);
+        let (mut min, mut max) = get_stack_domain(&layers, &offsets);
+        if let Some(axis_min) = y_axis_config.axis_min {
+            min = min.min(axis_min);
+        }
+        if let Some(axis_max) = y_axis_config.axis_max {
+            max = max.max(axis_max);
         }
-        if max_total <= 0.0 {
+        if max - min <= 0.0 {
             return c.svg();
         }
         // Leave a small vertical margin so the widest point does not touch edges.
-        let ky = (plot_h * 0.9) / max_total;
+        let ky = (plot_h * 0.9) / (max - min);
         let center_y = plot_h / 2.0;
+        // The stack grows downward on the canvas, so the middle is negated.
+        let middle = -(min + max) / 2.0;
+        let base_at = |t: usize| center_y + (tops[t] - middle) * ky;
 
         let x_at = |t: usize| -> f32 {
-            if t_count == 1 {
+            if let Some(times) = times {
+                let span = (times[t_count - 1] - times[0]) as f32;
+                if span <= 0.0 {
+                    cw / 2.0
+                } else {
+                    (times[t] - times[0]) as f32 / span * cw
+                }
+            } else if t_count == 1 {
                 cw / 2.0
             } else {
                 t as f32 / (t_count - 1) as f32 * cw
@@ -174,15 +270,14 @@ impl ThemeRiverChart {
 
         let alpha = (self.stream_opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
 
-        // ── Stream bands (stacked, centered baseline) ─────────────────────────
+        // ── Stream bands (stacked on the baseline) ────────────────────────────
         for (i, s) in self.series_list.iter().enumerate() {
             let color = get_color(&self.series_colors, s.index.unwrap_or(i)).with_alpha(alpha);
             let mut top_pts: Vec<Point> = Vec::with_capacity(t_count);
             let mut bottom_pts: Vec<Point> = Vec::with_capacity(t_count);
-            for t in 0..t_count {
+            for (t, below) in belows[i].iter().enumerate() {
                 let v = val(s, t);
-                let base = center_y - totals[t] * ky / 2.0;
-                let y_top = base + belows[i][t] * ky;
+                let y_top = base_at(t) + below * ky;
                 let y_bottom = y_top + v * ky;
                 let x = x_at(t);
                 top_pts.push((x, y_top).into());
@@ -190,43 +285,87 @@ impl ThemeRiverChart {
             }
             // Close the band: top edge left→right, bottom edge right→left.
             bottom_pts.reverse();
-            top_pts.extend(bottom_pts);
-            content.polygon(Polygon {
-                fill: Some(color),
-                points: top_pts,
-                ..Default::default()
-            });
+            if self.series_smooth {
+                content.smooth_line_fill(SmoothLineFill {
+                    fill: color.into(),
+                    points: top_pts,
+                    bottom_points: bottom_pts,
+                    ..Default::default()
+                });
+            } else {
+                top_pts.extend(bottom_pts);
+                content.polygon(Polygon {
+                    fill: Some(color),
+                    points: top_pts,
+                    ..Default::default()
+                });
+            }
         }
 
-        // ── Stream labels (name at each stream's widest time step) ─────────────
+        // ── Stream labels (name in each stream's widest part) ─────────────────
+        let xs: Vec<f32> = (0..t_count).map(x_at).collect();
+        // Thickness of a band between the steps, zero outside of the plot.
+        let thickness_at = |thicknesses: &[f32], x: f32| -> f32 {
+            if t_count == 1 {
+                return thicknesses[0];
+            }
+            (0..t_count - 1)
+                .find(|&t| x >= xs[t] && x <= xs[t + 1])
+                .map(|t| {
+                    let width = xs[t + 1] - xs[t];
+                    if width <= 0.0 {
+                        return thicknesses[t];
+                    }
+                    let ratio = (x - xs[t]) / width;
+                    thicknesses[t] + (thicknesses[t + 1] - thicknesses[t]) * ratio
+                })
+                .unwrap_or_default()
+        };
         for (i, s) in self.series_list.iter().enumerate() {
             if s.name.is_empty() {
                 continue;
             }
-            let mut best_t = 0usize;
-            let mut best_v = 0.0_f32;
+            let text_width = measure_text_width_family(&self.font_family, font_size, &s.name)
+                .map(|b| b.width())
+                .unwrap_or_default();
+            let thicknesses: Vec<f32> = (0..t_count).map(|t| val(s, t) * ky).collect();
+            // Pick the step where the band is thickest under the whole label.
+            let mut best: Option<(usize, f32, f32, &str)> = None;
+            let mut best_thickness = 0.0_f32;
             for t in 0..t_count {
-                let v = val(s, t);
-                if v > best_v {
-                    best_v = v;
-                    best_t = t;
+                if thicknesses[t] <= 0.0 {
+                    continue;
+                }
+                // Keep the label inside the plot: anchor the first step's
+                // label to the start and the last step's to the end.
+                let (lx, la, left) = if t == 0 {
+                    (xs[t] + 2.0, "start", xs[t] + 2.0)
+                } else if t + 1 == t_count {
+                    (xs[t] - 2.0, "end", xs[t] - 2.0 - text_width)
+                } else {
+                    (xs[t], "middle", xs[t] - text_width / 2.0)
+                };
+                let right = left + text_width;
+                let thickness = (0..t_count)
+                    .filter(|&j| xs[j] > left && xs[j] < right)
+                    .map(|j| thicknesses[j])
+                    .fold(
+                        thickness_at(&thicknesses, left).min(thickness_at(&thicknesses, right)),
+                        f32::min,
+                    );
+                if thickness > best_thickness {
+                    best_thickness = thickness;
+                    best = Some((t, lx, thicknesses[t], la));
                 }
             }
             // Only label bands thick enough to hold the text.
-            if best_v * ky < font_size {
+            let Some((best_t, lx, band, la)) = best else {
                 continue;
-            }
-            let base = center_y - totals[best_t] * ky / 2.0;
-            let y = base + (belows[i][best_t] + best_v / 2.0) * ky;
-            // Keep the label inside the plot: anchor the first step's label to
-            // the start and the last step's to the end.
-            let (lx, la) = if best_t == 0 {
-                (x_at(best_t) + 2.0, "start")
-            } else if best_t + 1 == t_count {
-                (x_at(best_t) - 2.0, "end")
-            } else {
-                (x_at(best_t), "middle")
             };
+            if best_thickness < font_size {
+                continue;
+            }
+            let y = base_at(best_t) + belows[i][best_t] * ky + band / 2.0;
             content.text(Text {
                 text: s.name.clone(),
                 font_family: Some(self.font_family.clone()),
@@ -242,11 +381,50 @@ impl ThemeRiverChart {
         }
 
         // ── Time-axis labels ──────────────────────────────────────────────────
-        if !self.x_axis_data.is_empty() {
+        let y = plot_h + font_size * 0.8;
+        if let Some(times) = times {
+            let start = times[0];
+            let span = (times[t_count - 1] - start) as f32;
+            let max_labels = (cw / 70.0).floor().max(1.0) as usize;
+            let (ticks, format) = get_time_ticks(start, times[t_count - 1], max_labels);
+            let formatter = if self.time_formatter.is_empty() {
+                format
+            } else {
+                &self.time_formatter
+            };
+            for tick in ticks {
+                let x = if span <= 0.0 {
+                    cw / 2.0
+                } else {
+                    (tick - start) as f32 / span * cw
+                };
+                let text = format_timestamp(tick, formatter);
+                let width = measure_text_width_family(&self.font_family, font_size, &text)
+                    .map(|b| b.width())
+                    .unwrap_or_default();
+                let anchor = if x - width / 2.0 < 0.0 {
+                    "start"
+                } else if x + width / 2.0 > cw {
+                    "end"
+                } else {
+                    "middle"
+                };
+                content.text(Text {
+                    text,
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.x_axis_font_color),
+                    font_size: Some(font_size),
+                    x: Some(x),
+                    y: Some(y),
+                    text_anchor: Some(anchor.to_string()),
+                    dominant_baseline: Some("central".to_string()),
+                    ..Default::default()
+                });
+            }
+        } else if !self.x_axis_data.is_empty() {
             // Subsample so labels do not overlap (~one every ~60px).
             let max_labels = (cw / 60.0).floor().max(1.0) as usize;
             let step = t_count.div_ceil(max_labels).max(1);
-            let y = plot_h + font_size * 0.8;
             for (t, label) in self.x_axis_data.iter().enumerate().take(t_count) {
                 if t % step != 0 && t != t_count - 1 {
                     continue;
@@ -282,7 +460,7 @@ impl ThemeRiverChart {
 #[cfg(test)]
 mod tests {
     use super::ThemeRiverChart;
-    use crate::Series;
+    use crate::{Series, StackBaseline};
     use pretty_assertions::assert_eq;
 
     fn make() -> ThemeRiverChart {
@@ -315,6 +493,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn theme_river_axis_range() {
+        let mut chart = ThemeRiverChart::new(
+            vec![Series::new("Rust".to_string(), vec![10.0, 10.0])],
+            vec![],
+        );
+        chart.legend_show = Some(false);
+        // The stream fills the plot area, less a small margin.
+        assert!(
+            chart
+                .svg()
+                .unwrap()
+                .contains(r#"<polygon points="5,24.5 595,24.5 595,375.5 5,375.5""#)
+        );
+        // The configured range scales the stream to a tenth of that height.
+        chart.y_axis_configs[0].axis_min = Some(-50.0);
+        chart.y_axis_configs[0].axis_max = Some(50.0);
+        assert!(
+            chart
+                .svg()
+                .unwrap()
+                .contains(r#"<polygon points="5,182.4 595,182.4 595,217.5 5,217.5""#)
+        );
+    }
+
     #[test]
     fn theme_river_from_json() {
         let chart = ThemeRiverChart::from_json(
@@ -351,4 +554,94 @@ mod tests {
         let chart = ThemeRiverChart::new(vec![], vec![]);
         assert!(chart.svg().unwrap().starts_with("<svg"));
     }
+
+    #[test]
+    fn theme_river_time_axis() {
+        let mut chart = ThemeRiverChart::new(
+            vec![
+                Series::new(
+                    "Errors".to_string(),
+                    vec![5.0, 12.0, 30.0, 18.0, 10.0, 6.0, 4.0, 8.0],
+                ),
+                Series::new(
+                    "Warnings".to_string(),
+                    vec![20.0, 26.0, 30.0, 45.0, 40.0, 35.0, 30.0, 20.0],
+                ),
+                Series::new(
+                    "Deploys".to_string(),
+                    vec![2.0, 4.0, 6.0, 8.0, 20.0, 30.0, 36.0, 40.0],
+                ),
+                Series::new(
+                    "Info".to_string(),
+                    vec![40.0, 38.0, 30.0, 28.0, 26.0, 24.0, 30.0, 34.0],
+                ),
+            ],
+            vec![],
+        );
+        chart.title_text = "Events".to_string();
+        chart.legend_show = Some(false);
+        chart.series_smooth = true;
+        chart.stack_baseline = Some(StackBaseline::Wiggle);
+        // 2024-01-01, 01-09, 01-12, 02-02, 02-20, 03-05, 03-30 and 04-10
+        chart.time_data = vec![
+            1704067200, 1704758400, 1705017600, 1706832000, 1708387200, 1709596800, 1711756800,
+            1712707200,
+        ];
+        let svg = chart.svg().unwrap();
+        assert_eq!(
+            include_str!("../../asset/theme_river_chart/time_axis.svg"),
+            svg
+        );
+    }
+
+    #[test]
+    fn theme_river_time_axis_json() {
+        let json = r##"{
+            "stack_baseline": "zero",
+            "time_formatter": "%d/%m",
+            "time_data": ["2024-01-01 00:00", "2024-01-02", 1704326400],
+            "series_list": [
+                {"name": "A", "data": [10, 20, 30]},
+                {"name": "B", "data": [30, 20, 10]}
+            ]
+        }"##;
+        let chart = ThemeRiverChart::from_json(json).unwrap();
+        assert_eq!(Some(StackBaseline::Zero), chart.stack_baseline);
+        assert_eq!(vec![1704067200, 1704153600, 1704326400], chart.time_data);
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n01/01\n"));
+        assert!(svg.contains("\n04/01\n"));
+
+        let chart = ThemeRiverChart::from_json(&json.replace(", 1704326400", "")).unwrap();
+        assert_eq!(
+            "Params is invalid: time_data has 2 values for 3 data points",
+            chart.svg().unwrap_err().to_string()
+        );
+        let err = ThemeRiverChart::from_json(&json.replace("1704326400", r#""soon""#))
+            .err()
+            .unwrap();
+        assert_eq!(
+            r#"Params is invalid: time_data value "soon" is invalid"#,
+            err.to_string()
+        );
+        let chart = ThemeRiverChart::from_json(&json.replace("1704326400", "1704067200")).unwrap();
+        assert_eq!(
+            "Params is invalid: time_data must be increasing, 1704067200 follows 1704153600",
+            chart.svg().unwrap_err().to_string()
+        );
+        let err = ThemeRiverChart::from_json(&json.replace("2024-01-02", "2024-02-31"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            r#"Params is invalid: time_data value "2024-02-31" is invalid"#,
+            err.to_string()
+        );
+        let err = ThemeRiverChart::from_json(&json.replace(r#""zero""#, r#""flat""#))
+            .err()
+            .unwrap();
+        assert_eq!(
+            r#"Params is invalid: stack baseline "flat" of "stack_baseline" is invalid"#,
+            err.to_string()
+        );
+    }
 }
@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
@@ -206,7 +206,11 @@ impl TreeChart {
         };
         let value = c.base.fill_option(json, &mut c.y_axis_configs)?;
         if let Some(arr) = value.get("series_data").and_then(|v| v.as_array()) {
-            c.series_data = arr.iter().filter_map(parse_node).collect();
+            c.series_data = arr
+                .iter()
+                .map(parse_node)
+                .filter_map(Result::transpose)
+                .collect::<canvas::Result<_>>()?;
         }
         if let Some(s) = get_string_from_value(&value, "orient") {
             c.orient = Some(s);
@@ -220,6 +224,9 @@ impl TreeChart {
 
     /// Renders the chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         let mut c = Canvas::new_width_xy(self.width, self.height, self.x, self.y);
 
         let axis_top = self.render_header(&mut c);
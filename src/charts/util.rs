@@ -10,7 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::common::AxisScale;
+use super::common::{AxisScale, StackBaseline};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -503,6 +503,75 @@ impl LabelOption {
     }
 }
 
+/// Gets the share of `value` in `total`, zero when the total is zero.
+pub(crate) fn get_percentage(value: f32, total: f32) -> f32 {
+    if total == 0.0 { 0.0 } else { value / total }
+}
+
+/// Gets the offset the first layer of a stack starts at for every x
+/// position. `layers` holds the values of each layer (missing points as
+/// zero), bottom layer first.
+pub(crate) fn get_stack_baselines(layers: &[Vec<f32>], baseline: &StackBaseline) -> Vec<f32> {
+    let count = layers.iter().map(|l| l.len()).max().unwrap_or(0);
+    let value = |i: usize, j: usize| layers[i].get(j).copied().unwrap_or(0.0);
+    let total = |j: usize| (0..layers.len()).map(|i| value(i, j)).sum::<f32>();
+    match baseline {
+        StackBaseline::Zero => vec![0.0; count],
+        StackBaseline::Silhouette => (0..count).map(|j| -total(j) / 2.0).collect(),
+        StackBaseline::Wiggle => {
+            // Byron & Wattenberg's streamgraph baseline: every step moves the
+            // baseline against the weighted change of the layers.
+            let mut offsets = Vec::with_capacity(count);
+            let mut y = 0.0;
+            for j in 0..count {
+                if j > 0 {
+                    let mut s1 = 0.0;
+                    let mut s2 = 0.0;
+                    let mut below = 0.0;
+                    for i in 0..layers.len() {
+                        let current = value(i, j);
+                        let delta = current - value(i, j - 1);
+                        s1 += current;
+                        s2 += (below + delta / 2.0) * current;
+                        below += delta;
+                    }
+                    if s1 != 0.0 {
+                        y -= s2 / s1;
+                    }
+                }
+                offsets.push(y);
+            }
+            // Center the stream over the whole range.
+            let (min, max) = (0..count).fold((f32::MAX, f32::MIN), |(min, max), j| {
+                (min.min(offsets[j]), max.max(offsets[j] + total(j)))
+            });
+            if count > 0 {
+                let shift = (min + max) / 2.0;
+                offsets.iter_mut().for_each(|v| *v -= shift);
+            }
+            offsets
+        }
+    }
+}
+
+/// Gets the range `(min, max)` covered by a stack of `layers` starting at
+/// `baselines`, including the edge of every layer.
+pub(crate) fn get_stack_domain(layers: &[Vec<f32>], baselines: &[f32]) -> (f32, f32) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for (j, baseline) in baselines.iter().enumerate() {
+        let mut value = *baseline;
+        min = min.min(value);
+        max = max.max(value);
+        for layer in layers {
+            value += layer.get(j).copied().unwrap_or(0.0);
+            min = min.min(value);
+            max = max.max(value);
+        }
+    }
+    (min, max)
+}
+
 /// Formats a value with a format label: `{c}` is replaced by the value and
 /// `{t}` by its thousands representation; an empty formatter returns the
 /// value unchanged.
@@ -516,6 +585,17 @@ pub fn format_string(value: &str, formatter: &str) -> String {
     }
 }
 
+/// Formats a value with a label template (see [`format_string`]), using the
+/// thousands representation when the template contains `{t}`.
+pub(crate) fn format_value(value: f32, formatter: &str) -> String {
+    let series_formatter = if formatter.contains(THOUSANDS_FORMAT_LABEL) {
+        THOUSANDS_FORMAT_LABEL
+    } else {
+        ""
+    };
+    format_string(&format_series_value(value, series_formatter), formatter)
+}
+
 pub(crate) fn get_pie_point(cx: f32, cy: f32, r: f32, angle: f32) -> Point {
     let value = angle / 180.0 * std::f32::consts::PI;
     let x = cx + r * value.sin();
@@ -556,9 +636,10 @@ mod tests {
     use crate::{AxisScale, thousands_format_float};
 
     use super::{
-        AxisValueParams, Box, Point, convert_to_points, format_float, get_axis_values,
-        get_box_of_points,
+        AxisValueParams, Box, Point, convert_to_points, format_float, format_value,
+        get_axis_values, get_box_of_points, get_stack_baselines, get_stack_domain,
     };
+    use crate::StackBaseline;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -600,6 +681,10 @@ mod tests {
         assert_eq!("100.1", format_float(100.14));
         assert_eq!("100", format_float(100.04));
         assert_eq!("1000.1", format_float(1000.14));
+
+        assert_eq!("75", format_value(75.0, ""));
+        assert_eq!("7.5%", format_value(7.5, "{c}%"));
+        assert_eq!("12,300 rpm", format_value(12300.0, "{t} rpm"));
     }
     #[test]
     fn thousands_format() {
@@ -668,4 +753,38 @@ mod tests {
         assert_eq!(250.0, b.right);
         assert_eq!(90.0, b.bottom);
     }
+
+    #[test]
+    fn stack_baselines() {
+        let layers = vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]];
+        assert_eq!(
+            vec![0.0, 0.0, 0.0],
+            get_stack_baselines(&layers, &StackBaseline::Zero)
+        );
+        assert_eq!(
+            vec![-2.0, -2.0, -2.0],
+            get_stack_baselines(&layers, &StackBaseline::Silhouette)
+        );
+        // The growing bottom layer pushes the baseline down, then the
+        // whole stream is centered.
+        assert_eq!(
+            vec![-1.5, -2.0, -2.5],
+            get_stack_baselines(&layers, &StackBaseline::Wiggle)
+        );
+
+        let layers = vec![vec![2.0, 4.0], vec![2.0, 2.0]];
+        let offsets = get_stack_baselines(&layers, &StackBaseline::Wiggle);
+        assert_eq!("-1.7", format_float(offsets[0]));
+        assert_eq!("-3", format_float(offsets[1]));
+    }
+
+    #[test]
+    fn stack_domain() {
+        let layers = vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]];
+        let baselines = get_stack_baselines(&layers, &StackBaseline::Silhouette);
+        assert_eq!((-2.0, 2.0), get_stack_domain(&layers, &baselines));
+        // A negative layer reaches below the baseline.
+        let layers = vec![vec![2.0, -3.0], vec![1.0, 1.0]];
+        assert_eq!((-3.0, 3.0), get_stack_domain(&layers, &[0.0, 0.0]));
+    }
 }
@@ -11,13 +11,13 @@
 // limitations under the License.
 
 use super::Canvas;
-use super::base::ChartBase;
+use super::base::{ChartBase, render_color_scheme};
 use super::canvas;
 use super::color::*;
 use super::common::*;
 use super::component::*;
 use super::params::*;
-use super::theme::{DEFAULT_Y_AXIS_WIDTH, get_default_theme_name, get_theme};
+use super::theme::{DEFAULT_Y_AXIS_WIDTH, Theme, get_default_theme_name, get_theme};
 use super::util::*;
 use crate::charts::measure_text_width_family;
 
@@ -33,13 +33,16 @@ pub struct WaterfallData {
     /// When `true` this bar is rendered as a "total" bar that starts at `0` and
     /// spans to the current running sum.  Colors it with `total_color`.
     pub is_total: bool,
+    /// For total bars: the following bars start from `0` again instead of
+    /// continuing the running sum, e.g. for the subtotal of a section.
+    pub reset: bool,
 }
 
 impl From<f32> for WaterfallData {
     fn from(value: f32) -> Self {
         WaterfallData {
             value,
-            is_total: false,
+            ..Default::default()
         }
     }
 }
@@ -49,6 +52,7 @@ impl From<(f32, bool)> for WaterfallData {
         WaterfallData {
             value: v.0,
             is_total: v.1,
+            ..Default::default()
         }
     }
 }
@@ -95,6 +99,9 @@ pub struct WaterfallChart {
 
     /// Fraction of each x-unit occupied by a bar (0..1, default: 0.6).
     pub bar_width_ratio: f32,
+
+    /// Lays the bars out from top to bottom, with the categories on the left.
+    pub horizontal: bool,
 }
 
 impl std::ops::Deref for WaterfallChart {
@@ -110,7 +117,7 @@ impl std::ops::DerefMut for WaterfallChart {
 }
 
 impl WaterfallChart {
-    fn fill_default(&mut self) {
+    fn fill_default(&mut self, t: &Theme) {
         // legend hidden by default (no series names in the usual sense)
         if self.legend_show.is_none() {
             self.legend_show = Some(false);
@@ -119,14 +126,20 @@ impl WaterfallChart {
             self.bar_width_ratio = 0.6;
         }
         if self.increase_color.is_zero() {
-            self.increase_color = get_color(&self.series_colors, 0);
+            self.increase_color = t.waterfall_increase_color;
         }
         if self.total_color.is_zero() {
-            self.total_color = get_color(&self.series_colors, 1);
+            self.total_color = t.waterfall_total_color;
         }
         if self.decrease_color.is_zero() {
-            // warm red not in the default palette – hard-coded
-            self.decrease_color = (238, 102, 102).into(); // #EE6666
+            self.decrease_color = t.waterfall_decrease_color;
+        }
+        // A theme without its own colors follows the series palette.
+        if self.increase_color.is_zero() {
+            self.increase_color = get_color(&self.series_colors, 0);
+        }
+        if self.total_color.is_zero() {
+            self.total_color = get_color(&self.series_colors, 1);
         }
     }
 
@@ -148,8 +161,9 @@ impl WaterfallChart {
             ..Default::default()
         };
         c.x_axis_data = x_axis_data;
-        c.base.fill_theme(get_theme(theme), &mut c.y_axis_configs);
-        c.fill_default();
+        let t = get_theme(theme);
+        c.base.fill_theme(t.clone(), &mut c.y_axis_configs);
+        c.fill_default(&t);
         c
     }
 
@@ -171,31 +185,42 @@ impl WaterfallChart {
         if let Some(v) = get_f32_from_value(&value, "bar_width_ratio") {
             c.bar_width_ratio = v;
         }
-        if let Some(col) = get_color_from_value(&value, "increase_color") {
+        if let Some(col) = get_color_from_value(&value, "increase_color")? {
             c.increase_color = col;
         }
-        if let Some(col) = get_color_from_value(&value, "decrease_color") {
+        if let Some(col) = get_color_from_value(&value, "decrease_color")? {
             c.decrease_color = col;
         }
-        if let Some(col) = get_color_from_value(&value, "total_color") {
+        if let Some(col) = get_color_from_value(&value, "total_color")? {
             c.total_color = col;
         }
+        if let Some(b) = get_bool_from_value(&value, "horizontal") {
+            c.horizontal = b;
+        }
 
-        // parse data: array of either [value, is_total] or bare numbers
+        // parse data: array of [value, is_total, reset], objects or bare numbers
         if let Some(arr) = value.get("data").and_then(|v| v.as_array()) {
             let mut items = vec![];
             for item in arr {
                 if let Some(pair) = item.as_array() {
                     let val = pair.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
                     let is_total = pair.get(1).and_then(|v| v.as_bool()).unwrap_or(false);
+                    let reset = pair.get(2).and_then(|v| v.as_bool()).unwrap_or(false);
                     items.push(WaterfallData {
                         value: val,
                         is_total,
+                        reset,
+                    });
+                } else if item.is_object() {
+                    items.push(WaterfallData {
+                        value: get_f32_from_value(item, "value").unwrap_or_default(),
+                        is_total: get_bool_from_value(item, "is_total").unwrap_or_default(),
+                        reset: get_bool_from_value(item, "reset").unwrap_or_default(),
                     });
                 } else if let Some(v) = item.as_f64() {
                     items.push(WaterfallData {
                         value: v as f32,
-                        is_total: false,
+                        ..Default::default()
                     });
                 }
             }
@@ -205,7 +230,7 @@ impl WaterfallChart {
             c.x_axis_data = x;
         }
 
-        c.fill_default();
+        c.fill_default(&get_theme_from_value(&value));
         Ok(c)
     }
 
@@ -227,8 +252,9 @@ impl WaterfallChart {
                 // If the user set an explicit value, use it; otherwise auto-compute.
                 let display = if item.value != 0.0 { item.value } else { cum };
                 result.push((0.0_f32, display));
-                // Totals reset-accumulate to match the display value for subsequent deltas
-                cum = display;
+                // Totals reset-accumulate to match the display value for subsequent deltas,
+                // or start the next section from zero
+                cum = if item.reset { 0.0 } else { display };
             } else {
                 let bottom = cum;
                 let top = cum + item.value;
@@ -239,8 +265,48 @@ impl WaterfallChart {
         result
     }
 
+    /// Formats the label of a bar: `{c}` is the change, `{s}` the signed
+    /// change and `{r}` the running total after the bar. A total has no change
+    /// of its own, so `{c}` and `{s}` show its running total as well.
+    fn format_label(&self, item: &WaterfallData, (_, end): (f32, f32)) -> String {
+        let formatter = if self.series_label_formatter.is_empty() {
+            "{c}"
+        } else {
+            self.series_label_formatter.as_str()
+        };
+        let (value, signed) = if item.is_total {
+            (end, format_float(end))
+        } else if item.value >= 0.0 {
+            (item.value, format!("+{}", format_float(item.value)))
+        } else {
+            (item.value.abs(), format_float(item.value))
+        };
+        let label_opt = LabelOption {
+            value,
+            formatter: formatter.to_string(),
+            ..Default::default()
+        };
+        label_opt
+            .format()
+            .replace("{s}", &signed)
+            .replace("{r}", &format_float(end))
+    }
+
+    /// Whether the label goes after the end of the bar (above it, or right of
+    /// it when horizontal), otherwise before its start.
+    fn is_label_after(item: &WaterfallData, (_, end): (f32, f32)) -> bool {
+        if item.is_total {
+            end >= 0.0
+        } else {
+            item.value >= 0.0
+        }
+    }
+
     /// Renders the waterfall chart to an SVG string.
     pub fn svg(&self) -> canvas::Result<String> {
+        if let Some(svg) = render_color_scheme(self, |c| &mut c.y_axis_configs, Self::svg) {
+            return svg;
+        }
         if self.data.is_empty() {
             return Err(canvas::Error::Params {
                 message: "data is empty".to_string(),
@@ -262,6 +328,9 @@ impl WaterfallChart {
 
         // ── Compute axis values ───────────────────────────────────────────────
         let cum = self.compute_cumulative();
+        if self.horizontal {
+            return self.render_horizontal(c, axis_top, &cum);
+        }
         // Collect all boundary values for the y-axis range
         let all_vals: Vec<f32> = cum.iter().flat_map(|(b, t)| [*b, *t]).collect();
 
@@ -352,13 +421,6 @@ impl WaterfallChart {
         let bar_w = unit_w * self.bar_width_ratio;
         let bar_margin = (unit_w - bar_w) / 2.0;
 
-        // Label format – {c} = value, {a} = series name (empty here)
-        let formatter = if self.series_label_formatter.is_empty() {
-            "{c}".to_string()
-        } else {
-            self.series_label_formatter.clone()
-        };
-
         let mut draw_c = c.child(Box {
             left: y_axis_width,
             ..Default::default()
@@ -401,13 +463,8 @@ impl WaterfallChart {
 
             // ── Value label ───────────────────────────────────────────────────
             if self.label_show {
-                let label_opt = LabelOption {
-                    value: item.value.abs(),
-                    formatter: formatter.clone(),
-                    ..Default::default()
-                };
-                let label_text = label_opt.format();
-                let label_y = if item.value >= 0.0 || item.is_total {
+                let label_text = self.format_label(item, cum[i]);
+                let label_y = if Self::is_label_after(item, cum[i]) {
                     y_high - 4.0 // above bar
                 } else {
                     y_low + self.series_label_font_size + 2.0 // below bar
@@ -433,7 +490,8 @@ impl WaterfallChart {
             }
 
             // ── Connector line to next bar ────────────────────────────────────
-            if self.connector_line_show && i + 1 < n {
+            // a reset subtotal does not connect, the next bar starts at zero
+            if self.connector_line_show && i + 1 < n && !(item.is_total && item.reset) {
                 // Connect at the top of the running cumulative after this bar
                 let connector_y = y_axis_values.get_offset_height(bar_top_val, max_height);
                 let x_right = x_left + bar_w;
@@ -452,7 +510,8 @@ impl WaterfallChart {
         }
 
         // Zero baseline (solid line at y=0 when there are negative values)
-        let has_negative = self.data.iter().any(|d| d.value < 0.0);
+        let has_negative = self.data.iter().any(|d| d.value < 0.0)
+            || cum.iter().any(|(start, end)| *start < 0.0 || *end < 0.0);
         if has_negative {
             draw_c.line(Line {
                 color: Some(self.x_axis_stroke_color),
@@ -467,6 +526,233 @@ impl WaterfallChart {
 
         c.svg()
     }
+
+    /// Renders the bars from top to bottom with the categories on the left
+    /// and the values on the bottom axis.
+    fn render_horizontal(
+        &self,
+        mut c: Canvas,
+        axis_top: f32,
+        cum: &[(f32, f32)],
+    ) -> canvas::Result<String> {
+        let x_axis_height = if self.x_axis_hidden {
+            0.0
+        } else {
+            self.x_axis_height
+        };
+        let axis_height = c.height() - axis_top - x_axis_height;
+        if axis_top > 0.0 {
+            c = c.child(Box {
+                top: axis_top,
+                ..Default::default()
+            });
+        }
+
+        let mut y_axis_width = 0.0;
+        if !self.y_axis_hidden {
+            for text in self.x_axis_data.iter() {
+                if let Ok(b) =
+                    measure_text_width_family(&self.font_family, self.x_axis_font_size, text)
+                    && b.width() > y_axis_width
+                {
+                    y_axis_width = b.width();
+                }
+            }
+            y_axis_width += 5.0;
+            c.axis(Axis {
+                position: Position::Left,
+                height: axis_height,
+                width: y_axis_width,
+                split_number: self.x_axis_data.len(),
+                font_family: self.font_family.clone(),
+                stroke_color: Some(self.x_axis_stroke_color),
+                name_align: Align::Center,
+                name_gap: self.x_axis_name_gap,
+                font_color: Some(self.x_axis_font_color),
+                font_size: self.x_axis_font_size,
+                data: self.x_axis_data.clone(),
+                ..Default::default()
+            });
+        }
+
+        // the value axis
+        let value_axis_config = &self.y_axis_configs[0];
+        let formatter = value_axis_config.axis_formatter.clone().unwrap_or_default();
+        let value_axis_values = get_axis_values(AxisValueParams {
+            data_list: cum.iter().flat_map(|(b, t)| [*b, *t]).collect(),
+            split_number: value_axis_config.axis_split_number,
+            min: value_axis_config.axis_min,
+            max: value_axis_config.axis_max,
+            thousands_format: formatter.contains(THOUSANDS_FORMAT_LABEL),
+            scale: value_axis_config.axis_scale.clone(),
+            ..Default::default()
+        });
+        // keep room for the labels beside the bars at both ends of the axis
+        let total_width = c.width() - y_axis_width;
+        let mut label_list = vec![];
+        if self.label_show {
+            for (i, item) in self.data.iter().enumerate() {
+                let text = self.format_label(item, cum[i]);
+                let width = measure_text_width_family(
+                    &self.font_family,
+                    self.series_label_font_size,
+                    &text,
+                )
+                .map(|b| b.width() + 4.0)
+                .unwrap_or_default();
+                label_list.push((text, width));
+            }
+        }
+        let get_percent = |value: f32| 1.0 - value_axis_values.get_offset_height(value, 1.0);
+        let (mut left_padding, mut right_padding) = (0.0_f32, 0.0_f32);
+        for _ in 0..2 {
+            let axis_width = total_width - left_padding - right_padding;
+            let (mut left, mut right) = (0.0_f32, 0.0_f32);
+            for (i, (_, width)) in label_list.iter().enumerate() {
+                let (start_val, end_val) = cum[i];
+                if Self::is_label_after(&self.data[i], cum[i]) {
+                    let percent = get_percent(start_val.max(end_val));
+                    right = right.max(width - (1.0 - percent) * axis_width);
+                } else {
+                    let percent = get_percent(start_val.min(end_val));
+                    left = left.max(width - percent * axis_width);
+                }
+            }
+            left_padding = left;
+            right_padding = right;
+        }
+        let axis_left = y_axis_width + left_padding;
+        let axis_width = total_width - left_padding - right_padding;
+
+        if !self.x_axis_hidden {
+            c.child(Box {
+                left: axis_left,
+                top: axis_height,
+                ..Default::default()
+            })
+            .axis(Axis {
+                position: Position::Bottom,
+                height: x_axis_height,
+                width: axis_width,
+                split_number: value_axis_config.axis_split_number,
+                font_family: self.font_family.clone(),
+                stroke_color: Some(value_axis_config.axis_stroke_color),
+                name_align: Align::Left,
+                name_gap: value_axis_config.axis_name_gap,
+                font_color: Some(value_axis_config.axis_font_color),
+                font_size: value_axis_config.axis_font_size,
+                data: value_axis_values
+                    .data
+                    .iter()
+                    .map(|text| format_string(text, &formatter))
+                    .collect(),
+                ..Default::default()
+            });
+        }
+        c.child(Box {
+            left: axis_left,
+            ..Default::default()
+        })
+        .grid(Grid {
+            right: axis_width,
+            bottom: axis_height,
+            color: Some(self.grid_stroke_color),
+            stroke_width: self.grid_stroke_width,
+            verticals: value_axis_config.axis_split_number,
+            hidden_verticals: vec![0],
+            ..Default::default()
+        });
+
+        // ── Render bars ───────────────────────────────────────────────────────
+        let mut draw_c = c.child(Box {
+            left: axis_left,
+            ..Default::default()
+        });
+        let n = self.data.len();
+        let unit_h = axis_height / n as f32;
+        let bar_h = unit_h * self.bar_width_ratio;
+        let bar_margin = (unit_h - bar_h) / 2.0;
+        let get_x =
+            |value: f32| axis_width - value_axis_values.get_offset_height(value, axis_width);
+
+        for (i, item) in self.data.iter().enumerate() {
+            let (start_val, end_val) = cum[i];
+            let x_low = get_x(start_val.min(end_val));
+            let x_high = get_x(start_val.max(end_val));
+            let bar_w = (x_high - x_low).max(1.0);
+            let y_top = i as f32 * unit_h + bar_margin;
+
+            let color = if item.is_total {
+                self.total_color
+            } else if item.value >= 0.0 {
+                self.increase_color
+            } else {
+                self.decrease_color
+            };
+            draw_c.rect(Rect {
+                color: Some(color),
+                fill: Some(color.into()),
+                left: x_low,
+                top: y_top,
+                width: bar_w,
+                height: bar_h,
+                rx: Some(2.0),
+                ry: Some(2.0),
+                ..Default::default()
+            });
+
+            if let Some((label_text, width)) = label_list.get(i) {
+                let label_x = if Self::is_label_after(item, cum[i]) {
+                    x_low + bar_w + 4.0
+                } else {
+                    x_low - width
+                };
+                draw_c.text(Text {
+                    text: label_text.clone(),
+                    font_family: Some(self.font_family.clone()),
+                    font_color: Some(self.series_label_font_color),
+                    font_size: Some(self.series_label_font_size),
+                    font_weight: self.series_label_font_weight.clone(),
+                    dominant_baseline: Some("central".to_string()),
+                    x: Some(label_x),
+                    y: Some(y_top + bar_h / 2.0),
+                    ..Default::default()
+                });
+            }
+
+            // a reset subtotal does not connect, the next bar starts at zero
+            if self.connector_line_show && i + 1 < n && !(item.is_total && item.reset) {
+                let connector_x = get_x(end_val);
+                draw_c.line(Line {
+                    color: Some(self.grid_stroke_color),
+                    stroke_width: 1.0,
+                    stroke_dash_array: Some("4,4".to_string()),
+                    left: connector_x,
+                    top: y_top + bar_h,
+                    right: connector_x,
+                    bottom: y_top + unit_h,
+                });
+            }
+        }
+
+        // Zero baseline when the values or the running sum turn negative
+        let has_negative = self.data.iter().any(|d| d.value < 0.0)
+            || cum.iter().any(|(start, end)| *start < 0.0 || *end < 0.0);
+        if has_negative {
+            let zero_x = get_x(0.0);
+            draw_c.line(Line {
+                color: Some(self.x_axis_stroke_color),
+                stroke_width: 1.0,
+                left: zero_x,
+                top: 0.0,
+                right: zero_x,
+                bottom: axis_height,
+                ..Default::default()
+            });
+        }
+
+        c.svg()
+    }
 }
 
 #[cfg(test)]
@@ -532,4 +818,82 @@ mod tests {
             chart.svg().unwrap()
         );
     }
+
+    #[test]
+    fn waterfall_chart_horizontal() {
+        let mut chart = WaterfallChart::new(
+            vec![
+                200.0.into(),
+                (-500.0).into(),
+                (-120.0).into(),
+                WaterfallData {
+                    is_total: true,
+                    reset: true,
+                    ..Default::default()
+                },
+                800.0.into(),
+                (-50.0).into(),
+                (0.0, true).into(),
+            ],
+            vec![
+                "Opening cash".to_string(),
+                "Operating loss".to_string(),
+                "Capex".to_string(),
+                "Q1 subtotal".to_string(),
+                "Funding".to_string(),
+                "Refund".to_string(),
+                "Q2 subtotal".to_string(),
+            ],
+        );
+        chart.title_text = "P&L Bridge".to_string();
+        chart.horizontal = true;
+        chart.series_label_formatter = "{s} ({r})".to_string();
+        let svg = chart.svg().unwrap();
+        assert_eq!(
+            include_str!("../../asset/waterfall_chart/horizontal.svg"),
+            svg
+        );
+    }
+
+    #[test]
+    fn waterfall_chart_auto_total_label() {
+        let chart = WaterfallChart::from_json(
+            r#"{
+                "x_axis_data": ["Q1", "Q2", "Total", "Fixed"],
+                "data": [100, 73, [0, true], [120, true]]
+            }"#,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        // an auto-computed total shows the running sum, not its zero value
+        assert!(svg.contains("\n173\n"));
+        assert!(svg.contains("\n120\n"));
+    }
+
+    #[test]
+    fn waterfall_chart_reset_json() {
+        let chart = WaterfallChart::from_json(
+            r#"{
+                "series_label_formatter": "{s} ({r})",
+                "x_axis_data": ["Q1", "Loss", "Q1 total", "Q2", "Q2 total"],
+                "data": [
+                    100,
+                    -300,
+                    [0, true, true],
+                    {"value": 50},
+                    {"value": 0, "is_total": true}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let svg = chart.svg().unwrap();
+        assert!(svg.contains("\n+100 (100)\n"));
+        assert!(svg.contains("\n-300 (-200)\n"));
+        assert!(svg.contains("\n-200 (-200)\n"));
+        // the quarter after the reset subtotal starts again from zero
+        assert!(svg.contains("\n+50 (50)\n"));
+        assert!(svg.contains("\n50 (50)\n"));
+        // no connector leaves the reset subtotal
+        assert_eq!(3, svg.matches("stroke-dasharray").count());
+    }
 }
@@ -13,10 +13,10 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
-//! Charts supports twenty-two chart types: bar, horizontal bar, line, pie,
+//! Charts supports twenty-six chart types: bar, horizontal bar, line, pie,
 //! radar, scatter, candlestick, table, heatmap, funnel, waterfall, multi
 //! chart, calendar, gauge, treemap, box plot, sunburst, sankey, tree, graph,
-//! parallel and theme river.
+//! parallel, theme river, polar bar, bullet, stat and sparkline.
 //!
 //! It supports ten built-in themes and is very easy to use.
 //! Each attribute can be customized, it can be saved as svg, png, jpeg,
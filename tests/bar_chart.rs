@@ -224,3 +224,67 @@ fn bar_chart_mixin() {
         bar_chart.svg().unwrap()
     );
 }
+
+#[test]
+fn bar_chart_fill() {
+    let bar_chart = BarChart::from_json(
+        r###"{
+            "width": 630,
+            "height": 410,
+            "series_list": [
+                {
+                    "name": "Email",
+                    "data": [120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0],
+                    "fill": {"type": "linear", "angle": 0, "stops": ["#5470c6", "#91cc75", "#fac858"]}
+                },
+                {
+                    "name": "Union Ads",
+                    "data": [220.0, 182.0, 191.0, 234.0, 290.0, 330.0, 310.0],
+                    "fill": {"type": "pattern", "pattern": "hatch", "color": "#333", "background": "#fff", "size": 6}
+                },
+                {
+                    "name": "Direct",
+                    "data": [320.0, 332.0, 301.0, 334.0, 390.0, 330.0, 320.0],
+                    "fill": {"type": "pattern", "pattern": "dots", "color": "#333", "size": 6}
+                }
+            ],
+            "x_axis_data": ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        }"###,
+    )
+    .unwrap();
+    assert_eq!(
+        include_str!("../asset/bar_chart/fill_json.svg"),
+        bar_chart.svg().unwrap()
+    );
+}
+
+#[test]
+fn bar_chart_label_overlap() {
+    let bar_chart = BarChart::from_json(
+        r###"{
+            "width": 630,
+            "height": 410,
+            "title_text": "Label Overlap",
+            "legend_align": "right",
+            "series_label_overlap": "hide",
+            "series_list": [
+                {
+                    "name": "Email",
+                    "label_show": true,
+                    "data": [120.0, 132.0, 101.0, 134.0, 90.0, 230.0, 210.0, 182.0, 191.0, 234.0, 290.0, 330.0]
+                },
+                {
+                    "name": "Direct",
+                    "label_show": true,
+                    "data": [125.0, 140.0, 105.0, 130.0, 150.0, 236.0, 220.0, 178.0, 199.0, 228.0, 301.0, 334.0]
+                }
+            ],
+            "x_axis_data": ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"]
+        }"###,
+    )
+    .unwrap();
+    assert_eq!(
+        include_str!("../asset/bar_chart/label_overlap_json.svg"),
+        bar_chart.svg().unwrap()
+    );
+}
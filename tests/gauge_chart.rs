@@ -1,4 +1,4 @@
-use charts_rs::GaugeChart;
+use charts_rs::{GaugeChart, Theme, add_theme};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -35,3 +35,20 @@ fn gauge_chart_grafana_json() {
         chart.svg().unwrap()
     );
 }
+
+#[test]
+fn gauge_chart_theme_color() {
+    add_theme(
+        "gauge-test",
+        Theme::from_json(r##"{"extends": "dark", "gauge_background_arc_color": "#123456"}"##)
+            .unwrap(),
+    );
+    let chart = GaugeChart::from_json(
+        r##"{
+            "theme": "gauge-test",
+            "series_list": [{"name": "Speed", "data": [60]}]
+        }"##,
+    )
+    .unwrap();
+    assert!(chart.svg().unwrap().contains(r##"stroke="#123456""##));
+}
@@ -1,4 +1,4 @@
-use charts_rs::{Canvas, Fill, Rect};
+use charts_rs::{Canvas, Fill, PatternKind, Rect};
 
 #[test]
 fn duplicate_gradients_share_one_def() {
@@ -10,7 +10,7 @@ fn duplicate_gradients_share_one_def() {
     let mut canvas = Canvas::new(200.0, 100.0);
     for i in 0..3 {
         canvas.rect(Rect {
-            fill: Some(gradient),
+            fill: Some(gradient.clone()),
             left: i as f32 * 60.0,
             top: 10.0,
             width: 50.0,
@@ -26,7 +26,7 @@ fn duplicate_gradients_share_one_def() {
 
     // A standalone shape keeps emitting its own defs.
     let single = Rect {
-        fill: Some(gradient),
+        fill: Some(gradient.clone()),
         width: 50.0,
         height: 80.0,
         ..Default::default()
@@ -34,3 +34,47 @@ fn duplicate_gradients_share_one_def() {
     .svg();
     assert_eq!(1, single.matches("<linearGradient").count());
 }
+
+#[test]
+fn duplicate_fills_share_one_def() {
+    let fills = [
+        Fill::MultiStopGradient {
+            stops: vec![
+                (0.0, (255, 0, 0).into()).into(),
+                (0.5, (0, 255, 0).into()).into(),
+                (1.0, (0, 0, 255).into()).into(),
+            ],
+            angle: 90.0,
+        },
+        Fill::RadialGradient {
+            stops: vec![
+                (0.0, (255, 255, 255).into()).into(),
+                (1.0, (0, 0, 255).into()).into(),
+            ],
+        },
+        Fill::Pattern {
+            kind: PatternKind::CrossHatch,
+            color: (0, 0, 0).into(),
+            background: (255, 255, 255).into(),
+            size: 8.0,
+        },
+    ];
+    let mut canvas = Canvas::new(400.0, 100.0);
+    for i in 0..6 {
+        canvas.rect(Rect {
+            fill: Some(fills[i % fills.len()].clone()),
+            left: i as f32 * 60.0,
+            top: 10.0,
+            width: 50.0,
+            height: 80.0,
+            ..Default::default()
+        });
+    }
+    let svg = canvas.svg().unwrap();
+    assert_eq!(1, svg.matches("<linearGradient").count());
+    assert_eq!(1, svg.matches("<radialGradient").count());
+    assert_eq!(1, svg.matches("<pattern").count());
+    assert_eq!(2, svg.matches("url(#grad_").count());
+    assert_eq!(2, svg.matches("url(#radial_").count());
+    assert_eq!(2, svg.matches("url(#pattern_").count());
+}
@@ -162,3 +162,49 @@ fn line_chart_nil_value() {
         line_chart.svg().unwrap()
     );
 }
+
+#[test]
+fn line_chart_area_fill() {
+    let line_chart = LineChart::from_json(
+        r###"{
+            "width": 630, "height": 410,
+            "title_text": "Area Fill",
+            "series_fill": true,
+            "series_list": [
+                {"name": "Email",  "data": [120.0, 132.0, 101.0, 134.0,  90.0, 230.0, 210.0],
+                 "fill": {"type": "linear", "stops": [{"offset": 0, "color": "rgba(84, 112, 198, 0.6)"}, {"offset": 1, "color": "rgba(84, 112, 198, 0)"}]}},
+                {"name": "Direct", "data": [150.0, 232.0, 201.0, 154.0, 190.0, 330.0, 410.0],
+                 "fill": {"type": "pattern", "pattern": "hatch", "color": "#91cc75", "size": 6}}
+            ],
+            "x_axis_data": ["Mon","Tue","Wed","Thu","Fri","Sat","Sun"]
+        }"###,
+    )
+    .unwrap();
+    assert_eq!(
+        include_str!("../asset/line_chart/area_fill_json.svg"),
+        line_chart.svg().unwrap()
+    );
+}
+
+#[test]
+fn line_chart_label_overlap() {
+    let line_chart = LineChart::from_json(
+        r###"{
+            "width": 630, "height": 410,
+            "title_text": "Label Overlap",
+            "legend_align": "right",
+            "series_label_overlap": "leader_line",
+            "series_list": [
+                {"name": "Email",  "label_show": true, "data": [120.0, 132.0, 101.0, 134.0,  90.0, 230.0, 210.0]},
+                {"name": "Direct", "label_show": true, "data": [125.0, 140.0, 105.0, 130.0, 150.0, 236.0, 320.0]},
+                {"name": "Search", "label_show": true, "data": [118.0, 128.0, 180.0, 137.0,  95.0, 226.0, 300.0]}
+            ],
+            "x_axis_data": ["Mon","Tue","Wed","Thu","Fri","Sat","Sun"]
+        }"###,
+    )
+    .unwrap();
+    assert_eq!(
+        include_str!("../asset/line_chart/label_overlap_json.svg"),
+        line_chart.svg().unwrap()
+    );
+}
@@ -108,3 +108,42 @@ fn not_rose_radius_pie_chart() {
         pie_chart.svg().unwrap()
     );
 }
+
+fn new_crowded_pie_chart(overlap: &str) -> PieChart {
+    PieChart::from_json(&format!(
+        r###"{{
+            "title_text": "Label Overlap",
+            "legend_show": false,
+            "rose_type": false,
+            "series_label_overlap": "{overlap}",
+            "series_list": [
+                {{"name": "Search Engine", "data": [400]}},
+                {{"name": "Direct", "data": [8]}},
+                {{"name": "Email", "data": [6]}},
+                {{"name": "Union Ads", "data": [5]}},
+                {{"name": "Video Ads", "data": [4]}},
+                {{"name": "Social", "data": [3]}},
+                {{"name": "Other", "data": [2]}}
+            ]
+        }}"###
+    ))
+    .unwrap()
+}
+
+#[test]
+fn pie_chart_label_overlap_nudge() {
+    let pie_chart = new_crowded_pie_chart("nudge");
+    assert_eq!(
+        include_str!("../asset/pie_chart/label_overlap_nudge_json.svg"),
+        pie_chart.svg().unwrap()
+    );
+}
+
+#[test]
+fn pie_chart_label_overlap_hide() {
+    let pie_chart = new_crowded_pie_chart("hide");
+    assert_eq!(
+        include_str!("../asset/pie_chart/label_overlap_hide_json.svg"),
+        pie_chart.svg().unwrap()
+    );
+}